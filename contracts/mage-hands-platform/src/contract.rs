@@ -1,23 +1,58 @@
 use crate::msg::PledgeMinMax;
 use crate::msg::{
-    ContractInfo, ExecuteAnswer, ExecuteMsg, InstantiateMsg, QueryAnswer, QueryMsg, ResponseStatus::Success, space_pad,
+    ExecuteAnswer, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryAnswer, QueryMsg,
+    ResponseStatus::Success, space_pad, ProjectsFilter, ProjectInfo, ProjectSummary, SortOrder,
+    Category, Profile, Backing, Role, AdminAction, AdminLogEntry, DelistReason, Delisting,
+    BackingChange, ExecuteReceiveMsg,
+};
+use crate::matching::{
+    isqrt, next_contribution, quadratic_match, Round, RoundProject, StoredRound, StoredRoundEntry,
+    MAX_ROUND_PROJECTS, MAX_PROJECT_ROUNDS,
+};
+use crate::migrate::migrate_storage;
+use crate::project::{
+    ProjectInstantiateMsg, ProjectMigrateMsg, ProjectExecuteMsg, ProjectStatus, Snip24RewardInit, RewardMessage, TextLimits,
+    MembershipInit, ProjectQueryMsg, ProjectStatusResponse,
 };
-use crate::project::{ProjectInstantiateMsg, Snip24RewardInit, RewardMessage, };
 use crate::state::{
-    add_project, get_config, get_projects, is_creating_project, project_count,
-    set_config, set_creating_project, Config, StoredContractInfo, StoredPledgeMinMax,
+    add_project, get_config, get_projects, project_count,
+    set_config, Config, StoredContractInfo, StoredPledgeMinMax,
+    get_project, set_project, set_contract_version, CONTRACT_VERSION,
+    set_pending_project, take_pending_project, StoredProjectSummary, set_project_summary,
+    get_project_summary, set_project_id, get_project_id, get_project_counts, set_project_counts,
+    get_creator_stats, set_creator_stats, StoredCategory, add_category, set_category,
+    get_categories, category_count, push_creator_project, get_creator_projects, StoredProfile,
+    set_profile, get_profile, get_backings, set_backing, set_paused, is_paused, get_roles, set_roles,
+    get_pending_owner, set_pending_owner, StoredAdminLogEntry, push_admin_log, get_admin_log,
+    admin_log_count, StoredDelisting, set_delisting, get_delisting, get_backing, add_round, get_round,
+    set_round, get_rounds, round_count, round_project_count, push_round_project, get_round_projects,
+    get_project_rounds, set_project_rounds, get_round_entry, set_round_entry, get_round_contribution,
+    set_round_contribution,
 };
+use crate::parse_reply::parse_reply_instantiate_data;
+use crate::error::ContractError;
 use cosmwasm_std::{
-    entry_point, to_binary, Binary, Env, DepsMut, MessageInfo, Addr,
-    Response, StdError, StdResult, Deps, Uint128,
+    entry_point, to_binary, from_binary, Binary, Env, DepsMut, MessageInfo, Addr,
+    Response, StdResult, StdError, Deps, Uint128, WasmMsg, SubMsg, Reply, CanonicalAddr, Storage,
 };
-use secret_toolkit::utils::{ InitCallback, };
+use secret_toolkit::utils::{ InitCallback, HandleCallback, Query, };
+use secret_toolkit::snip20::{ register_receive_msg, transfer_msg, };
 use secret_toolkit::permit::{ validate, RevokedPermits, Permit, };
 
 pub const RESPONSE_BLOCK_SIZE: usize = 256;
 pub const PREFIX_REVOKED_PERMITS: &str = "revoked_permits";
 // 6 sec / block ~= 30 days
 pub const DEFAULT_DEADMAN: u64 = 259200;
+pub const PER_MILLE_DENOM: u64 = 1000;
+pub const DEFAULT_PAGE_SIZE: u32 = 10;
+pub const MAX_PAGE_SIZE: u32 = 50;
+// profile limits in bytes
+pub const MAX_DISPLAY_NAME_LEN: u32 = 64;
+pub const MAX_BIO_LEN: u32 = 1024;
+pub const MAX_URL_LEN: u32 = 256;
+pub const MAX_PROFILE_LINKS: u32 = 5;
+pub const MAX_CONTACT_KEY_LEN: u32 = 128;
+pub const MAX_DELIST_TEXT_LEN: u32 = 1024;
 
 #[entry_point]
 pub fn instantiate(
@@ -25,18 +60,17 @@ pub fn instantiate(
     env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
-) -> StdResult<Response> {
-    let owner;
-    if msg.owner.is_some() {
-        owner = deps.api.addr_canonicalize(msg.owner.unwrap().as_str())?;
-    } else {
-        owner = deps.api.addr_canonicalize(info.sender.as_str())?;
-    }
+) -> Result<Response, ContractError> {
+    let owner = match msg.owner {
+        Some(owner) => deps.api.addr_canonicalize(owner.as_str())?,
+        None => deps.api.addr_canonicalize(info.sender.as_str())?,
+    };
 
-    if msg.token_min_max_pledges.len() < 1 {
-        return Err(StdError::generic_err("You must set at least one snip20 contribution token addr"));
+    if msg.token_min_max_pledges.is_empty() {
+        return Err(ContractError::NoPledgeTokens {});
     }
 
+    set_contract_version(deps.storage, CONTRACT_VERSION)?;
     set_config(
         deps.storage,
         owner,
@@ -45,18 +79,26 @@ pub fn instantiate(
         deps.api.addr_canonicalize(env.contract.address.as_str())?,
         msg.token_min_max_pledges.into_iter().map(|t| {
             StoredPledgeMinMax {
-                token_addr: deps.api.addr_canonicalize(&t.token_addr.as_str()).unwrap(),
+                token_addr: deps.api.addr_canonicalize(t.token_addr.as_str()).unwrap(),
                 min: t.min.u128(),
                 max: t.max.u128(),
+                min_goal: t.min_goal.map(|goal| goal.u128()),
+                max_goal: t.max_goal.map(|goal| goal.u128()),
             }
         }).collect(),
         msg.deadman.unwrap_or(DEFAULT_DEADMAN),
+        msg.max_duration,
+        msg.text_limits.unwrap_or_default(),
     )?;
 
+    for label in msg.categories.unwrap_or_default() {
+        add_category(deps.storage, &StoredCategory { label, active: true })?;
+    }
+
     Ok(Response::new().add_attribute("init", "😎"))
 }
 
-fn pad_response(response: StdResult<Response>) -> StdResult<Response> {
+fn pad_response(response: Result<Response, ContractError>) -> Result<Response, ContractError> {
     response.map(|mut response| {
         response.data = response.data.map(|mut data| {
             space_pad(RESPONSE_BLOCK_SIZE, &mut data.0);
@@ -72,7 +114,7 @@ pub fn execute(
     env: Env, 
     info: MessageInfo, 
     msg: ExecuteMsg
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     let response = match msg {
         ExecuteMsg::Create {
             title,
@@ -88,6 +130,8 @@ pub fn execute(
             snip20_contract,
             snip20_hash,
             snip24_reward_init,
+            bond,
+            membership,
             entropy,
             ..
         } => try_create(
@@ -107,34 +151,75 @@ pub fn execute(
             snip20_contract,
             snip20_hash,
             snip24_reward_init,
+            bond,
+            membership,
             entropy,
         ),
         ExecuteMsg::Config {
-            owner,
             project_contract_code_id,
             project_contract_code_hash,
             token_min_max_pledges,
             deadman,
+            max_duration,
+            clear_max_duration,
+            text_limits,
             ..
         } => try_config(
             deps,
             env,
             info,
-            owner,
             project_contract_code_id,
             project_contract_code_hash,
             token_min_max_pledges,
             deadman,
+            max_duration,
+            clear_max_duration,
+            text_limits,
         ),
-        ExecuteMsg::Register {
-            contract_addr,
-            contract_code_hash,
-        } => try_register(deps, env, info, contract_addr, contract_code_hash),
+        ExecuteMsg::SetCategory { id, label, active, .. } => {
+            try_set_category(deps, env, info, id, label, active)
+        }
+        ExecuteMsg::SetPledgeTokens { token_min_max_pledges, .. } => {
+            try_set_pledge_tokens(deps, env, info, token_min_max_pledges)
+        }
+        ExecuteMsg::SetRole { address, role, granted, .. } => {
+            try_set_role(deps, env, info, address, role, granted)
+        }
+        ExecuteMsg::ProposeOwner { owner, .. } => try_propose_owner(deps, env, info, owner),
+        ExecuteMsg::AcceptOwner { .. } => try_accept_owner(deps, env, info),
+        ExecuteMsg::SetHidden { project_id, hidden, .. } => {
+            try_set_hidden(deps, env, info, project_id, hidden)
+        }
+        ExecuteMsg::ClearSpamFlags { project_id, .. } => try_clear_spam_flags(deps, env, info, project_id),
+        ExecuteMsg::Delist { project_id, reason, text, .. } => {
+            try_delist(deps, env, info, project_id, reason, text)
+        }
+        ExecuteMsg::SetPaused { paused, .. } => try_set_paused(deps, env, info, paused),
+        ExecuteMsg::SetProfile { display_name, bio, avatar_url, links, contact_public_key, .. } => {
+            try_set_profile(deps, env, info, display_name, bio, avatar_url, links, contact_public_key)
+        }
+        ExecuteMsg::UpdateStatus { status } => try_update_status(deps, env, info, status),
+        ExecuteMsg::UpdateHidden { hidden } => try_update_hidden(deps, env, info, hidden),
+        ExecuteMsg::UpdateBacking { backer, amount, change } => {
+            try_update_backing(deps, env, info, backer, amount, change)
+        }
+        ExecuteMsg::SyncStatus { project_ids, .. } => try_sync_status(deps, env, project_ids),
+        ExecuteMsg::CreateRound { token, token_hash, start, end, .. } => {
+            try_create_round(deps, env, info, token, token_hash, start, end)
+        }
+        ExecuteMsg::Receive { from, amount, msg, .. } => try_receive(deps, env, info, from, amount, msg),
+        ExecuteMsg::JoinRound { round_id, project_id, .. } => try_join_round(deps, env, info, round_id, project_id),
+        ExecuteMsg::FinalizeRound { round_id, .. } => try_finalize_round(deps, env, info, round_id),
+        ExecuteMsg::SettleMatch { round_id, project_id, .. } => {
+            try_settle_match(deps, env, info, round_id, project_id)
+        }
+        ExecuteMsg::MigrateProject { project_id, .. } => try_migrate_project(deps, env, info, project_id),
         ExecuteMsg::RevokePermit { permit_name, .. } => revoke_permit(deps, env, info, permit_name),
     };
     pad_response(response)
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn try_create(
     deps: DepsMut,
     env: Env,
@@ -152,28 +237,83 @@ pub fn try_create(
     snip20_contract: Addr,
     snip20_hash: String,
     snip24_reward_init: Option<Snip24RewardInit>,
+    bond: Option<Uint128>,
+    membership: Option<MembershipInit>,
     entropy: String,
-) -> StdResult<Response> {
-    let msg;
-    let mut messages = vec![];
+) -> Result<Response, ContractError> {
+    if is_paused(deps.storage) {
+        return Err(ContractError::Paused {});
+    }
+
     let config: Config = get_config(deps.storage)?;
     let token_min_max_pledges: Vec<PledgeMinMax> = config.token_min_max_pledges
-        .into_iter()
+        .iter()
         .map(|t| {
             PledgeMinMax {
                 token_addr: deps.api.addr_humanize(&t.token_addr).unwrap(),
                 min: Uint128::from(t.min),
                 max: Uint128::from(t.max),
+                min_goal: t.min_goal.map(Uint128::from),
+                max_goal: t.max_goal.map(Uint128::from),
             }
         })
         .filter(|t| { t.token_addr == snip20_contract })
         .collect();
 
-    if token_min_max_pledges.len() < 1 {
-        return Err(StdError::generic_err(format!("{} is not an allowed snip20 contract", snip20_contract.to_string())));
+    if token_min_max_pledges.is_empty() {
+        return Err(ContractError::TokenNotAllowed { token: snip20_contract.to_string() });
     }
 
-    set_creating_project(deps.storage, true)?;
+    // memberships run until cancelled, so only campaigns are held to the goal and duration bounds
+    if membership.is_none() {
+        let min_goal = token_min_max_pledges[0].min_goal.map_or(1, |goal| goal.u128());
+        let max_goal = token_min_max_pledges[0].max_goal.map_or(u128::MAX, |goal| goal.u128());
+        if goal.u128() < min_goal || goal.u128() > max_goal {
+            return Err(ContractError::GoalOutOfBounds { min: min_goal, max: max_goal });
+        }
+
+        if deadline <= env.block.height {
+            return Err(ContractError::DeadlineInPast {});
+        }
+        if let Some(max_duration) = config.max_duration {
+            if deadline - env.block.height > max_duration {
+                return Err(ContractError::DurationTooLong { max: max_duration });
+            }
+        }
+    }
+
+    let limits = &config.text_limits;
+    check_text_len("title", &title, limits.max_title_len)?;
+    check_text_len("subtitle", subtitle.as_deref().unwrap_or_default(), limits.max_subtitle_len)?;
+    check_text_len("description", &description, limits.max_description_len)?;
+    check_text_len("pledged message", pledged_message.as_deref().unwrap_or_default(), limits.max_message_len)?;
+    check_text_len("funded message", funded_message.as_deref().unwrap_or_default(), limits.max_message_len)?;
+    for reward_message in &reward_messages {
+        check_text_len("reward message", &reward_message.message, limits.max_message_len)?;
+    }
+    check_text_len("cover image", &cover_img, limits.max_cover_img_len)?;
+    if !cover_img.is_empty() && !cover_img.starts_with("data:image/") && !cover_img.starts_with("https://") {
+        return Err(ContractError::InvalidCoverImg {});
+    }
+    if categories.len() > limits.max_categories as usize {
+        return Err(ContractError::TooManyCategories { max: limits.max_categories });
+    }
+
+    // the project is registered under this id when its instantiation reply arrives
+    let project_id = project_count(deps.storage)?;
+    let summary = StoredProjectSummary {
+        title: title.clone(),
+        creator: deps.api.addr_canonicalize(info.sender.as_str())?,
+        categories: categories.clone(),
+        goal: goal.u128(),
+        deadline,
+        deadman: config.deadman,
+        token: deps.api.addr_canonicalize(snip20_contract.as_str())?,
+        status: ProjectStatus::Fundraising,
+        hidden: false,
+        membership: membership.is_some(),
+    };
+    set_pending_project(deps.storage, project_id, &summary)?;
 
     let project_init_msg = ProjectInstantiateMsg {
         creator: info.sender,
@@ -195,29 +335,32 @@ pub fn try_create(
         snip20_hash,
         minimum_pledge: token_min_max_pledges[0].min,
         maximum_pledge: token_min_max_pledges[0].max,
+        bond,
+        text_limits: config.text_limits.clone(),
         snip24_reward_init,
+        membership,
         padding: None,
     };
     let label = format!(
         "{}-Mage-Hands-Project-{}-{}",
         &env.contract.address.clone(),
-        project_count(deps.storage)?,
+        project_id,
         &base64::encode(env.block.time.to_string()),
     );
 
-    let config: Config = get_config(deps.storage)?;
-
+    // the platform is the project admin so it can migrate projects to new code
     let cosmos_msg = project_init_msg.to_cosmos_msg(
+        Some(env.contract.address.to_string()),
         label.clone(),
         config.project_contract_code_id,
         String::from_utf8(config.project_contract_code_hash).unwrap_or_default(),
         None,
     )?;
-    messages.push(cosmos_msg);
 
-    msg = format!("Created project contract {}", label);
+    let msg = format!("Created project contract {}", label);
 
-    let mut resp = Response::new().add_messages(messages);
+    let mut resp = Response::new()
+        .add_submessage(SubMsg::reply_on_success(cosmos_msg, project_id as u64));
     resp.data = Some(to_binary(&ExecuteAnswer::Create {
         status: Success,
         msg,
@@ -225,102 +368,80 @@ pub fn try_create(
     Ok(resp)
 }
 
-fn try_register(
-    deps: DepsMut,
-    _env: Env,
-    _info: MessageInfo,
-    contract_addr: Addr,
-    contract_code_hash: String,
-) -> StdResult<Response> {
-    if !is_creating_project(deps.storage) {
-        return Err(StdError::generic_err(format!("Unauthorized")));
-    }
-
-    set_creating_project(deps.storage, false)?;
-    let contract_info = StoredContractInfo {
-        address: deps.api.addr_canonicalize(&contract_addr.as_str())?,
-        code_hash: contract_code_hash.clone(),
-    };
-    let project_id = add_project(deps.storage, contract_info)?;
-
-    let status = Success;
-    let msg = format!("Registered contract {}", contract_addr);
-
-    let mut resp: Response = Response::default();
-    resp.data = Some(to_binary(&ExecuteAnswer::Register {
-        status,
-        msg,
-        project_id,
-        project_address: contract_addr,
-        project_code_hash: contract_code_hash,
-    })?);
-    Ok(resp)
-}
-
+#[allow(clippy::too_many_arguments)]
 fn try_config(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
-    owner: Option<Addr>,
     project_contract_code_id: Option<u64>,
     project_contract_code_hash: Option<String>,
     token_min_max_pledges: Option<Vec<PledgeMinMax>>,
     deadman: Option<u64>,
-) -> StdResult<Response> {
-    let status;
-    let msg;
-
-    let sender_address_raw = deps.api.addr_canonicalize(&info.sender.as_str())?;
+    max_duration: Option<u64>,
+    clear_max_duration: Option<bool>,
+    text_limits: Option<TextLimits>,
+) -> Result<Response, ContractError> {
+    let sender_address_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
     let mut config = get_config(deps.storage)?;
 
     if sender_address_raw != config.owner {
-        return Err(StdError::generic_err(format!("Unauthorized")));
-    }
-
-    if owner.is_some() {
-        config.owner = deps.api.addr_canonicalize(owner.unwrap().as_str())?;
+        return Err(ContractError::Unauthorized {});
     }
 
-    if project_contract_code_id.is_some() {
-        config.project_contract_code_id = project_contract_code_id.unwrap();
+    if let Some(project_contract_code_id) = project_contract_code_id {
+        config.project_contract_code_id = project_contract_code_id;
     }
 
-    if project_contract_code_hash.is_some() {
-        config.project_contract_code_hash = project_contract_code_hash.unwrap().as_bytes().to_vec();
+    if let Some(project_contract_code_hash) = project_contract_code_hash {
+        config.project_contract_code_hash = project_contract_code_hash.as_bytes().to_vec();
     }
 
-    if token_min_max_pledges.is_some() {
-        let token_min_max_pledges = token_min_max_pledges.unwrap();
-        if token_min_max_pledges.len() < 1 {
-            return Err(StdError::generic_err("You must set at least one snip20 contribution token addr"));
+    if let Some(token_min_max_pledges) = token_min_max_pledges {
+        if token_min_max_pledges.is_empty() {
+            return Err(ContractError::NoPledgeTokens {});
         }
         config.token_min_max_pledges = token_min_max_pledges
             .into_iter()
             .map(|t| {
                 StoredPledgeMinMax {
-                    token_addr: deps.api.addr_canonicalize(&t.token_addr.as_str()).unwrap(),
+                    token_addr: deps.api.addr_canonicalize(t.token_addr.as_str()).unwrap(),
                     min: t.min.u128(),
                     max: t.max.u128(),
+                    min_goal: t.min_goal.map(|goal| goal.u128()),
+                    max_goal: t.max_goal.map(|goal| goal.u128()),
                 }
             }).collect();
     }
 
-    if deadman.is_some() {
-        config.deadman = deadman.unwrap();
+    if let Some(deadman) = deadman {
+        config.deadman = deadman;
+    }
+
+    if clear_max_duration.unwrap_or(false) {
+        config.max_duration = None;
+    } else if let Some(max_duration) = max_duration {
+        config.max_duration = Some(max_duration);
+    }
+
+    if let Some(text_limits) = text_limits {
+        config.text_limits = text_limits;
     }
 
     set_config(
         deps.storage,
         config.owner.clone(),
-        config.project_contract_code_id.clone(),
+        config.project_contract_code_id,
         config.project_contract_code_hash.clone(),
         config.contract_address,
         config.token_min_max_pledges,
         config.deadman,
+        config.max_duration,
+        config.text_limits,
     )?;
+    log_admin_action(deps.storage, &env, sender_address_raw, AdminAction::Config)?;
 
-    status = Success;
-    msg = format!(
+    let status = Success;
+    let msg = format!(
         "New config: owner {}, project code id {}, project code hash {}", 
         config.owner,
         config.project_contract_code_id,
@@ -332,12 +453,800 @@ fn try_config(
     Ok(resp)
 }
 
+fn try_set_paused(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    paused: bool,
+) -> Result<Response, ContractError> {
+    let sender_address_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let config = get_config(deps.storage)?;
+
+    if sender_address_raw != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    set_paused(deps.storage, paused)?;
+    log_admin_action(deps.storage, &env, sender_address_raw, AdminAction::SetPaused { paused })?;
+
+    let mut resp = Response::default();
+    resp.data = Some(to_binary(&ExecuteAnswer::SetPaused { status: Success })?);
+    Ok(resp)
+}
+
+fn try_set_category(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    id: Option<u16>,
+    label: String,
+    active: bool,
+) -> Result<Response, ContractError> {
+    let sender_address_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let config = get_config(deps.storage)?;
+
+    if sender_address_raw != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let category = StoredCategory { label, active };
+    let id = match id {
+        Some(id) => {
+            if id as u32 >= category_count(deps.storage)? {
+                return Err(ContractError::CategoryNotFound { id });
+            }
+            set_category(deps.storage, id, &category)?;
+            id
+        }
+        None => add_category(deps.storage, &category)?,
+    };
+    log_admin_action(deps.storage, &env, sender_address_raw, AdminAction::SetCategory { id })?;
+
+    let mut resp = Response::default();
+    resp.data = Some(to_binary(&ExecuteAnswer::SetCategory { status: Success, id })?);
+    Ok(resp)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn try_set_profile(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    display_name: String,
+    bio: Option<String>,
+    avatar_url: Option<String>,
+    links: Option<Vec<String>>,
+    contact_public_key: Option<Binary>,
+) -> Result<Response, ContractError> {
+    let bio = bio.unwrap_or_default();
+    let links = links.unwrap_or_default();
+
+    check_text_len("display name", &display_name, MAX_DISPLAY_NAME_LEN)?;
+    check_text_len("bio", &bio, MAX_BIO_LEN)?;
+    if let Some(ref avatar_url) = avatar_url {
+        check_url("avatar url", avatar_url)?;
+    }
+    if links.len() > MAX_PROFILE_LINKS as usize {
+        return Err(ContractError::TooManyLinks { max: MAX_PROFILE_LINKS });
+    }
+    for link in &links {
+        check_url("link", link)?;
+    }
+    if let Some(ref contact_public_key) = contact_public_key {
+        if contact_public_key.len() > MAX_CONTACT_KEY_LEN as usize {
+            return Err(ContractError::TextTooLong {
+                field: String::from("contact public key"),
+                max: MAX_CONTACT_KEY_LEN,
+            });
+        }
+    }
+
+    set_profile(
+        deps.storage,
+        &deps.api.addr_canonicalize(info.sender.as_str())?,
+        &StoredProfile {
+            display_name,
+            bio,
+            avatar_url,
+            links,
+            contact_public_key: contact_public_key.map(|key| key.0),
+        },
+    )?;
+
+    let mut resp = Response::default();
+    resp.data = Some(to_binary(&ExecuteAnswer::SetProfile { status: Success })?);
+    Ok(resp)
+}
+
+fn check_url(field: &str, url: &str) -> Result<(), ContractError> {
+    check_text_len(field, url, MAX_URL_LEN)?;
+    if !url.starts_with("https://") {
+        return Err(ContractError::InvalidUrl { field: field.to_string() });
+    }
+    Ok(())
+}
+
+fn check_text_len(field: &str, text: &str, max: u32) -> Result<(), ContractError> {
+    if text.len() > max as usize {
+        return Err(ContractError::TextTooLong { field: field.to_string(), max });
+    }
+    Ok(())
+}
+
+fn try_update_status(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    status: ProjectStatus,
+) -> Result<Response, ContractError> {
+    let sender_address_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let project_id = match get_project_id(deps.storage, &sender_address_raw) {
+        Some(project_id) => project_id,
+        None => return Err(ContractError::Unauthorized {}),
+    };
+    if status == ProjectStatus::Unindexed {
+        return Err(ContractError::InvalidStatus {});
+    }
+
+    if let Some(summary) = get_project_summary(deps.storage, project_id) {
+        set_summary_status(deps.storage, project_id, summary, status)?;
+    }
+
+    let mut resp = Response::default();
+    resp.data = Some(to_binary(&ExecuteAnswer::UpdateStatus { status: Success })?);
+    Ok(resp)
+}
+
+// stores a project's new status and moves it in the project and creator stats, returns whether it changed
+fn set_summary_status(
+    storage: &mut dyn Storage,
+    project_id: u32,
+    mut summary: StoredProjectSummary,
+    status: ProjectStatus,
+) -> StdResult<bool> {
+    if summary.status == status {
+        return Ok(false);
+    }
+    let mut counts = get_project_counts(storage);
+    counts.transition(summary.status, status);
+    set_project_counts(storage, &counts)?;
+    if !summary.is_placeholder() {
+        let mut creator_stats = get_creator_stats(storage, &summary.creator);
+        creator_stats.transition(summary.status, status);
+        set_creator_stats(storage, &summary.creator, &creator_stats)?;
+    }
+
+    summary.status = status;
+    set_project_summary(storage, project_id, &summary)?;
+    Ok(true)
+}
+
+fn try_sync_status(
+    deps: DepsMut,
+    env: Env,
+    project_ids: Vec<u32>,
+) -> Result<Response, ContractError> {
+    if project_ids.is_empty() || project_ids.len() > MAX_PAGE_SIZE as usize {
+        return Err(ContractError::InvalidPageSize { max: MAX_PAGE_SIZE });
+    }
+
+    let mut synced = 0_u32;
+    for project_id in project_ids {
+        let summary = get_project_summary(deps.storage, project_id)
+            .ok_or(ContractError::ProjectNotFound { project_id })?;
+        let status = summary.effective_status(env.block.height);
+        if set_summary_status(deps.storage, project_id, summary, status)? {
+            synced += 1;
+        }
+    }
+
+    let mut resp = Response::default();
+    resp.data = Some(to_binary(&ExecuteAnswer::SyncStatus { status: Success, synced })?);
+    Ok(resp)
+}
+
+fn try_update_hidden(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    hidden: bool,
+) -> Result<Response, ContractError> {
+    let sender_address_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let project_id = match get_project_id(deps.storage, &sender_address_raw) {
+        Some(project_id) => project_id,
+        None => return Err(ContractError::Unauthorized {}),
+    };
+
+    set_project_hidden(deps.storage, project_id, hidden)?;
+
+    let mut resp = Response::default();
+    resp.data = Some(to_binary(&ExecuteAnswer::UpdateHidden { status: Success })?);
+    Ok(resp)
+}
+
+fn set_project_hidden(storage: &mut dyn Storage, project_id: u32, hidden: bool) -> StdResult<()> {
+    if let Some(mut summary) = get_project_summary(storage, project_id) {
+        if summary.hidden != hidden {
+            let mut counts = get_project_counts(storage);
+            if hidden {
+                counts.hidden += 1;
+            } else {
+                counts.hidden = counts.hidden.saturating_sub(1);
+            }
+            set_project_counts(storage, &counts)?;
+
+            summary.hidden = hidden;
+            set_project_summary(storage, project_id, &summary)?;
+        }
+    }
+    Ok(())
+}
+
+// the owner passes any role check, other addresses need to hold the given role
+fn check_admin(deps: Deps, sender: &Addr, role: Option<Role>) -> Result<CanonicalAddr, ContractError> {
+    let sender_address_raw = deps.api.addr_canonicalize(sender.as_str())?;
+    let config = get_config(deps.storage)?;
+    if sender_address_raw == config.owner {
+        return Ok(sender_address_raw);
+    }
+    if let Some(role) = role {
+        if get_roles(deps.storage).members(role).contains(&sender_address_raw) {
+            return Ok(sender_address_raw);
+        }
+    }
+    Err(ContractError::Unauthorized {})
+}
+
+fn log_admin_action(
+    storage: &mut dyn Storage,
+    env: &Env,
+    actor: CanonicalAddr,
+    action: AdminAction,
+) -> StdResult<()> {
+    push_admin_log(storage, &StoredAdminLogEntry {
+        actor,
+        action,
+        height: env.block.height,
+        time: env.block.time.seconds(),
+    })
+}
+
+fn try_set_pledge_tokens(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_min_max_pledges: Vec<PledgeMinMax>,
+) -> Result<Response, ContractError> {
+    let sender_address_raw = check_admin(deps.as_ref(), &info.sender, Some(Role::FeeManager))?;
+
+    if token_min_max_pledges.is_empty() {
+        return Err(ContractError::NoPledgeTokens {});
+    }
+    let mut config = get_config(deps.storage)?;
+    config.token_min_max_pledges = token_min_max_pledges
+        .into_iter()
+        .map(|t| {
+            Ok(StoredPledgeMinMax {
+                token_addr: deps.api.addr_canonicalize(t.token_addr.as_str())?,
+                min: t.min.u128(),
+                max: t.max.u128(),
+                min_goal: t.min_goal.map(|goal| goal.u128()),
+                max_goal: t.max_goal.map(|goal| goal.u128()),
+            })
+        })
+        .collect::<StdResult<Vec<StoredPledgeMinMax>>>()?;
+    set_config(
+        deps.storage,
+        config.owner,
+        config.project_contract_code_id,
+        config.project_contract_code_hash,
+        config.contract_address,
+        config.token_min_max_pledges,
+        config.deadman,
+        config.max_duration,
+        config.text_limits,
+    )?;
+    log_admin_action(deps.storage, &env, sender_address_raw, AdminAction::SetPledgeTokens)?;
+
+    let mut resp = Response::default();
+    resp.data = Some(to_binary(&ExecuteAnswer::SetPledgeTokens { status: Success })?);
+    Ok(resp)
+}
+
+fn try_set_role(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    address: Addr,
+    role: Role,
+    granted: bool,
+) -> Result<Response, ContractError> {
+    let sender_address_raw = check_admin(deps.as_ref(), &info.sender, None)?;
+
+    let address_raw = deps.api.addr_canonicalize(address.as_str())?;
+    let mut roles = get_roles(deps.storage);
+    let members = roles.members(role);
+    members.retain(|member| *member != address_raw);
+    if granted {
+        members.push(address_raw);
+    }
+    set_roles(deps.storage, &roles)?;
+    log_admin_action(deps.storage, &env, sender_address_raw, AdminAction::SetRole { address, role, granted })?;
+
+    let mut resp = Response::default();
+    resp.data = Some(to_binary(&ExecuteAnswer::SetRole { status: Success })?);
+    Ok(resp)
+}
+
+fn try_propose_owner(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    owner: Option<Addr>,
+) -> Result<Response, ContractError> {
+    let sender_address_raw = check_admin(deps.as_ref(), &info.sender, None)?;
+
+    let pending_owner = match owner {
+        Some(ref owner) => Some(deps.api.addr_canonicalize(owner.as_str())?),
+        None => None,
+    };
+    set_pending_owner(deps.storage, pending_owner)?;
+    log_admin_action(deps.storage, &env, sender_address_raw, AdminAction::ProposeOwner { owner })?;
+
+    let mut resp = Response::default();
+    resp.data = Some(to_binary(&ExecuteAnswer::ProposeOwner { status: Success })?);
+    Ok(resp)
+}
+
+fn try_accept_owner(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let sender_address_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if get_pending_owner(deps.storage) != Some(sender_address_raw.clone()) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let config = get_config(deps.storage)?;
+    set_config(
+        deps.storage,
+        sender_address_raw.clone(),
+        config.project_contract_code_id,
+        config.project_contract_code_hash,
+        config.contract_address,
+        config.token_min_max_pledges,
+        config.deadman,
+        config.max_duration,
+        config.text_limits,
+    )?;
+    set_pending_owner(deps.storage, None)?;
+    log_admin_action(deps.storage, &env, sender_address_raw, AdminAction::AcceptOwner)?;
+
+    let mut resp = Response::default();
+    resp.data = Some(to_binary(&ExecuteAnswer::AcceptOwner { status: Success })?);
+    Ok(resp)
+}
+
+fn try_set_hidden(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    project_id: u32,
+    hidden: bool,
+) -> Result<Response, ContractError> {
+    let sender_address_raw = check_admin(deps.as_ref(), &info.sender, Some(Role::Moderator))?;
+
+    if project_id >= project_count(deps.storage)? {
+        return Err(ContractError::ProjectNotFound { project_id });
+    }
+    set_project_hidden(deps.storage, project_id, hidden)?;
+    log_admin_action(deps.storage, &env, sender_address_raw, AdminAction::SetHidden { project_id, hidden })?;
+
+    let mut resp = Response::default();
+    resp.data = Some(to_binary(&ExecuteAnswer::SetHidden { status: Success })?);
+    Ok(resp)
+}
+
+fn try_clear_spam_flags(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    project_id: u32,
+) -> Result<Response, ContractError> {
+    let sender_address_raw = check_admin(deps.as_ref(), &info.sender, Some(Role::Moderator))?;
+
+    let project = get_project(deps.storage, project_id)
+        .map_err(|_| ContractError::ProjectNotFound { project_id })?;
+    let clear_msg = ProjectExecuteMsg::ClearSpamFlags {}.to_cosmos_msg(
+        project.code_hash,
+        deps.api.addr_humanize(&project.address)?.into_string(),
+        None,
+    )?;
+    set_project_hidden(deps.storage, project_id, false)?;
+    log_admin_action(deps.storage, &env, sender_address_raw, AdminAction::ClearSpamFlags { project_id })?;
+
+    let mut resp = Response::new().add_message(clear_msg);
+    resp.data = Some(to_binary(&ExecuteAnswer::ClearSpamFlags { status: Success })?);
+    Ok(resp)
+}
+
+fn try_update_backing(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    backer: Addr,
+    amount: Uint128,
+    change: BackingChange,
+) -> Result<Response, ContractError> {
+    let mut resp = Response::default();
+    resp.data = Some(to_binary(&ExecuteAnswer::UpdateBacking { status: Success })?);
+
+    // the callback is part of the project's own transaction, so a project the platform does not
+    // index, e.g. one instantiated directly, must not have its pledges fail here
+    let sender_address_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let project_id = match get_project_id(deps.storage, &sender_address_raw) {
+        Some(project_id) => project_id,
+        None => return Ok(resp),
+    };
+
+    let backer = deps.api.addr_canonicalize(backer.as_str())?;
+    let old_amount = get_backing(deps.storage, &backer, project_id);
+    let amount = amount.u128();
+
+    // keep the contribution sums of any open round the project joined
+    for (round_id, round) in open_project_rounds(deps.storage, project_id, env.block.height)? {
+        if env.block.height < round.start {
+            continue;
+        }
+        let contribution = get_round_contribution(deps.storage, round_id, project_id, &backer);
+        let next = next_contribution(contribution, old_amount, amount, change == BackingChange::Pledge);
+        if next == contribution {
+            continue;
+        }
+        let mut entry = get_round_entry(deps.storage, round_id, project_id).unwrap_or_default();
+        entry.total = entry.total - contribution + next;
+        entry.sqrt_sum = entry.sqrt_sum - isqrt(contribution) + isqrt(next);
+        set_round_entry(deps.storage, round_id, project_id, &entry)?;
+        set_round_contribution(deps.storage, round_id, project_id, &backer, next)?;
+    }
+
+    set_backing(deps.storage, &backer, project_id, amount)?;
+
+    Ok(resp)
+}
+
+// rounds the project joined that have not ended yet, at most MAX_PROJECT_ROUNDS, the ones that
+// ended are dropped from its list
+fn open_project_rounds(
+    storage: &mut dyn Storage,
+    project_id: u32,
+    height: u64,
+) -> StdResult<Vec<(u32, StoredRound)>> {
+    let round_ids = get_project_rounds(storage, project_id);
+    let mut open_rounds = vec![];
+    for round_id in round_ids.iter() {
+        let round = get_round(storage, *round_id)?;
+        if !round.finalized && height < round.end {
+            open_rounds.push((*round_id, round));
+        }
+    }
+    if open_rounds.len() < round_ids.len() {
+        let open_ids: Vec<u32> = open_rounds.iter().map(|(round_id, _)| *round_id).collect();
+        set_project_rounds(storage, project_id, &open_ids)?;
+    }
+    Ok(open_rounds)
+}
+
+fn try_create_round(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token: Addr,
+    token_hash: String,
+    start: u64,
+    end: u64,
+) -> Result<Response, ContractError> {
+    let sender_address_raw = check_admin(deps.as_ref(), &info.sender, Some(Role::RoundManager))?;
+    if is_paused(deps.storage) {
+        return Err(ContractError::Paused {});
+    }
+
+    let config = get_config(deps.storage)?;
+    let token_raw = deps.api.addr_canonicalize(token.as_str())?;
+    if !config.token_min_max_pledges.iter().any(|t| t.token_addr == token_raw) {
+        return Err(ContractError::TokenNotAllowed { token: token.to_string() });
+    }
+    if start >= end {
+        return Err(ContractError::InvalidRound { reason: String::from("Round must start before it ends") });
+    }
+    if end <= env.block.height {
+        return Err(ContractError::InvalidRound { reason: String::from("Round end is in the past") });
+    }
+
+    let round_id = add_round(deps.storage, &StoredRound {
+        sponsor: sender_address_raw.clone(),
+        token: token_raw,
+        token_hash: token_hash.clone(),
+        pool: 0,
+        start,
+        end,
+        finalized: false,
+    })?;
+    log_admin_action(deps.storage, &env, sender_address_raw, AdminAction::CreateRound { round_id })?;
+
+    // the pool is deposited with a snip-20 send
+    let register_msg = register_receive_msg(
+        env.contract.code_hash,
+        None,
+        256,
+        token_hash,
+        token.into_string(),
+    )?;
+
+    let mut resp = Response::new().add_message(register_msg);
+    resp.data = Some(to_binary(&ExecuteAnswer::CreateRound { status: Success, round_id })?);
+    Ok(resp)
+}
+
+fn try_receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    from: Addr,
+    amount: Uint128,
+    msg: Option<Binary>,
+) -> Result<Response, ContractError> {
+    let round_id = match msg {
+        Some(msg) => match from_binary(&msg)? {
+            ExecuteReceiveMsg::FundRound { round_id } => round_id,
+        },
+        None => return Err(ContractError::MissingReceiveMsg {}),
+    };
+    if is_paused(deps.storage) {
+        return Err(ContractError::Paused {});
+    }
+
+    let mut round = get_round(deps.storage, round_id)
+        .map_err(|_| ContractError::RoundNotFound { round_id })?;
+    if deps.api.addr_canonicalize(info.sender.as_str())? != round.token {
+        return Err(ContractError::TokenNotAllowed { token: info.sender.to_string() });
+    }
+    if deps.api.addr_canonicalize(from.as_str())? != round.sponsor {
+        return Err(ContractError::Unauthorized {});
+    }
+    if round.finalized || env.block.height >= round.end {
+        return Err(ContractError::RoundClosed { round_id });
+    }
+
+    round.pool += amount.u128();
+    set_round(deps.storage, round_id, &round)?;
+
+    let mut resp = Response::default();
+    resp.data = Some(to_binary(&ExecuteAnswer::Receive {
+        status: Success,
+        msg: format!("Round {} pool is now {}", round_id, round.pool),
+    })?);
+    Ok(resp)
+}
+
+fn try_join_round(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    round_id: u32,
+    project_id: u32,
+) -> Result<Response, ContractError> {
+    if is_paused(deps.storage) {
+        return Err(ContractError::Paused {});
+    }
+
+    let round = get_round(deps.storage, round_id)
+        .map_err(|_| ContractError::RoundNotFound { round_id })?;
+    if round.finalized || env.block.height >= round.end {
+        return Err(ContractError::RoundClosed { round_id });
+    }
+    let summary = match get_project_summary(deps.storage, project_id) {
+        Some(summary) => summary,
+        None => return Err(ContractError::ProjectNotFound { project_id }),
+    };
+    if deps.api.addr_canonicalize(info.sender.as_str())? != summary.creator {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let not_eligible = |reason: &str| Err(ContractError::NotEligible { reason: reason.to_string() });
+    if summary.token != round.token {
+        return not_eligible("project is funded in a different token");
+    }
+    if get_delisting(deps.storage, project_id).is_some() {
+        return not_eligible("project is delisted");
+    }
+    let status = summary.effective_status(env.block.height);
+    if status != ProjectStatus::Fundraising && status != ProjectStatus::Succeeded {
+        return not_eligible("project is not accepting pledges");
+    }
+    if get_round_entry(deps.storage, round_id, project_id).is_some() {
+        return not_eligible("project already joined");
+    }
+    if round_project_count(deps.storage, round_id)? >= MAX_ROUND_PROJECTS {
+        return not_eligible("round is full");
+    }
+    if open_project_rounds(deps.storage, project_id, env.block.height)?.len() >= MAX_PROJECT_ROUNDS {
+        return not_eligible("project is in too many open rounds");
+    }
+
+    set_round_entry(deps.storage, round_id, project_id, &StoredRoundEntry::default())?;
+    push_round_project(deps.storage, round_id, project_id)?;
+
+    let mut resp = Response::default();
+    resp.data = Some(to_binary(&ExecuteAnswer::JoinRound { status: Success })?);
+    Ok(resp)
+}
+
+fn try_finalize_round(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    round_id: u32,
+) -> Result<Response, ContractError> {
+    let mut round = get_round(deps.storage, round_id)
+        .map_err(|_| ContractError::RoundNotFound { round_id })?;
+    if env.block.height < round.end {
+        return Err(ContractError::RoundNotEnded { end: round.end });
+    }
+    if round.finalized {
+        return Err(ContractError::InvalidRound { reason: String::from("Round is already finalized") });
+    }
+
+    // joining is capped, so this reads at most MAX_ROUND_PROJECTS entries
+    let project_ids: Vec<u32> = get_round_projects(deps.storage, round_id, None, MAX_ROUND_PROJECTS)?
+        .into_iter()
+        .map(|(_, project_id)| project_id)
+        .collect();
+    let mut entries = vec![];
+    let mut scored = vec![];
+    for project_id in &project_ids {
+        let entry = get_round_entry(deps.storage, round_id, *project_id).unwrap_or_default();
+        // projects delisted during the round are not matched
+        if get_delisting(deps.storage, *project_id).is_some() {
+            scored.push(StoredRoundEntry::default());
+        } else {
+            scored.push(entry.clone());
+        }
+        entries.push(entry);
+    }
+    let matches = quadratic_match(round.pool, &scored);
+    for ((project_id, mut entry), matched) in project_ids.iter().zip(entries).zip(&matches) {
+        entry.matched = *matched;
+        set_round_entry(deps.storage, round_id, *project_id, &entry)?;
+    }
+    round.finalized = true;
+    set_round(deps.storage, round_id, &round)?;
+
+    // rounding leftovers, or the whole pool if nothing was matched, go back to the sponsor
+    let leftover = round.pool - matches.iter().sum::<u128>();
+    let mut resp = Response::default();
+    if leftover > 0 {
+        resp = resp.add_message(transfer_msg(
+            deps.api.addr_humanize(&round.sponsor)?.into_string(),
+            Uint128::from(leftover),
+            None,
+            None,
+            256,
+            round.token_hash,
+            deps.api.addr_humanize(&round.token)?.into_string(),
+        )?);
+    }
+    resp.data = Some(to_binary(&ExecuteAnswer::FinalizeRound {
+        status: Success,
+        msg: format!("Matched {} projects, returned {} to the sponsor", project_ids.len(), leftover),
+    })?);
+    Ok(resp)
+}
+
+fn try_settle_match(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    round_id: u32,
+    project_id: u32,
+) -> Result<Response, ContractError> {
+    let round = get_round(deps.storage, round_id)
+        .map_err(|_| ContractError::RoundNotFound { round_id })?;
+    if !round.finalized {
+        return Err(ContractError::InvalidRound { reason: String::from("Round is not finalized") });
+    }
+    let mut entry = match get_round_entry(deps.storage, round_id, project_id) {
+        Some(entry) if entry.matched > 0 && !entry.settled => entry,
+        _ => return Err(ContractError::NothingToSettle {}),
+    };
+
+    // anyone can settle, so the project itself is asked for its status and creator rather than
+    // trusting the summary it last reported
+    let project = get_project(deps.storage, project_id)
+        .map_err(|_| ContractError::ProjectNotFound { project_id })?;
+    let response: ProjectStatusResponse = ProjectQueryMsg::Status {}.query(
+        deps.querier,
+        project.code_hash,
+        deps.api.addr_humanize(&project.address)?.into_string(),
+    )?;
+    let status = response.status.status;
+    let recipient = match status.outcome() {
+        Some(true) => response.status.creator,
+        Some(false) => deps.api.addr_humanize(&round.sponsor)?,
+        None => return Err(ContractError::MatchNotSettleable {}),
+    };
+    if let Some(summary) = get_project_summary(deps.storage, project_id) {
+        set_summary_status(deps.storage, project_id, summary, status)?;
+    }
+
+    entry.settled = true;
+    set_round_entry(deps.storage, round_id, project_id, &entry)?;
+
+    let transfer = transfer_msg(
+        recipient.to_string(),
+        Uint128::from(entry.matched),
+        None,
+        None,
+        256,
+        round.token_hash,
+        deps.api.addr_humanize(&round.token)?.into_string(),
+    )?;
+    let mut resp = Response::new().add_message(transfer);
+    resp.data = Some(to_binary(&ExecuteAnswer::SettleMatch {
+        status: Success,
+        msg: format!("Sent {} to {}", entry.matched, recipient),
+    })?);
+    Ok(resp)
+}
+
+fn try_migrate_project(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    project_id: u32,
+) -> Result<Response, ContractError> {
+    let sender_address_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let config = get_config(deps.storage)?;
+
+    if sender_address_raw != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut project = get_project(deps.storage, project_id)
+        .map_err(|_| ContractError::ProjectNotFound { project_id })?;
+    let project_address = deps.api.addr_humanize(&project.address)?;
+    let code_hash = String::from_utf8(config.project_contract_code_hash).unwrap_or_default();
+
+    let migrate_msg = WasmMsg::Migrate {
+        contract_addr: project_address.to_string(),
+        code_hash: code_hash.clone(),
+        code_id: config.project_contract_code_id,
+        msg: to_binary(&ProjectMigrateMsg {})?,
+    };
+
+    project.code_hash = code_hash;
+    set_project(deps.storage, project_id, &project)?;
+    log_admin_action(deps.storage, &env, sender_address_raw, AdminAction::MigrateProject { project_id })?;
+
+    let mut resp = Response::new().add_message(migrate_msg);
+    resp.data = Some(to_binary(&ExecuteAnswer::MigrateProject {
+        status: Success,
+        msg: format!(
+            "Migrating project {} to code id {}",
+            project_address,
+            config.project_contract_code_id,
+        ),
+    })?);
+    Ok(resp)
+}
+
 fn revoke_permit(
     deps: DepsMut,
     _env: Env,
     info: MessageInfo,
     permit_name: String,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     RevokedPermits::revoke_permit(
         deps.storage,
         PREFIX_REVOKED_PERMITS,
@@ -350,54 +1259,452 @@ fn revoke_permit(
     Ok(resp)
 }
 
+#[entry_point]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    // reply ids are the project id reserved in try_create
+    let project_id = msg.id as u32;
+    let summary = take_pending_project(deps.storage, project_id)
+        .map_err(|_| ContractError::UnknownReplyId { id: msg.id })?;
+    let config = get_config(deps.storage)?;
+
+    let res = parse_reply_instantiate_data(msg)
+        .map_err(|err| StdError::generic_err(err.to_string()))?;
+    let project_address = deps.api.addr_canonicalize(&res.contract_address)?;
+    let contract_info = StoredContractInfo {
+        address: project_address.clone(),
+        code_hash: String::from_utf8(config.project_contract_code_hash).unwrap_or_default(),
+    };
+    if add_project(deps.storage, contract_info)? != project_id {
+        return Err(ContractError::UnknownReplyId { id: project_id as u64 });
+    }
+    set_project_summary(deps.storage, project_id, &summary)?;
+    set_project_id(deps.storage, &project_address, project_id)?;
+    push_creator_project(deps.storage, &summary.creator, project_id)?;
+
+    let mut counts = get_project_counts(deps.storage);
+    if let Some(count) = counts.status_count(summary.status) {
+        *count += 1;
+    }
+    set_project_counts(deps.storage, &counts)?;
+    let mut creator_stats = get_creator_stats(deps.storage, &summary.creator);
+    creator_stats.created += 1;
+    set_creator_stats(deps.storage, &summary.creator, &creator_stats)?;
+
+    let mut resp = Response::new()
+        .add_attribute("project_id", project_id.to_string())
+        .add_attribute("project_address", res.contract_address.clone());
+    resp.data = Some(to_binary(&ExecuteAnswer::Create {
+        status: Success,
+        msg: format!("Registered project {} at {}", project_id, res.contract_address),
+    })?);
+    pad_response(Ok(resp))
+}
+
+#[entry_point]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let from_version = migrate_storage(deps.storage)?;
+    Ok(Response::new()
+        .add_attribute("from_version", from_version.to_string())
+        .add_attribute("to_version", CONTRACT_VERSION.to_string()))
+}
+
 #[entry_point]
 pub fn query(
     deps: Deps, 
-    _env: Env, 
+    env: Env, 
     msg: QueryMsg
-) -> StdResult<Binary> {
+) -> Result<Binary, ContractError> {
     match msg {
-        QueryMsg::Projects { page, page_size } => query_projects(deps, page, page_size,),
-        QueryMsg::ValidatePermit { permit, } => query_validate_permit(deps, permit,),
+        QueryMsg::Projects { start_after, limit, order, filter } => query_projects(
+            deps,
+            env,
+            start_after,
+            limit.unwrap_or(DEFAULT_PAGE_SIZE),
+            order.unwrap_or(SortOrder::Descending),
+            filter.unwrap_or_default(),
+        ),
+        QueryMsg::Categories {} => query_categories(deps),
+        QueryMsg::Roles {} => query_roles(deps),
+        QueryMsg::AdminLog { start_after, limit, order } => query_admin_log(
+            deps,
+            start_after,
+            limit.unwrap_or(DEFAULT_PAGE_SIZE),
+            order.unwrap_or(SortOrder::Descending),
+        ),
+        QueryMsg::Paused {} => Ok(to_binary(&QueryAnswer::Paused { paused: is_paused(deps.storage) })?),
+        QueryMsg::ProjectStats {} => query_project_stats(deps),
+        QueryMsg::CreatorStats { creator } => query_creator_stats(deps, creator),
+        QueryMsg::Profile { address } => query_profile(deps, address),
+        QueryMsg::CreatorProjects { creator, start_after, limit, order } => query_creator_projects(
+            deps,
+            env,
+            creator,
+            start_after,
+            limit.unwrap_or(DEFAULT_PAGE_SIZE),
+            order.unwrap_or(SortOrder::Descending),
+        ),
+        QueryMsg::Rounds { start_after, limit, order } => query_rounds(
+            deps,
+            start_after,
+            limit.unwrap_or(DEFAULT_PAGE_SIZE),
+            order.unwrap_or(SortOrder::Descending),
+        ),
+        QueryMsg::RoundProjects { round_id, start_after, limit } => query_round_projects(
+            deps,
+            round_id,
+            start_after,
+            limit.unwrap_or(DEFAULT_PAGE_SIZE),
+        ),
+        QueryMsg::MyProjects { permit } => query_my_projects(deps, permit),
+        QueryMsg::ValidatePermit { permit, } => Ok(query_validate_permit(deps, permit,)?),
     }
 }
 
 fn query_projects(
     deps: Deps,
-    page: u32,
-    page_size: u32,
-) -> StdResult<Binary> {
-    let mut count = 0_u32;
-    if page_size < 1 {
-        return Err(StdError::generic_err("Invalid page_size"));
-    }
-    let mut projects: Vec<ContractInfo> = vec![];
-    let projects_wrapped = get_projects(deps.storage, page, page_size);
-    if projects_wrapped.is_ok() {
-        projects = projects_wrapped
-            .unwrap()
-            .iter()
-            .map(|project| project.to_humanized(deps.api).unwrap())
+    env: Env,
+    start_after: Option<u32>,
+    limit: u32,
+    order: SortOrder,
+    filter: ProjectsFilter,
+) -> Result<Binary, ContractError> {
+    if !(1..=MAX_PAGE_SIZE).contains(&limit) {
+        return Err(ContractError::InvalidPageSize { max: MAX_PAGE_SIZE });
+    }
+    let creator = match filter.creator {
+        Some(ref creator) => Some(deps.api.addr_canonicalize(creator.as_str())?),
+        None => None,
+    };
+    let token = match filter.token {
+        Some(ref token) => Some(deps.api.addr_canonicalize(token.as_str())?),
+        None => None,
+    };
+    let is_filtered = filter.category.is_some() || filter.status.is_some()
+        || creator.is_some() || token.is_some() || filter.hidden.is_some();
+
+    let delisted = filter.delisted.unwrap_or(false);
+
+    let (stored_projects, next_start_after) = get_projects(deps.storage, start_after, limit, order, |project_id| {
+        if get_delisting(deps.storage, project_id).is_some() != delisted {
+            return false;
+        }
+        if !is_filtered {
+            return true;
+        }
+        let summary = match get_project_summary(deps.storage, project_id) {
+            Some(summary) => summary,
+            None => return false,
+        };
+        let status = summary.effective_status(env.block.height);
+        filter.category.is_none_or(|category| summary.categories.contains(&category))
+            && filter.status.is_none_or(|filter_status| filter_status == status)
+            && creator.as_ref().is_none_or(|creator| *creator == summary.creator)
+            && token.as_ref().is_none_or(|token| *token == summary.token)
+            && filter.hidden.is_none_or(|hidden| hidden == summary.hidden)
+    })?;
+
+    let projects: StdResult<Vec<ProjectInfo>> = stored_projects
+        .into_iter()
+        .map(|(project_id, project)| project_info(deps, &env, project_id, project))
+        .collect();
+    let projects = projects?;
+    let count = project_count(deps.storage)?;
+    Ok(to_binary(&QueryAnswer::Projects { projects, count, next_start_after })?)
+}
+
+fn project_info(
+    deps: Deps,
+    env: &Env,
+    project_id: u32,
+    project: StoredContractInfo,
+) -> StdResult<ProjectInfo> {
+    let contract_info = project.to_humanized(deps.api)?;
+    let summary = match get_project_summary(deps.storage, project_id) {
+        Some(summary) if !summary.is_placeholder() => Some(ProjectSummary {
+            status: summary.effective_status(env.block.height),
+            title: summary.title,
+            creator: deps.api.addr_humanize(&summary.creator)?,
+            categories: summary.categories,
+            goal: Uint128::from(summary.goal),
+            deadline: summary.deadline,
+            token: deps.api.addr_humanize(&summary.token)?,
+            hidden: summary.hidden,
+            membership: summary.membership,
+        }),
+        _ => None,
+    };
+    let delisting = get_delisting(deps.storage, project_id).map(|delisting| Delisting {
+        reason: delisting.reason,
+        text: delisting.text,
+        height: delisting.height,
+        time: delisting.time,
+    });
+    Ok(ProjectInfo {
+        project_id,
+        code_hash: contract_info.code_hash,
+        address: contract_info.address,
+        summary,
+        delisting,
+    })
+}
+
+fn query_profile(deps: Deps, address: Addr) -> Result<Binary, ContractError> {
+    let address = deps.api.addr_canonicalize(address.as_str())?;
+    let profile = get_profile(deps.storage, &address).map(|profile| Profile {
+        display_name: profile.display_name,
+        bio: profile.bio,
+        avatar_url: profile.avatar_url,
+        links: profile.links,
+        contact_public_key: profile.contact_public_key.map(Binary),
+    });
+    Ok(to_binary(&QueryAnswer::Profile { profile })?)
+}
+
+fn query_creator_projects(
+    deps: Deps,
+    env: Env,
+    creator: Addr,
+    start_after: Option<u32>,
+    limit: u32,
+    order: SortOrder,
+) -> Result<Binary, ContractError> {
+    if !(1..=MAX_PAGE_SIZE).contains(&limit) {
+        return Err(ContractError::InvalidPageSize { max: MAX_PAGE_SIZE });
+    }
+    let creator = deps.api.addr_canonicalize(creator.as_str())?;
+    let projects: StdResult<Vec<ProjectInfo>> =
+        get_creator_projects(deps.storage, &creator, start_after, limit, order)?
+            .into_iter()
+            .map(|project_id| project_info(deps, &env, project_id, get_project(deps.storage, project_id)?))
             .collect();
-        count = project_count(deps.storage)?;
+    let stats = get_creator_stats(deps.storage, &creator);
+    Ok(to_binary(&QueryAnswer::CreatorProjects {
+        projects: projects?,
+        created: stats.created,
+        active: stats.created.saturating_sub(stats.successful + stats.unsuccessful),
+        successful: stats.successful,
+        unsuccessful: stats.unsuccessful,
+    })?)
+}
+
+fn try_delist(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    project_id: u32,
+    reason: DelistReason,
+    text: String,
+) -> Result<Response, ContractError> {
+    let sender_address_raw = check_admin(deps.as_ref(), &info.sender, Some(Role::Moderator))?;
+
+    check_text_len("text", &text, MAX_DELIST_TEXT_LEN)?;
+    let project = get_project(deps.storage, project_id)
+        .map_err(|_| ContractError::ProjectNotFound { project_id })?;
+    if get_delisting(deps.storage, project_id).is_some() {
+        return Err(ContractError::AlreadyDelisted { project_id });
     }
-    to_binary(&QueryAnswer::Projects { projects, count })
+
+    // the project reports its new status back through UpdateStatus
+    let delist_msg = ProjectExecuteMsg::Delist {}.to_cosmos_msg(
+        project.code_hash,
+        deps.api.addr_humanize(&project.address)?.into_string(),
+        None,
+    )?;
+    set_delisting(deps.storage, project_id, &StoredDelisting {
+        reason,
+        text,
+        moderator: sender_address_raw.clone(),
+        height: env.block.height,
+        time: env.block.time.seconds(),
+    })?;
+    log_admin_action(deps.storage, &env, sender_address_raw, AdminAction::Delist { project_id, reason })?;
+
+    let mut resp = Response::new().add_message(delist_msg);
+    resp.data = Some(to_binary(&ExecuteAnswer::Delist { status: Success })?);
+    Ok(resp)
+}
+
+fn query_roles(deps: Deps) -> Result<Binary, ContractError> {
+    let config = get_config(deps.storage)?;
+    let roles = get_roles(deps.storage);
+    let humanize_all = |addrs: Vec<CanonicalAddr>| -> StdResult<Vec<Addr>> {
+        addrs.iter().map(|addr| deps.api.addr_humanize(addr)).collect()
+    };
+    let pending_owner = match get_pending_owner(deps.storage) {
+        Some(owner) => Some(deps.api.addr_humanize(&owner)?),
+        None => None,
+    };
+    Ok(to_binary(&QueryAnswer::Roles {
+        owner: deps.api.addr_humanize(&config.owner)?,
+        pending_owner,
+        moderators: humanize_all(roles.moderators)?,
+        fee_managers: humanize_all(roles.fee_managers)?,
+        round_managers: humanize_all(roles.round_managers)?,
+    })?)
+}
+
+fn query_admin_log(
+    deps: Deps,
+    start_after: Option<u32>,
+    limit: u32,
+    order: SortOrder,
+) -> Result<Binary, ContractError> {
+    if !(1..=MAX_PAGE_SIZE).contains(&limit) {
+        return Err(ContractError::InvalidPageSize { max: MAX_PAGE_SIZE });
+    }
+    let entries: StdResult<Vec<AdminLogEntry>> = get_admin_log(deps.storage, start_after, limit, order)?
+        .into_iter()
+        .map(|(id, entry)| Ok(AdminLogEntry {
+            id,
+            actor: deps.api.addr_humanize(&entry.actor)?,
+            action: entry.action,
+            height: entry.height,
+            time: entry.time,
+        }))
+        .collect();
+    Ok(to_binary(&QueryAnswer::AdminLog {
+        entries: entries?,
+        count: admin_log_count(deps.storage)?,
+    })?)
+}
+
+fn query_categories(deps: Deps) -> Result<Binary, ContractError> {
+    let categories = get_categories(deps.storage)?
+        .into_iter()
+        .enumerate()
+        .map(|(id, category)| Category {
+            id: id as u16,
+            label: category.label,
+            active: category.active,
+        })
+        .collect();
+    Ok(to_binary(&QueryAnswer::Categories { categories })?)
+}
+
+fn query_project_stats(deps: Deps) -> Result<Binary, ContractError> {
+    let counts = get_project_counts(deps.storage);
+    Ok(to_binary(&QueryAnswer::ProjectStats {
+        active: counts.fundraising + counts.succeeded,
+        successful: counts.paid_out,
+        fundraising: counts.fundraising,
+        succeeded: counts.succeeded,
+        failed: counts.failed,
+        cancelled: counts.cancelled,
+        paid_out: counts.paid_out,
+        deadman_expired: counts.deadman_expired,
+        defaulted: counts.defaulted,
+        delisted: counts.delisted,
+        hidden: counts.hidden,
+    })?)
+}
+
+fn query_creator_stats(deps: Deps, creator: Addr) -> Result<Binary, ContractError> {
+    let creator = deps.api.addr_canonicalize(creator.as_str())?;
+    let stats = get_creator_stats(deps.storage, &creator);
+    let finished = stats.successful + stats.unsuccessful;
+    let success_rate = match finished {
+        0 => None,
+        _ => Some((stats.successful as u64 * PER_MILLE_DENOM / finished as u64) as u16),
+    };
+    Ok(to_binary(&QueryAnswer::CreatorStats {
+        created: stats.created,
+        active: stats.created.saturating_sub(finished),
+        successful: stats.successful,
+        unsuccessful: stats.unsuccessful,
+        success_rate,
+    })?)
+}
+
+fn query_rounds(
+    deps: Deps,
+    start_after: Option<u32>,
+    limit: u32,
+    order: SortOrder,
+) -> Result<Binary, ContractError> {
+    if !(1..=MAX_PAGE_SIZE).contains(&limit) {
+        return Err(ContractError::InvalidPageSize { max: MAX_PAGE_SIZE });
+    }
+    let rounds: StdResult<Vec<Round>> = get_rounds(deps.storage, start_after, limit, order)?
+        .into_iter()
+        .map(|(id, round)| Ok(Round {
+            id,
+            sponsor: deps.api.addr_humanize(&round.sponsor)?,
+            token: deps.api.addr_humanize(&round.token)?,
+            pool: Uint128::from(round.pool),
+            start: round.start,
+            end: round.end,
+            finalized: round.finalized,
+            project_count: round_project_count(deps.storage, id)?,
+        }))
+        .collect();
+    Ok(to_binary(&QueryAnswer::Rounds {
+        rounds: rounds?,
+        count: round_count(deps.storage)?,
+    })?)
+}
+
+fn query_round_projects(
+    deps: Deps,
+    round_id: u32,
+    start_after: Option<u32>,
+    limit: u32,
+) -> Result<Binary, ContractError> {
+    if !(1..=MAX_PAGE_SIZE).contains(&limit) {
+        return Err(ContractError::InvalidPageSize { max: MAX_PAGE_SIZE });
+    }
+    if round_id >= round_count(deps.storage)? {
+        return Err(ContractError::RoundNotFound { round_id });
+    }
+    let projects = get_round_projects(deps.storage, round_id, start_after, limit)?
+        .into_iter()
+        .map(|(idx, project_id)| {
+            let entry = get_round_entry(deps.storage, round_id, project_id).unwrap_or_default();
+            RoundProject {
+                idx,
+                project_id,
+                matched: Uint128::from(entry.matched),
+                settled: entry.settled,
+            }
+        })
+        .collect();
+    Ok(to_binary(&QueryAnswer::RoundProjects { projects })?)
+}
+
+fn query_my_projects(deps: Deps, permit: Permit) -> Result<Binary, ContractError> {
+    let backer = deps.api.addr_canonicalize(&permit_address(deps, &permit)?)?;
+    let projects: StdResult<Vec<Backing>> = get_backings(deps.storage, &backer)
+        .into_iter()
+        .map(|backing| {
+            let project = get_project(deps.storage, backing.project_id)?;
+            Ok(Backing {
+                project_id: backing.project_id,
+                address: deps.api.addr_humanize(&project.address)?,
+                amount: Uint128::from(backing.amount),
+            })
+        })
+        .collect();
+    Ok(to_binary(&QueryAnswer::MyProjects { projects: projects? })?)
 }
 
 fn query_validate_permit(
     deps: Deps,
     permit: Permit, 
 ) -> StdResult<Binary> {
+    let address = permit_address(deps, &permit)?;
+
+    to_binary(&QueryAnswer::ValidatePermit {
+        address: Addr::unchecked(address),
+    })
+}
+
+// the signer of a permit for this contract
+fn permit_address(deps: Deps, permit: &Permit) -> StdResult<String> {
     let config = get_config(deps.storage)?;
-    let address = validate(
+    validate(
         deps, 
         PREFIX_REVOKED_PERMITS, 
-        &permit, 
+        permit, 
         deps.api.addr_humanize(&config.contract_address)?.into_string(),
         None,
-    )?;
-
-    to_binary(&QueryAnswer::ValidatePermit {
-        address: Addr::unchecked(address),
-    })
+    )
 }
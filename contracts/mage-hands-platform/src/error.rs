@@ -0,0 +1,91 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+// Every failure aborts the transaction.
+// Each variant's message starts with a stable code in brackets that clients can match on.
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("[std] {0}")]
+    Std(#[from] StdError),
+
+    #[error("[unauthorized] Unauthorized")]
+    Unauthorized {},
+
+    #[error("[no_pledge_tokens] You must set at least one snip20 contribution token addr")]
+    NoPledgeTokens {},
+
+    #[error("[token_not_allowed] {token} is not an allowed snip20 contract")]
+    TokenNotAllowed { token: String },
+
+    #[error("[deadline_in_past] Cannot create project with deadline in the past")]
+    DeadlineInPast {},
+
+    #[error("[duration_too_long] Project deadline must be within {max} blocks")]
+    DurationTooLong { max: u64 },
+
+    #[error("[goal_out_of_bounds] Goal must be between {min} and {max}")]
+    GoalOutOfBounds { min: u128, max: u128 },
+
+    #[error("[text_too_long] {field} must be at most {max} bytes")]
+    TextTooLong { field: String, max: u32 },
+
+    #[error("[too_many_categories] Project can have at most {max} categories")]
+    TooManyCategories { max: u32 },
+
+    #[error("[invalid_cover_img] Cover image must be an image data uri or https url")]
+    InvalidCoverImg {},
+
+    #[error("[category_not_found] Category {id} does not exist")]
+    CategoryNotFound { id: u16 },
+
+    #[error("[invalid_url] {field} must be an https url")]
+    InvalidUrl { field: String },
+
+    #[error("[too_many_links] Profile can have at most {max} links")]
+    TooManyLinks { max: u32 },
+
+    #[error("[already_delisted] Project {project_id} is already delisted")]
+    AlreadyDelisted { project_id: u32 },
+
+    #[error("[missing_receive_msg] Tokens must be sent with a message saying what they are for")]
+    MissingReceiveMsg {},
+
+    #[error("[round_not_found] No matching round with id {round_id}")]
+    RoundNotFound { round_id: u32 },
+
+    #[error("[invalid_round] {reason}")]
+    InvalidRound { reason: String },
+
+    #[error("[round_closed] Round {round_id} has ended")]
+    RoundClosed { round_id: u32 },
+
+    #[error("[round_not_ended] Round ends at block {end}")]
+    RoundNotEnded { end: u64 },
+
+    #[error("[not_eligible] Project cannot join this round: {reason}")]
+    NotEligible { reason: String },
+
+    #[error("[match_not_settleable] Project has not paid out or failed yet")]
+    MatchNotSettleable {},
+
+    #[error("[nothing_to_settle] No unsettled match for this project")]
+    NothingToSettle {},
+
+    #[error("[paused] Platform is paused")]
+    Paused {},
+
+    #[error("[invalid_page_size] Page size must be between 1 and {max}")]
+    InvalidPageSize { max: u32 },
+
+    #[error("[invalid_status] Projects cannot report the unindexed status")]
+    InvalidStatus {},
+
+    #[error("[project_not_found] Project {project_id} does not exist")]
+    ProjectNotFound { project_id: u32 },
+
+    #[error("[unknown_reply_id] Unknown reply id: {id}")]
+    UnknownReplyId { id: u64 },
+
+    #[error("[invalid_migration] Cannot migrate from version {from} to {to}")]
+    InvalidMigration { from: u32, to: u32 },
+}
@@ -1,4 +1,8 @@
 pub mod contract;
+pub mod error;
+mod matching;
+pub mod migrate;
 pub mod msg;
+pub mod parse_reply;
 pub mod state;
 mod project;
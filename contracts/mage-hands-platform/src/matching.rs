@@ -0,0 +1,184 @@
+use cosmwasm_std::{Addr, CanonicalAddr, Uint128};
+use primitive_types::U256;
+use serde::{Deserialize, Serialize};
+
+// maximum number of projects in a round, bounds the work done when finalizing it
+pub const MAX_ROUND_PROJECTS: u32 = 100;
+// maximum number of open rounds a project can be in, bounds the work done on every backing update
+pub const MAX_PROJECT_ROUNDS: usize = 10;
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct StoredRound {
+    pub sponsor: CanonicalAddr,
+    pub token: CanonicalAddr,
+    pub token_hash: String,
+    // deposited matching pool
+    pub pool: u128,
+    // contributions count from the start block up to but not including the end block
+    pub start: u64,
+    pub end: u64,
+    pub finalized: bool,
+}
+
+// a project's standing in a round, built from the backing updates the project reports
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct StoredRoundEntry {
+    // sum of the square roots of each backer's contribution during the round
+    pub sqrt_sum: u128,
+    // sum of each backer's contribution during the round
+    pub total: u128,
+    // set when the round is finalized
+    pub matched: u128,
+    pub settled: bool,
+}
+
+// Round sent in query responses
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct Round {
+    pub id: u32,
+    pub sponsor: Addr,
+    pub token: Addr,
+    pub pool: Uint128,
+    pub start: u64,
+    pub end: u64,
+    pub finalized: bool,
+    pub project_count: u32,
+}
+
+// Project standing sent in query responses, contributions stay private
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct RoundProject {
+    // position in join order, used as the query cursor
+    pub idx: u32,
+    pub project_id: u32,
+    pub matched: Uint128,
+    pub settled: bool,
+}
+
+// integer square root, rounded down
+pub fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    // start at n / 2 + 1, at least the root, so x + n / x cannot overflow
+    let mut x = n / 2 + 1;
+    let mut y = (x + n / x) / 2;
+    while y < x {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+    x
+}
+
+// a backer's contribution to a round after their pledge changed from `old` to `new`, only pledges
+// add to it while transfers out and refunds take from it
+pub fn next_contribution(contribution: u128, old: u128, new: u128, is_pledge: bool) -> u128 {
+    if new > old {
+        if is_pledge { contribution + (new - old) } else { contribution }
+    } else {
+        contribution.saturating_sub(old - new)
+    }
+}
+
+// splits the pool in proportion to each project's quadratic funding subsidy,
+// (sum of square roots of contributions)^2 - sum of contributions
+pub fn quadratic_match(pool: u128, entries: &[StoredRoundEntry]) -> Vec<u128> {
+    let subsidies: Vec<U256> = entries
+        .iter()
+        .map(|entry| {
+            let sqrt_sum = U256::from(entry.sqrt_sum);
+            (sqrt_sum * sqrt_sum).saturating_sub(U256::from(entry.total))
+        })
+        .collect();
+    let total_subsidy = subsidies.iter().fold(U256::zero(), |sum, subsidy| sum + *subsidy);
+    if total_subsidy.is_zero() {
+        return vec![0; entries.len()];
+    }
+    subsidies
+        .into_iter()
+        .map(|subsidy| (U256::from(pool) * subsidy / total_subsidy).as_u128())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // entry for a project backed by the given contributions
+    fn entry(contributions: &[u128]) -> StoredRoundEntry {
+        StoredRoundEntry {
+            sqrt_sum: contributions.iter().map(|c| isqrt(*c)).sum(),
+            total: contributions.iter().sum(),
+            ..StoredRoundEntry::default()
+        }
+    }
+
+    #[test]
+    fn isqrt_small_values() {
+        let roots: Vec<u128> = (0..10).map(isqrt).collect();
+        assert_eq!(roots, vec![0, 1, 1, 1, 2, 2, 2, 2, 2, 3]);
+    }
+
+    #[test]
+    fn isqrt_u128_boundaries() {
+        let max_root = u64::MAX as u128;
+        assert_eq!(isqrt(u128::MAX), max_root);
+        assert_eq!(isqrt(max_root * max_root), max_root);
+        assert_eq!(isqrt(max_root * max_root - 1), max_root - 1);
+        assert_eq!(isqrt(1_u128 << 126), 1_u128 << 63);
+        assert_eq!(isqrt((1_u128 << 126) - 1), (1_u128 << 63) - 1);
+    }
+
+    #[test]
+    fn single_backer_gets_no_subsidy() {
+        // a lone backer's (sqrt c)^2 - c is zero, or would be negative after rounding the root down
+        assert_eq!(quadratic_match(1_000, &[entry(&[400])]), vec![0]);
+        assert_eq!(quadratic_match(1_000, &[entry(&[401]), entry(&[100, 100])]), vec![0, 1_000]);
+    }
+
+    #[test]
+    fn more_backers_get_a_larger_match_for_the_same_total() {
+        let entries = [entry(&[400]), entry(&[100; 4]), entry(&[25; 16])];
+        // subsidies are 0, 40^2 - 400 = 1200 and 80^2 - 400 = 6000
+        assert_eq!(quadratic_match(7_200, &entries), vec![0, 1_200, 6_000]);
+    }
+
+    #[test]
+    fn no_subsidy_matches_nothing() {
+        assert_eq!(quadratic_match(1_000, &[]), Vec::<u128>::new());
+        assert_eq!(quadratic_match(1_000, &[entry(&[]), entry(&[50])]), vec![0, 0]);
+    }
+
+    #[test]
+    fn leftover_is_pool_minus_matches() {
+        let pool = 1_000;
+        let entries = [entry(&[100; 4]), entry(&[25; 16]), entry(&[9, 16])];
+        let matches = quadratic_match(pool, &entries);
+        let matched: u128 = matches.iter().sum();
+        assert!(matched <= pool);
+        // each match is rounded down by less than one token
+        assert!(pool - matched < entries.len() as u128);
+        // subsidies 1200, 6000 and 24 split 1000 into 166, 830 and 3
+        assert_eq!(matches, vec![166, 830, 3]);
+        assert_eq!(pool - matched, 1);
+    }
+
+    #[test]
+    fn large_contributions_do_not_overflow() {
+        let matches = quadratic_match(u128::MAX, &[entry(&[u128::MAX / 4; 2]), entry(&[1, 1])]);
+        let matched = matches.iter().try_fold(0_u128, |sum, matched| sum.checked_add(*matched));
+        assert!(matched.is_some());
+        assert!(matches[0] > matches[1]);
+    }
+
+    #[test]
+    fn next_contribution_counts_pledges_only() {
+        // new pledges add to the contribution, increases from transfers in do not
+        assert_eq!(next_contribution(10, 10, 25, true), 25);
+        assert_eq!(next_contribution(10, 10, 25, false), 10);
+        // refunds and transfers out take from it, down to zero
+        assert_eq!(next_contribution(25, 25, 5, false), 5);
+        assert_eq!(next_contribution(10, 40, 0, false), 0);
+        assert_eq!(next_contribution(10, 10, 10, true), 10);
+    }
+}
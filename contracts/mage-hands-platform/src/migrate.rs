@@ -0,0 +1,153 @@
+use cosmwasm_std::{CanonicalAddr, Storage};
+use serde::{Deserialize, Serialize};
+use crate::error::ContractError;
+use crate::project::{ProjectStatus, TextLimits};
+use crate::state::{
+    add_category, category_count, get_bin_data, get_contract_version, set_bin_data,
+    set_contract_version, Config, StoredCategory, StoredPledgeMinMax, CONFIG_KEY, CONTRACT_VERSION,
+    get_project, get_project_id, project_count, set_project_id,
+    set_project_summary, StoredProjectSummary, set_roles, StoredRoles, ROLES_KEY,
+};
+
+// v1 registered projects through a Register callback guarded by a global flag
+const V1_CREATING_PROJECT_FLAG_KEY: &[u8] = b"flag";
+
+// v3 projects used indexes into this list, which was kept by the UI
+const V3_CATEGORIES: [&str; 31] = [
+    "Secret Network", "Crypto", "NFTs",
+    "Art", "Comics & Illustration", "Dance & Theater", "Film", "Music",
+    "Photography", "Podcasts", "Blogs & Vlogs", "Tabletop Games", "Video Games", "Web Series & TV Shows",
+    "Writing and Publishing", "Audio/Video", "Education", "Energy & Green Tech", "Fashion & Wearables",
+    "Food & Beverages", "Health & Fitness", "Home", "Phones & Accessories", "Productivity", "Transportation",
+    "Travel & Outdoors", "Culture", "Environment", "Human Rights", "Local Business", "Wellness",
+];
+
+// config before per-token goal bounds, max duration and text limits
+#[derive(Serialize, Deserialize)]
+struct V2Config {
+    owner: CanonicalAddr,
+    project_contract_code_id: u64,
+    project_contract_code_hash: Vec<u8>,
+    contract_address: CanonicalAddr,
+    token_min_max_pledges: Vec<V2StoredPledgeMinMax>,
+    deadman: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct V2StoredPledgeMinMax {
+    token_addr: CanonicalAddr,
+    min: u128,
+    max: u128,
+}
+
+// roles before round managers
+#[derive(Serialize, Deserialize)]
+struct V4StoredRoles {
+    moderators: Vec<CanonicalAddr>,
+    fee_managers: Vec<CanonicalAddr>,
+}
+
+// runs each upgrade step from the stored version up to CONTRACT_VERSION, returns the stored version
+pub fn migrate_storage(storage: &mut dyn Storage) -> Result<u32, ContractError> {
+    let from_version = get_contract_version(storage);
+    if from_version > CONTRACT_VERSION {
+        return Err(ContractError::InvalidMigration { from: from_version, to: CONTRACT_VERSION });
+    }
+
+    if from_version < 2 {
+        migrate_v1_to_v2(storage)?;
+    }
+    if from_version < 3 {
+        migrate_v2_to_v3(storage)?;
+    }
+    if from_version < 4 {
+        migrate_v3_to_v4(storage)?;
+    }
+    if from_version < 5 {
+        migrate_v4_to_v5(storage)?;
+    }
+
+    set_contract_version(storage, CONTRACT_VERSION)?;
+    Ok(from_version)
+}
+
+// projects are now registered in the instantiate reply, so the flag is no longer used
+fn migrate_v1_to_v2(storage: &mut dyn Storage) -> Result<(), ContractError> {
+    storage.remove(V1_CREATING_PROJECT_FLAG_KEY);
+    index_v1_projects(storage)
+}
+
+// v1 kept no project index, so callbacks from existing projects would be rejected. Each project
+// gets its id and a placeholder summary: the platform never stored its metadata, so it has no
+// creator, categories or token and no deadline. Its status is unindexed, which keeps it out of the
+// project counts, until the project reports one.
+fn index_v1_projects(storage: &mut dyn Storage) -> Result<(), ContractError> {
+    for project_id in 0..project_count(storage)? {
+        let project = get_project(storage, project_id)?;
+        if get_project_id(storage, &project.address).is_some() {
+            continue;
+        }
+        set_project_id(storage, &project.address, project_id)?;
+        set_project_summary(storage, project_id, &StoredProjectSummary {
+            title: String::new(),
+            creator: CanonicalAddr::from(vec![]),
+            categories: vec![],
+            goal: 0,
+            deadline: u64::MAX,
+            deadman: 0,
+            token: CanonicalAddr::from(vec![]),
+            status: ProjectStatus::Unindexed,
+            hidden: false,
+            membership: false,
+        })?;
+    }
+    Ok(())
+}
+
+// existing tokens get no goal bounds, with no max duration and the default text limits
+fn migrate_v2_to_v3(storage: &mut dyn Storage) -> Result<(), ContractError> {
+    let config: V2Config = get_bin_data(storage, CONFIG_KEY)?;
+    set_bin_data(storage, CONFIG_KEY, &Config {
+        owner: config.owner,
+        project_contract_code_id: config.project_contract_code_id,
+        project_contract_code_hash: config.project_contract_code_hash,
+        contract_address: config.contract_address,
+        token_min_max_pledges: config.token_min_max_pledges
+            .into_iter()
+            .map(|t| StoredPledgeMinMax {
+                token_addr: t.token_addr,
+                min: t.min,
+                max: t.max,
+                min_goal: None,
+                max_goal: None,
+            })
+            .collect(),
+        deadman: config.deadman,
+        max_duration: None,
+        text_limits: TextLimits::default(),
+    })?;
+    Ok(())
+}
+
+// seed the category registry with the ids existing projects already use
+fn migrate_v3_to_v4(storage: &mut dyn Storage) -> Result<(), ContractError> {
+    if category_count(storage)? > 0 {
+        return Ok(());
+    }
+    for label in V3_CATEGORIES {
+        add_category(storage, &StoredCategory { label: label.to_string(), active: true })?;
+    }
+    Ok(())
+}
+
+// existing roles are kept, with no round managers
+fn migrate_v4_to_v5(storage: &mut dyn Storage) -> Result<(), ContractError> {
+    if let Ok(roles) = get_bin_data::<V4StoredRoles>(storage, ROLES_KEY) {
+        set_roles(storage, &StoredRoles {
+            moderators: roles.moderators,
+            fee_managers: roles.fee_managers,
+            round_managers: vec![],
+        })?;
+    }
+    Ok(())
+}
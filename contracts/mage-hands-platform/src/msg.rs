@@ -1,8 +1,9 @@
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Binary, Uint128};
 use serde::{Deserialize, Serialize};
 use secret_toolkit::permit::Permit;
 
-use crate::project::{Snip24RewardInit, RewardMessage};
+use crate::matching::{Round, RoundProject};
+use crate::project::{Snip24RewardInit, RewardMessage, ProjectStatus, TextLimits, MembershipInit};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct InstantiateMsg {
@@ -14,17 +15,29 @@ pub struct InstantiateMsg {
     // deadman timeout for successful projects
     pub deadman: Option<u64>,
     pub token_min_max_pledges: Vec<PledgeMinMax>,
+    // maximum number of blocks between Create and the project deadline
+    pub max_duration: Option<u64>,
+    pub text_limits: Option<TextLimits>,
+    // labels of the initial categories, with ids in the given order
+    pub categories: Option<Vec<String>>,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct MigrateMsg {}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct PledgeMinMax {
     pub token_addr: Addr,
     pub min: Uint128,
     pub max: Uint128,
+    // bounds on the goal of projects raising this token
+    pub min_goal: Option<Uint128>,
+    pub max_goal: Option<Uint128>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(rename_all = "snake_case")]
+#[allow(clippy::large_enum_variant)]
 pub enum ExecuteMsg {
     // create a new project
     Create {
@@ -42,21 +55,143 @@ pub enum ExecuteMsg {
         snip20_contract: Addr,
         snip20_hash: String,
         snip24_reward_init: Option<Snip24RewardInit>,
+        // optional creator bond, in the snip20 token
+        bond: Option<Uint128>,
+        // recurring membership with tiers instead of a campaign, goal and deadline are not used
+        membership: Option<MembershipInit>,
         padding: Option<String>,
     },
     // owner only
     Config {
-        owner: Option<Addr>,
         project_contract_code_id: Option<u64>,
         project_contract_code_hash: Option<String>,
         deadman: Option<u64>,
         token_min_max_pledges: Option<Vec<PledgeMinMax>>,
+        max_duration: Option<u64>,
+        // removes the max duration limit, takes precedence over max_duration
+        clear_max_duration: Option<bool>,
+        text_limits: Option<TextLimits>,
+        padding: Option<String>,
+    },
+    // owner only: add a category when id is none, otherwise relabel or retire an existing one
+    SetCategory {
+        id: Option<u16>,
+        label: String,
+        active: bool,
+        padding: Option<String>,
+    },
+    // owner or fee manager: replace the accepted pledge tokens and their bounds
+    SetPledgeTokens {
+        token_min_max_pledges: Vec<PledgeMinMax>,
+        padding: Option<String>,
+    },
+    // owner only: grant or revoke a role
+    SetRole {
+        address: Addr,
+        role: Role,
+        granted: bool,
+        padding: Option<String>,
+    },
+    // owner only: propose a new owner, who takes over once they accept; none cancels a proposal
+    ProposeOwner {
+        owner: Option<Addr>,
+        padding: Option<String>,
+    },
+    // proposed owner only
+    AcceptOwner {
+        padding: Option<String>,
+    },
+    // owner or moderator: hide or unhide a project in the registry
+    SetHidden {
+        project_id: u32,
+        hidden: bool,
+        padding: Option<String>,
+    },
+    // owner or moderator: clear a project's spam flags and unhide it
+    ClearSpamFlags {
+        project_id: u32,
+        padding: Option<String>,
+    },
+    // owner or moderator: remove a project from listings and stop it taking pledges,
+    // existing pledges become refundable
+    Delist {
+        project_id: u32,
+        reason: DelistReason,
+        text: String,
+        padding: Option<String>,
+    },
+    // owner only: while paused, no projects can be created and projects stop taking
+    // contributions and paying out, refunds stay available
+    SetPaused {
+        paused: bool,
+        padding: Option<String>,
+    },
+    // set the sender's public profile, replacing any existing one
+    SetProfile {
+        display_name: String,
+        bio: Option<String>,
+        avatar_url: Option<String>,
+        links: Option<Vec<String>>,
+        contact_public_key: Option<Binary>,
+        padding: Option<String>,
+    },
+    // registered project only: report a status transition
+    UpdateStatus {
+        status: ProjectStatus,
+    },
+    // registered project only: report that the project was hidden or unhidden for spam
+    UpdateHidden {
+        hidden: bool,
+    },
+    // registered project only: report a backer's total pledge, zero when fully refunded
+    UpdateBacking {
+        backer: Addr,
+        amount: Uint128,
+        change: BackingChange,
+    },
+    // anyone: apply deadlines that passed since the projects last reported to their stored
+    // status, moving the project and creator stats
+    SyncStatus {
+        project_ids: Vec<u32>,
+        padding: Option<String>,
+    },
+    // owner or round manager: start a quadratic funding round in an accepted pledge token, the
+    // sender is its sponsor
+    CreateRound {
+        token: Addr,
+        token_hash: String,
+        start: u64,
+        end: u64,
+        padding: Option<String>,
+    },
+    // snip-20 receiver, used to deposit into a round's matching pool
+    Receive {
+        sender: Addr,
+        from: Addr,
+        amount: Uint128,
+        msg: Option<Binary>,
+    },
+    // project creator: enter a project in a round before it ends
+    JoinRound {
+        round_id: u32,
+        project_id: u32,
+        padding: Option<String>,
+    },
+    // split the pool across the round's projects once it has ended
+    FinalizeRound {
+        round_id: u32,
         padding: Option<String>,
     },
-    // register a project contract
-    Register {
-        contract_addr: Addr,
-        contract_code_hash: String,
+    // send a project's match to its creator once paid out, or back to the sponsor if it failed
+    SettleMatch {
+        round_id: u32,
+        project_id: u32,
+        padding: Option<String>,
+    },
+    // owner only: migrate a project to the current project contract code id
+    MigrateProject {
+        project_id: u32,
+        padding: Option<String>,
     },
 
     // Permit
@@ -66,11 +201,28 @@ pub enum ExecuteMsg {
     },
 }
 
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteReceiveMsg {
+    // sponsor only
+    FundRound {
+        round_id: u32,
+    },
+}
+
+// what changed a backer's pledge, only pledges count toward matching
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BackingChange {
+    Pledge,
+    Transfer,
+    Refund,
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
 #[serde(rename_all = "snake_case")]
 pub enum ResponseStatus {
     Success,
-    Failure,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -84,12 +236,73 @@ pub enum ExecuteAnswer {
         status: ResponseStatus,
         msg: String,
     },
-    Register {
+    SetCategory {
+        status: ResponseStatus,
+        id: u16,
+    },
+    SetPledgeTokens {
+        status: ResponseStatus,
+    },
+    SetRole {
+        status: ResponseStatus,
+    },
+    ProposeOwner {
+        status: ResponseStatus,
+    },
+    AcceptOwner {
+        status: ResponseStatus,
+    },
+    SetHidden {
+        status: ResponseStatus,
+    },
+    ClearSpamFlags {
+        status: ResponseStatus,
+    },
+    Delist {
+        status: ResponseStatus,
+    },
+    SetPaused {
+        status: ResponseStatus,
+    },
+    SetProfile {
+        status: ResponseStatus,
+    },
+    UpdateStatus {
+        status: ResponseStatus,
+    },
+    UpdateHidden {
+        status: ResponseStatus,
+    },
+    UpdateBacking {
+        status: ResponseStatus,
+    },
+    SyncStatus {
+        status: ResponseStatus,
+        // projects whose stored status changed
+        synced: u32,
+    },
+    CreateRound {
+        status: ResponseStatus,
+        round_id: u32,
+    },
+    Receive {
+        status: ResponseStatus,
+        msg: String,
+    },
+    JoinRound {
+        status: ResponseStatus,
+    },
+    FinalizeRound {
+        status: ResponseStatus,
+        msg: String,
+    },
+    SettleMatch {
+        status: ResponseStatus,
+        msg: String,
+    },
+    MigrateProject {
         status: ResponseStatus,
         msg: String,
-        project_id: u32,
-        project_address: Addr,
-        project_code_hash: String,
     },
     // Permit
     RevokePermit {
@@ -100,9 +313,61 @@ pub enum ExecuteAnswer {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
-    // gets a paginated list of projects
-    Projects { page: u32, page_size: u32 },
+    // projects after the `start_after` project id, latest first unless order is ascending,
+    // optionally filtered. A page can hold fewer than `limit` projects while more remain,
+    // continue from `next_start_after` until it is none
+    Projects {
+        start_after: Option<u32>,
+        limit: Option<u32>,
+        order: Option<SortOrder>,
+        filter: Option<ProjectsFilter>,
+    },
+
+    // all categories, including retired ones
+    Categories {},
+    Paused {},
+    Roles {},
+    // admin actions after the `start_after` entry id, latest first unless order is ascending
+    AdminLog {
+        start_after: Option<u32>,
+        limit: Option<u32>,
+        order: Option<SortOrder>,
+    },
+    // counts of registered projects by status, as last reported by the projects or applied by
+    // SyncStatus, so a project past its deadline stays counted in its prior status until synced
+    ProjectStats {},
+    // project outcomes for a creator, last reported or synced like ProjectStats
+    CreatorStats {
+        creator: Addr,
+    },
+    Profile {
+        address: Addr,
+    },
+    // projects created by an address after the `start_after` project id, latest first unless
+    // order is ascending
+    CreatorProjects {
+        creator: Addr,
+        start_after: Option<u32>,
+        limit: Option<u32>,
+        order: Option<SortOrder>,
+    },
+    // matching rounds after the `start_after` round id, latest first unless order is ascending
+    Rounds {
+        start_after: Option<u32>,
+        limit: Option<u32>,
+        order: Option<SortOrder>,
+    },
+    // projects in a round in the order they joined, after the `start_after` join index
+    RoundProjects {
+        round_id: u32,
+        start_after: Option<u32>,
+        limit: Option<u32>,
+    },
 
+    // projects backed by the permit signer
+    MyProjects {
+        permit: Permit,
+    },
     ValidatePermit { 
         permit: Permit,
     },
@@ -112,9 +377,72 @@ pub enum QueryMsg {
 #[serde(rename_all = "snake_case")]
 pub enum QueryAnswer {
     Projects {
-        projects: Vec<ContractInfo>,
+        projects: Vec<ProjectInfo>,
+        // number of registered projects
+        count: u32,
+        // last project id examined, none once every project has been examined
+        next_start_after: Option<u32>,
+    },
+    Categories {
+        categories: Vec<Category>,
+    },
+    Paused {
+        paused: bool,
+    },
+    Roles {
+        owner: Addr,
+        pending_owner: Option<Addr>,
+        moderators: Vec<Addr>,
+        fee_managers: Vec<Addr>,
+        round_managers: Vec<Addr>,
+    },
+    AdminLog {
+        entries: Vec<AdminLogEntry>,
+        count: u32,
+    },
+    ProjectStats {
+        // fundraising or succeeded and not yet paid out
+        active: u32,
+        // paid out to the creator
+        successful: u32,
+        fundraising: u32,
+        succeeded: u32,
+        failed: u32,
+        cancelled: u32,
+        paid_out: u32,
+        deadman_expired: u32,
+        defaulted: u32,
+        delisted: u32,
+        hidden: u32,
+    },
+    CreatorStats {
+        created: u32,
+        active: u32,
+        successful: u32,
+        unsuccessful: u32,
+        // successful projects per thousand finished projects, none if no project has finished
+        success_rate: Option<u16>,
+    },
+    Profile {
+        profile: Option<Profile>,
+    },
+    CreatorProjects {
+        projects: Vec<ProjectInfo>,
+        created: u32,
+        active: u32,
+        successful: u32,
+        unsuccessful: u32,
+    },
+    Rounds {
+        rounds: Vec<Round>,
         count: u32,
     },
+    RoundProjects {
+        projects: Vec<RoundProject>,
+    },
+    MyProjects {
+        projects: Vec<Backing>,
+    },
     ValidatePermit {
         address: Addr,
     },
@@ -129,6 +457,127 @@ pub struct ContractInfo {
     pub address: Addr,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Backing {
+    pub project_id: u32,
+    pub address: Addr,
+    pub amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Profile {
+    pub display_name: String,
+    pub bio: String,
+    pub avatar_url: Option<String>,
+    pub links: Vec<String>,
+    pub contact_public_key: Option<Binary>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Category {
+    pub id: u16,
+    pub label: String,
+    pub active: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    // hides projects and clears spam flags
+    Moderator,
+    // sets the accepted pledge tokens and their bounds
+    FeeManager,
+    // creates quadratic funding rounds and sponsors their pools
+    RoundManager,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AdminAction {
+    Config,
+    SetCategory { id: u16 },
+    SetPledgeTokens,
+    SetRole { address: Addr, role: Role, granted: bool },
+    ProposeOwner { owner: Option<Addr> },
+    AcceptOwner,
+    SetHidden { project_id: u32, hidden: bool },
+    ClearSpamFlags { project_id: u32 },
+    Delist { project_id: u32, reason: DelistReason },
+    SetPaused { paused: bool },
+    MigrateProject { project_id: u32 },
+    CreateRound { round_id: u32 },
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DelistReason {
+    Scam,
+    Illegal,
+    Spam,
+    TermsViolation,
+    Other,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Delisting {
+    pub reason: DelistReason,
+    pub text: String,
+    pub height: u64,
+    pub time: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AdminLogEntry {
+    pub id: u32,
+    pub actor: Addr,
+    pub action: AdminAction,
+    pub height: u64,
+    pub time: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+// every set field must match; projects without a summary only match an empty filter, and
+// delisted projects are left out unless delisted is set
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct ProjectsFilter {
+    pub category: Option<u16>,
+    pub status: Option<ProjectStatus>,
+    pub creator: Option<Addr>,
+    pub token: Option<Addr>,
+    pub hidden: Option<bool>,
+    pub delisted: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct ProjectInfo {
+    pub project_id: u32,
+    pub code_hash: String,
+    pub address: Addr,
+    // none for projects registered before summaries were stored
+    pub summary: Option<ProjectSummary>,
+    pub delisting: Option<Delisting>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct ProjectSummary {
+    pub title: String,
+    pub creator: Addr,
+    pub categories: Vec<u16>,
+    pub goal: Uint128,
+    pub deadline: u64,
+    pub token: Addr,
+    // last reported status, with any passed deadline applied
+    pub status: ProjectStatus,
+    pub hidden: bool,
+    pub membership: bool,
+}
+
 // Take a Vec<u8> and pad it up to a multiple of `block_size`, using spaces at the end.
 pub fn space_pad(block_size: usize, message: &mut Vec<u8>) -> &mut Vec<u8> {
     let len = message.len();
@@ -139,6 +588,6 @@ pub fn space_pad(block_size: usize, message: &mut Vec<u8>) -> &mut Vec<u8> {
 
     let missing = block_size - surplus;
     message.reserve(missing);
-    message.extend(std::iter::repeat(b' ').take(missing));
+    message.extend(std::iter::repeat_n(b' ', missing));
     message
 }
\ No newline at end of file
@@ -1,6 +1,6 @@
 
 use cosmwasm_std::{Addr, Uint128};
-use secret_toolkit::utils::InitCallback;
+use secret_toolkit::utils::{HandleCallback, InitCallback, Query};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
@@ -31,13 +31,44 @@ pub struct ProjectInstantiateMsg {
     // minimum and maximum pledge amounts
     pub minimum_pledge: Uint128,
     pub maximum_pledge: Uint128,
+    // bond the creator must deposit in the contribution token before pledges are accepted
+    pub bond: Option<Uint128>,
+    // limits applied when the creator changes the project text
+    pub text_limits: TextLimits,
 
     // new snip24
     pub snip24_reward_init: Option<Snip24RewardInit>,
+    // recurring membership, goal and deadline are not used
+    pub membership: Option<MembershipInit>,
 
     pub padding: Option<String>,
 }
 
+// maximum lengths in bytes for project text, and the maximum number of categories
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug,)]
+pub struct TextLimits {
+    pub max_title_len: u32,
+    pub max_subtitle_len: u32,
+    pub max_description_len: u32,
+    // pledged, funded and reward messages
+    pub max_message_len: u32,
+    pub max_cover_img_len: u32,
+    pub max_categories: u32,
+}
+
+impl Default for TextLimits {
+    fn default() -> Self {
+        TextLimits {
+            max_title_len: 128,
+            max_subtitle_len: 256,
+            max_description_len: 32768,
+            max_message_len: 4096,
+            max_cover_img_len: 65536,
+            max_categories: 5,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug,)]
 pub struct RewardMessage {
     pub threshold: Uint128,
@@ -70,12 +101,47 @@ pub struct Snip24RewardInit {
     // contribution weighting: one of linear, sqrt, or log
     // weighting is applied after minimum and maximum is applied
     pub contribution_weight: u8,
+    // optional bonus for early pledges
+    pub bonus_curve: Option<RewardBonusCurve>,
+    // tokens released continuously to contributors, in addition to the schedule
+    pub contributor_linear_vesting: Option<LinearVesting>,
 
     // timeline of release of tokens to creator
     pub creator_vesting_schedule: Vec<VestingEvent>,
+    // tokens released continuously to the creator, in addition to the schedule
+    pub creator_linear_vesting: Option<LinearVesting>,
     // addresses to evenly distribute coins to
     // if none, will distribute to project creator address
     pub creator_addresses: Option<Vec<Addr>>,
+
+    // further named allocations, e.g. treasury or advisors
+    pub allocation_buckets: Option<Vec<AllocationBucket>>,
+}
+
+// Named allocation of the reward token, each vesting event is split evenly among the recipients
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug,)]
+pub struct AllocationBucket {
+    pub name: String,
+    pub recipients: Vec<Addr>,
+    pub vesting_schedule: Vec<VestingEvent>,
+}
+
+// Bonus applied to the reward weight of early pledges, in per mille of the pledged amount
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug,)]
+pub struct RewardBonusCurve {
+    // applied to pledges up to `full_until`, e.g. 1500 for 1.5x
+    pub multiplier: u16,
+    // last block height with the full multiplier, it then slides linearly to 1x at the deadline
+    pub full_until: u64,
+}
+
+// Allocation that vests linearly from `start` to `end`, nothing can be claimed before `cliff`
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug,)]
+pub struct LinearVesting {
+    pub amount: Uint128,
+    pub start: u64,
+    pub cliff: u64,
+    pub end: u64,
 }
 
 // Vesting events indicate what block
@@ -85,6 +151,97 @@ pub struct VestingEvent {
     pub amount: Uint128,
 }
 
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug,)]
+pub struct MembershipTier {
+    pub name: String,
+    // price per period in the snip20 token
+    pub price: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug,)]
+pub struct MembershipInit {
+    // length of a period in blocks
+    pub period: u64,
+    pub tiers: Vec<MembershipTier>,
+}
+
 impl InitCallback for ProjectInstantiateMsg {
     const BLOCK_SIZE: usize = 256;
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ProjectMigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectExecuteMsg {
+    ClearSpamFlags {},
+    Delist {},
+}
+
+impl HandleCallback for ProjectExecuteMsg {
+    const BLOCK_SIZE: usize = 256;
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectQueryMsg {
+    Status {},
+}
+
+impl Query for ProjectQueryMsg {
+    const BLOCK_SIZE: usize = 256;
+}
+
+// the part of a project's status answer read by the platform, other fields are ignored
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ProjectStatusResponse {
+    pub status: ProjectStatusInnerResponse,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ProjectStatusInnerResponse {
+    pub creator: Addr,
+    pub status: ProjectStatus,
+}
+
+// mirrors the project contract's status, reported back to the platform on every transition
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectStatus {
+    Fundraising,
+    Succeeded,
+    Failed,
+    Cancelled,
+    PaidOut,
+    DeadmanExpired,
+    Defaulted,
+    Delisted,
+    // platform only: a v1 project indexed by the migration that has not reported a status yet
+    Unindexed,
+}
+
+impl ProjectStatus {
+    // Some(true) for a project that delivered its funding to the creator, Some(false) for one that
+    // ended any other way, and None while it is still running
+    pub fn outcome(&self) -> Option<bool> {
+        match self {
+            ProjectStatus::Fundraising | ProjectStatus::Succeeded | ProjectStatus::Unindexed => None,
+            ProjectStatus::PaidOut => Some(true),
+            ProjectStatus::Failed
+            | ProjectStatus::Cancelled
+            | ProjectStatus::DeadmanExpired
+            | ProjectStatus::Defaulted
+            | ProjectStatus::Delisted => Some(false),
+        }
+    }
+
+    // status taking into account deadlines that have passed since the project last reported
+    pub fn effective(self, height: u64, deadline: u64, deadman: u64) -> ProjectStatus {
+        match self {
+            ProjectStatus::Fundraising if height > deadline => ProjectStatus::Failed,
+            ProjectStatus::Succeeded if height > deadline.saturating_add(deadman) => ProjectStatus::DeadmanExpired,
+            status => status,
+        }
+    }
 }
\ No newline at end of file
@@ -1,16 +1,46 @@
-use crate::msg::ContractInfo;
+use crate::matching::{StoredRound, StoredRoundEntry};
+use crate::msg::{AdminAction, ContractInfo, DelistReason, Role, SortOrder};
+use crate::project::{ProjectStatus, TextLimits};
 use cosmwasm_std::{
     Api, CanonicalAddr, Addr, StdError, StdResult, Storage,
 };
+use cosmwasm_storage::{prefixed, prefixed_read};
 use secret_toolkit::storage::{AppendStore};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::any::type_name;
 
+// storage layout version, bumped with a step in migrate.rs whenever a stored record changes
+pub const CONTRACT_VERSION: u32 = 5;
+// most project ids a filtered projects query examines in one call
+pub const MAX_PROJECTS_SCAN: u32 = 500;
+
+pub static CONTRACT_VERSION_KEY: &[u8] = b"cver";
 pub static CONFIG_KEY: &[u8] = b"conf";
-pub static CREATING_PROJECT_FLAG_KEY: &[u8] = b"flag";
-pub const CREATING_PROJECT: bool = true;
+pub static PREFIX_PENDING_PROJECT_KEY: &[u8] = b"pend";
+pub static PREFIX_PROJECT_SUMMARY_KEY: &[u8] = b"psum";
+pub static PREFIX_PROJECT_ID_KEY: &[u8] = b"pids";
+pub static PROJECT_COUNTS_KEY: &[u8] = b"pcnt";
+pub static PAUSED_KEY: &[u8] = b"paus";
+pub static ROLES_KEY: &[u8] = b"role";
+pub static PENDING_OWNER_KEY: &[u8] = b"pown";
+pub static PREFIX_CREATOR_STATS_KEY: &[u8] = b"cstt";
+pub static PREFIX_PROFILE_KEY: &[u8] = b"prof";
+pub static PREFIX_BACKINGS_KEY: &[u8] = b"bakr";
+pub static PREFIX_DELISTING_KEY: &[u8] = b"dlst";
+pub static PREFIX_ROUND_ENTRY_KEY: &[u8] = b"rent";
+pub static PREFIX_ROUND_CONTRIBUTION_KEY: &[u8] = b"rcon";
+pub static PREFIX_PROJECT_ROUNDS_KEY: &[u8] = b"prnd";
 pub static PROJECTS_STORE: AppendStore<StoredContractInfo> = AppendStore::new(b"proj");
+// project ids created by each address in ascending order, suffixed by the creator address
+pub static CREATOR_PROJECTS_STORE: AppendStore<u32> = AppendStore::new(b"cprj");
+// round ids are positions in this store
+pub static ROUNDS_STORE: AppendStore<StoredRound> = AppendStore::new(b"rnds");
+// project ids in the order they joined each round, suffixed by the round id
+pub static ROUND_PROJECTS_STORE: AppendStore<u32> = AppendStore::new(b"rprj");
+pub static ADMIN_LOG_STORE: AppendStore<StoredAdminLogEntry> = AppendStore::new(b"alog");
+// category ids are positions in this store
+pub static CATEGORIES_STORE: AppendStore<StoredCategory> = AppendStore::new(b"ctgy");
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct Config {
@@ -20,6 +50,8 @@ pub struct Config {
     pub contract_address: CanonicalAddr,
     pub token_min_max_pledges: Vec<StoredPledgeMinMax>,
     pub deadman: u64,
+    pub max_duration: Option<u64>,
+    pub text_limits: TextLimits,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
@@ -27,8 +59,11 @@ pub struct StoredPledgeMinMax {
     pub token_addr: CanonicalAddr,
     pub min: u128,
     pub max: u128,
+    pub min_goal: Option<u128>,
+    pub max_goal: Option<u128>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn set_config(
     storage: &mut dyn Storage,
     owner: CanonicalAddr,
@@ -37,6 +72,8 @@ pub fn set_config(
     contract_address: CanonicalAddr,
     token_min_max_pledges: Vec<StoredPledgeMinMax>,
     deadman: u64,
+    max_duration: Option<u64>,
+    text_limits: TextLimits,
 ) -> StdResult<()> {
     let config = Config {
         owner,
@@ -45,6 +82,8 @@ pub fn set_config(
         contract_address,
         token_min_max_pledges,
         deadman,
+        max_duration,
+        text_limits,
     };
     set_bin_data(storage, CONFIG_KEY, &config)
 }
@@ -53,12 +92,514 @@ pub fn get_config(storage: &dyn Storage) -> StdResult<Config> {
     get_bin_data(storage, CONFIG_KEY)
 }
 
-pub fn set_creating_project(storage: &mut dyn Storage, creating_project: bool) -> StdResult<()> {
-    set_bin_data(storage, CREATING_PROJECT_FLAG_KEY, &creating_project)
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StoredCategory {
+    pub label: String,
+    // retired categories are kept so existing projects still resolve, but cannot be chosen
+    pub active: bool,
+}
+
+pub fn category_count(storage: &dyn Storage) -> StdResult<u32> {
+    CATEGORIES_STORE.get_len(storage)
+}
+
+pub fn add_category(storage: &mut dyn Storage, category: &StoredCategory) -> StdResult<u16> {
+    CATEGORIES_STORE.push(storage, category)?;
+    category_count(storage).map(|len| (len - 1) as u16)
+}
+
+pub fn set_category(storage: &mut dyn Storage, id: u16, category: &StoredCategory) -> StdResult<()> {
+    CATEGORIES_STORE.set_at(storage, id as u32, category)
+}
+
+pub fn get_categories(storage: &dyn Storage) -> StdResult<Vec<StoredCategory>> {
+    CATEGORIES_STORE.iter(storage)?.collect()
+}
+
+pub fn set_contract_version(storage: &mut dyn Storage, version: u32) -> StdResult<()> {
+    set_bin_data(storage, CONTRACT_VERSION_KEY, &version)
+}
+
+// contracts instantiated before versioning was added are version 1
+pub fn get_contract_version(storage: &dyn Storage) -> u32 {
+    get_bin_data(storage, CONTRACT_VERSION_KEY).unwrap_or(1_u32)
+}
+
+// registry metadata for a project, so clients can list and filter without querying each contract
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StoredProjectSummary {
+    pub title: String,
+    pub creator: CanonicalAddr,
+    pub categories: Vec<u16>,
+    pub goal: u128,
+    pub deadline: u64,
+    pub deadman: u64,
+    pub token: CanonicalAddr,
+    // latest status reported by the project
+    pub status: ProjectStatus,
+    // hidden after enough backers flagged the project as spam
+    pub hidden: bool,
+    // memberships have no deadline and keep the reported status
+    pub membership: bool,
+}
+
+impl StoredProjectSummary {
+    // v1 projects were indexed by the migration without their metadata, see index_v1_projects
+    pub fn is_placeholder(&self) -> bool {
+        self.creator.as_slice().is_empty()
+    }
+
+    pub fn effective_status(&self, height: u64) -> ProjectStatus {
+        if self.membership {
+            return self.status;
+        }
+        self.status.effective(height, self.deadline, self.deadman)
+    }
+}
+
+// number of registered projects in each reported status
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct StoredProjectCounts {
+    pub fundraising: u32,
+    pub succeeded: u32,
+    pub failed: u32,
+    pub cancelled: u32,
+    pub paid_out: u32,
+    pub deadman_expired: u32,
+    pub defaulted: u32,
+    pub delisted: u32,
+    pub hidden: u32,
+}
+
+impl StoredProjectCounts {
+    // unindexed projects are not counted until they report a status
+    pub fn status_count(&mut self, status: ProjectStatus) -> Option<&mut u32> {
+        match status {
+            ProjectStatus::Fundraising => Some(&mut self.fundraising),
+            ProjectStatus::Succeeded => Some(&mut self.succeeded),
+            ProjectStatus::Failed => Some(&mut self.failed),
+            ProjectStatus::Cancelled => Some(&mut self.cancelled),
+            ProjectStatus::PaidOut => Some(&mut self.paid_out),
+            ProjectStatus::DeadmanExpired => Some(&mut self.deadman_expired),
+            ProjectStatus::Defaulted => Some(&mut self.defaulted),
+            ProjectStatus::Delisted => Some(&mut self.delisted),
+            ProjectStatus::Unindexed => None,
+        }
+    }
+
+    pub fn transition(&mut self, from: ProjectStatus, to: ProjectStatus) {
+        if let Some(from_count) = self.status_count(from) {
+            *from_count = from_count.saturating_sub(1);
+        }
+        if let Some(to_count) = self.status_count(to) {
+            *to_count += 1;
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct StoredCreatorStats {
+    pub created: u32,
+    pub successful: u32,
+    pub unsuccessful: u32,
+}
+
+impl StoredCreatorStats {
+    pub fn transition(&mut self, from: ProjectStatus, to: ProjectStatus) {
+        match from.outcome() {
+            Some(true) => self.successful = self.successful.saturating_sub(1),
+            Some(false) => self.unsuccessful = self.unsuccessful.saturating_sub(1),
+            None => {}
+        }
+        match to.outcome() {
+            Some(true) => self.successful += 1,
+            Some(false) => self.unsuccessful += 1,
+            None => {}
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StoredDelisting {
+    pub reason: DelistReason,
+    pub text: String,
+    pub moderator: CanonicalAddr,
+    pub height: u64,
+    pub time: u64,
+}
+
+pub fn set_delisting(
+    storage: &mut dyn Storage,
+    project_id: u32,
+    delisting: &StoredDelisting,
+) -> StdResult<()> {
+    let mut delisting_store = prefixed(storage, PREFIX_DELISTING_KEY);
+    set_bin_data(&mut delisting_store, &project_id.to_be_bytes(), delisting)
+}
+
+pub fn get_delisting(storage: &dyn Storage, project_id: u32) -> Option<StoredDelisting> {
+    let delisting_store = prefixed_read(storage, PREFIX_DELISTING_KEY);
+    get_bin_data(&delisting_store, &project_id.to_be_bytes()).ok()
+}
+
+pub fn push_creator_project(
+    storage: &mut dyn Storage,
+    creator: &CanonicalAddr,
+    project_id: u32,
+) -> StdResult<()> {
+    CREATOR_PROJECTS_STORE.add_suffix(creator.as_slice()).push(storage, &project_id)
+}
+
+// Take up to `limit` of a creator's project ids following the `start_after` project id
+pub fn get_creator_projects(
+    storage: &dyn Storage,
+    creator: &CanonicalAddr,
+    start_after: Option<u32>,
+    limit: u32,
+    order: SortOrder,
+) -> StdResult<Vec<u32>> {
+    let store = CREATOR_PROJECTS_STORE.add_suffix(creator.as_slice());
+    let len = store.get_len(storage)?;
+    // first position holding a project id of at least `project_id`, the list is sorted
+    let lower_bound = |project_id: u32| -> StdResult<u32> {
+        let (mut lo, mut hi) = (0_u32, len);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if store.get_at(storage, mid)? < project_id {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        Ok(lo)
+    };
+    let positions: Box<dyn Iterator<Item = u32>> = match (order, start_after) {
+        (SortOrder::Ascending, None) => Box::new(0..len),
+        (SortOrder::Ascending, Some(id)) => Box::new(lower_bound(id.saturating_add(1))?..len),
+        (SortOrder::Descending, None) => Box::new((0..len).rev()),
+        (SortOrder::Descending, Some(id)) => Box::new((0..lower_bound(id)?).rev()),
+    };
+    positions
+        .take(limit as _)
+        .map(|idx| store.get_at(storage, idx))
+        .collect()
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StoredProfile {
+    pub display_name: String,
+    pub bio: String,
+    pub avatar_url: Option<String>,
+    pub links: Vec<String>,
+    // for backers to encrypt messages to the creator
+    pub contact_public_key: Option<Vec<u8>>,
+}
+
+pub fn set_profile(
+    storage: &mut dyn Storage,
+    addr: &CanonicalAddr,
+    profile: &StoredProfile,
+) -> StdResult<()> {
+    let mut profile_store = prefixed(storage, PREFIX_PROFILE_KEY);
+    set_bin_data(&mut profile_store, addr.as_slice(), profile)
+}
+
+pub fn get_profile(storage: &dyn Storage, addr: &CanonicalAddr) -> Option<StoredProfile> {
+    let profile_store = prefixed_read(storage, PREFIX_PROFILE_KEY);
+    get_bin_data(&profile_store, addr.as_slice()).ok()
+}
+
+// a backer's current pledge to a project, as last reported by the project
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StoredBacking {
+    pub project_id: u32,
+    pub amount: u128,
+}
+
+pub fn get_backings(storage: &dyn Storage, backer: &CanonicalAddr) -> Vec<StoredBacking> {
+    let backings_store = prefixed_read(storage, PREFIX_BACKINGS_KEY);
+    get_bin_data(&backings_store, backer.as_slice()).unwrap_or_default()
+}
+
+// sets a backer's pledge to a project, removing the project from their list when it is zero
+pub fn set_backing(
+    storage: &mut dyn Storage,
+    backer: &CanonicalAddr,
+    project_id: u32,
+    amount: u128,
+) -> StdResult<()> {
+    let mut backings = get_backings(storage, backer);
+    match backings.iter().position(|backing| backing.project_id == project_id) {
+        Some(idx) if amount == 0 => {
+            backings.remove(idx);
+        }
+        Some(idx) => backings[idx].amount = amount,
+        None if amount == 0 => return Ok(()),
+        None => backings.push(StoredBacking { project_id, amount }),
+    }
+    let mut backings_store = prefixed(storage, PREFIX_BACKINGS_KEY);
+    set_bin_data(&mut backings_store, backer.as_slice(), &backings)
+}
+
+pub fn get_backing(storage: &dyn Storage, backer: &CanonicalAddr, project_id: u32) -> u128 {
+    get_backings(storage, backer)
+        .into_iter()
+        .find(|backing| backing.project_id == project_id)
+        .map_or(0, |backing| backing.amount)
+}
+
+pub fn round_count(storage: &dyn Storage) -> StdResult<u32> {
+    ROUNDS_STORE.get_len(storage)
+}
+
+pub fn add_round(storage: &mut dyn Storage, round: &StoredRound) -> StdResult<u32> {
+    let round_id = round_count(storage)?;
+    ROUNDS_STORE.push(storage, round)?;
+    Ok(round_id)
+}
+
+pub fn get_round(storage: &dyn Storage, round_id: u32) -> StdResult<StoredRound> {
+    ROUNDS_STORE.get_at(storage, round_id)
+}
+
+pub fn set_round(storage: &mut dyn Storage, round_id: u32, round: &StoredRound) -> StdResult<()> {
+    ROUNDS_STORE.set_at(storage, round_id, round)
+}
+
+pub fn get_rounds(
+    storage: &dyn Storage,
+    start_after: Option<u32>,
+    limit: u32,
+    order: SortOrder,
+) -> StdResult<Vec<(u32, StoredRound)>> {
+    let len = round_count(storage)?;
+    cursor_positions(len, start_after, order)
+        .take(limit as _)
+        .map(|round_id| Ok((round_id, ROUNDS_STORE.get_at(storage, round_id)?)))
+        .collect()
+}
+
+pub fn round_project_count(storage: &dyn Storage, round_id: u32) -> StdResult<u32> {
+    ROUND_PROJECTS_STORE.add_suffix(&round_id.to_be_bytes()).get_len(storage)
 }
 
-pub fn is_creating_project(storage: &dyn Storage) -> bool {
-    get_bin_data(storage, CREATING_PROJECT_FLAG_KEY).unwrap_or_else(|_| false)
+pub fn push_round_project(storage: &mut dyn Storage, round_id: u32, project_id: u32) -> StdResult<()> {
+    ROUND_PROJECTS_STORE.add_suffix(&round_id.to_be_bytes()).push(storage, &project_id)?;
+    let mut project_rounds = get_project_rounds(storage, project_id);
+    project_rounds.push(round_id);
+    set_project_rounds(storage, project_id, &project_rounds)
+}
+
+// (join order, project id) of the projects in a round after the `start_after` join order
+pub fn get_round_projects(
+    storage: &dyn Storage,
+    round_id: u32,
+    start_after: Option<u32>,
+    limit: u32,
+) -> StdResult<Vec<(u32, u32)>> {
+    let store = ROUND_PROJECTS_STORE.add_suffix(&round_id.to_be_bytes());
+    let len = store.get_len(storage)?;
+    cursor_positions(len, start_after, SortOrder::Ascending)
+        .take(limit as _)
+        .map(|idx| Ok((idx, store.get_at(storage, idx)?)))
+        .collect()
+}
+
+// open rounds a project joined, rounds that ended are dropped when the list is next read for an update
+pub fn get_project_rounds(storage: &dyn Storage, project_id: u32) -> Vec<u32> {
+    let project_rounds_store = prefixed_read(storage, PREFIX_PROJECT_ROUNDS_KEY);
+    get_bin_data(&project_rounds_store, &project_id.to_be_bytes()).unwrap_or_default()
+}
+
+pub fn set_project_rounds(storage: &mut dyn Storage, project_id: u32, round_ids: &[u32]) -> StdResult<()> {
+    let mut project_rounds_store = prefixed(storage, PREFIX_PROJECT_ROUNDS_KEY);
+    set_bin_data(&mut project_rounds_store, &project_id.to_be_bytes(), &round_ids)
+}
+
+fn round_entry_key(round_id: u32, project_id: u32) -> Vec<u8> {
+    [round_id.to_be_bytes(), project_id.to_be_bytes()].concat()
+}
+
+pub fn get_round_entry(storage: &dyn Storage, round_id: u32, project_id: u32) -> Option<StoredRoundEntry> {
+    let entry_store = prefixed_read(storage, PREFIX_ROUND_ENTRY_KEY);
+    get_bin_data(&entry_store, &round_entry_key(round_id, project_id)).ok()
+}
+
+pub fn set_round_entry(
+    storage: &mut dyn Storage,
+    round_id: u32,
+    project_id: u32,
+    entry: &StoredRoundEntry,
+) -> StdResult<()> {
+    let mut entry_store = prefixed(storage, PREFIX_ROUND_ENTRY_KEY);
+    set_bin_data(&mut entry_store, &round_entry_key(round_id, project_id), entry)
+}
+
+pub fn get_round_contribution(
+    storage: &dyn Storage,
+    round_id: u32,
+    project_id: u32,
+    backer: &CanonicalAddr,
+) -> u128 {
+    let contribution_store = prefixed_read(storage, PREFIX_ROUND_CONTRIBUTION_KEY);
+    let key = [round_entry_key(round_id, project_id).as_slice(), backer.as_slice()].concat();
+    get_bin_data(&contribution_store, &key).unwrap_or(0)
+}
+
+pub fn set_round_contribution(
+    storage: &mut dyn Storage,
+    round_id: u32,
+    project_id: u32,
+    backer: &CanonicalAddr,
+    contribution: u128,
+) -> StdResult<()> {
+    let mut contribution_store = prefixed(storage, PREFIX_ROUND_CONTRIBUTION_KEY);
+    let key = [round_entry_key(round_id, project_id).as_slice(), backer.as_slice()].concat();
+    set_bin_data(&mut contribution_store, &key, &contribution)
+}
+
+pub fn set_project_counts(storage: &mut dyn Storage, counts: &StoredProjectCounts) -> StdResult<()> {
+    set_bin_data(storage, PROJECT_COUNTS_KEY, counts)
+}
+
+// addresses granted each role in addition to the owner, who can do everything
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+pub struct StoredRoles {
+    pub moderators: Vec<CanonicalAddr>,
+    pub fee_managers: Vec<CanonicalAddr>,
+    pub round_managers: Vec<CanonicalAddr>,
+}
+
+impl StoredRoles {
+    pub fn members(&mut self, role: Role) -> &mut Vec<CanonicalAddr> {
+        match role {
+            Role::Moderator => &mut self.moderators,
+            Role::FeeManager => &mut self.fee_managers,
+            Role::RoundManager => &mut self.round_managers,
+        }
+    }
+}
+
+pub fn get_roles(storage: &dyn Storage) -> StoredRoles {
+    get_bin_data(storage, ROLES_KEY).unwrap_or_default()
+}
+
+pub fn set_roles(storage: &mut dyn Storage, roles: &StoredRoles) -> StdResult<()> {
+    set_bin_data(storage, ROLES_KEY, roles)
+}
+
+pub fn get_pending_owner(storage: &dyn Storage) -> Option<CanonicalAddr> {
+    get_bin_data(storage, PENDING_OWNER_KEY).unwrap_or(None)
+}
+
+pub fn set_pending_owner(storage: &mut dyn Storage, owner: Option<CanonicalAddr>) -> StdResult<()> {
+    set_bin_data(storage, PENDING_OWNER_KEY, &owner)
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StoredAdminLogEntry {
+    pub actor: CanonicalAddr,
+    pub action: AdminAction,
+    pub height: u64,
+    pub time: u64,
+}
+
+pub fn admin_log_count(storage: &dyn Storage) -> StdResult<u32> {
+    ADMIN_LOG_STORE.get_len(storage)
+}
+
+pub fn push_admin_log(storage: &mut dyn Storage, entry: &StoredAdminLogEntry) -> StdResult<()> {
+    ADMIN_LOG_STORE.push(storage, entry)
+}
+
+pub fn get_admin_log(
+    storage: &dyn Storage,
+    start_after: Option<u32>,
+    limit: u32,
+    order: SortOrder,
+) -> StdResult<Vec<(u32, StoredAdminLogEntry)>> {
+    let len = admin_log_count(storage)?;
+    cursor_positions(len, start_after, order)
+        .take(limit as _)
+        .map(|idx| Ok((idx, ADMIN_LOG_STORE.get_at(storage, idx)?)))
+        .collect()
+}
+
+pub fn set_paused(storage: &mut dyn Storage, paused: bool) -> StdResult<()> {
+    set_bin_data(storage, PAUSED_KEY, &paused)
+}
+
+pub fn is_paused(storage: &dyn Storage) -> bool {
+    get_bin_data(storage, PAUSED_KEY).unwrap_or(false)
+}
+
+// counts only cover projects registered with a summary
+pub fn get_project_counts(storage: &dyn Storage) -> StoredProjectCounts {
+    get_bin_data(storage, PROJECT_COUNTS_KEY).unwrap_or_default()
+}
+
+pub fn set_creator_stats(
+    storage: &mut dyn Storage,
+    creator: &CanonicalAddr,
+    stats: &StoredCreatorStats,
+) -> StdResult<()> {
+    let mut stats_store = prefixed(storage, PREFIX_CREATOR_STATS_KEY);
+    set_bin_data(&mut stats_store, creator.as_slice(), stats)
+}
+
+pub fn get_creator_stats(storage: &dyn Storage, creator: &CanonicalAddr) -> StoredCreatorStats {
+    let stats_store = prefixed_read(storage, PREFIX_CREATOR_STATS_KEY);
+    get_bin_data(&stats_store, creator.as_slice()).unwrap_or_default()
+}
+
+// summary of a project instantiation waiting for its reply, keyed by the project id it will receive
+pub fn set_pending_project(
+    storage: &mut dyn Storage,
+    project_id: u32,
+    summary: &StoredProjectSummary,
+) -> StdResult<()> {
+    let mut pending_store = prefixed(storage, PREFIX_PENDING_PROJECT_KEY);
+    set_bin_data(&mut pending_store, &project_id.to_be_bytes(), summary)
+}
+
+pub fn take_pending_project(
+    storage: &mut dyn Storage,
+    project_id: u32,
+) -> StdResult<StoredProjectSummary> {
+    let mut pending_store = prefixed(storage, PREFIX_PENDING_PROJECT_KEY);
+    let summary = get_bin_data(&pending_store, &project_id.to_be_bytes())?;
+    pending_store.remove(&project_id.to_be_bytes());
+    Ok(summary)
+}
+
+pub fn set_project_summary(
+    storage: &mut dyn Storage,
+    project_id: u32,
+    summary: &StoredProjectSummary,
+) -> StdResult<()> {
+    let mut summary_store = prefixed(storage, PREFIX_PROJECT_SUMMARY_KEY);
+    set_bin_data(&mut summary_store, &project_id.to_be_bytes(), summary)
+}
+
+// v1 projects have a placeholder summary written by the migration
+pub fn get_project_summary(storage: &dyn Storage, project_id: u32) -> Option<StoredProjectSummary> {
+    let summary_store = prefixed_read(storage, PREFIX_PROJECT_SUMMARY_KEY);
+    get_bin_data(&summary_store, &project_id.to_be_bytes()).ok()
+}
+
+pub fn set_project_id(
+    storage: &mut dyn Storage,
+    project_addr: &CanonicalAddr,
+    project_id: u32,
+) -> StdResult<()> {
+    let mut id_store = prefixed(storage, PREFIX_PROJECT_ID_KEY);
+    set_bin_data(&mut id_store, project_addr.as_slice(), &project_id)
+}
+
+// id of a project registered by the platform, used to authenticate project callbacks
+pub fn get_project_id(storage: &dyn Storage, project_addr: &CanonicalAddr) -> Option<u32> {
+    let id_store = prefixed_read(storage, PREFIX_PROJECT_ID_KEY);
+    get_bin_data(&id_store, project_addr.as_slice()).ok()
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
@@ -93,7 +634,7 @@ impl StoredContractInfo {
 }
 
 pub fn project_count(storage: &dyn Storage) -> StdResult<u32> {
-    return PROJECTS_STORE.get_len(storage);
+    PROJECTS_STORE.get_len(storage)
 }
 
 pub fn add_project(storage: &mut dyn Storage, project: StoredContractInfo) -> StdResult<u32> {
@@ -101,20 +642,61 @@ pub fn add_project(storage: &mut dyn Storage, project: StoredContractInfo) -> St
     project_count(storage).map(|len| len-1)
 }
 
-pub fn get_projects(
+pub fn get_project(storage: &dyn Storage, project_id: u32) -> StdResult<StoredContractInfo> {
+    PROJECTS_STORE.get_at(storage, project_id)
+}
+
+pub fn set_project(storage: &mut dyn Storage, project_id: u32, project: &StoredContractInfo) -> StdResult<()> {
+    PROJECTS_STORE.set_at(storage, project_id, project)
+}
+
+// AppendStore positions following the `start_after` cursor in the given order
+pub fn cursor_positions(
+    len: u32,
+    start_after: Option<u32>,
+    order: SortOrder,
+) -> Box<dyn Iterator<Item = u32>> {
+    match order {
+        SortOrder::Ascending => {
+            let start = start_after.map_or(0, |idx| idx.saturating_add(1));
+            Box::new(start..len)
+        }
+        SortOrder::Descending => {
+            let end = start_after.map_or(len, |idx| idx.min(len));
+            Box::new((0..end).rev())
+        }
+    }
+}
+
+// a page of projects with the id to continue from, if any
+pub type ProjectsPage = (Vec<(u32, StoredContractInfo)>, Option<u32>);
+
+// Take up to `limit` projects accepted by `filter` following the `start_after` project id,
+// examining at most MAX_PROJECTS_SCAN ids. Also returns the last examined id when more remain.
+pub fn get_projects<F: Fn(u32) -> bool>(
     storage: &dyn Storage,
-    page: u32,
-    page_size: u32,
-) -> StdResult<Vec<StoredContractInfo>> {
-    // Take `page_size` projects starting from the latest project, potentially skipping `page * page_size`
-    // projects from the start.
-    let projects = PROJECTS_STORE
-        .iter(storage)?
-        .rev()
-        .skip((page * page_size) as _)
-        .take(page_size as _)
-        .collect();
-    projects
+    start_after: Option<u32>,
+    limit: u32,
+    order: SortOrder,
+    filter: F,
+) -> StdResult<ProjectsPage> {
+    let len = project_count(storage)?;
+    let mut projects = vec![];
+    let mut last_scanned = None;
+    for project_id in cursor_positions(len, start_after, order).take(MAX_PROJECTS_SCAN as _) {
+        last_scanned = Some(project_id);
+        if filter(project_id) {
+            projects.push((project_id, PROJECTS_STORE.get_at(storage, project_id)?));
+            if projects.len() >= limit as usize {
+                break;
+            }
+        }
+    }
+    let next_start_after = last_scanned.filter(|&project_id| match order {
+        SortOrder::Ascending => project_id + 1 < len,
+        SortOrder::Descending => project_id > 0,
+    });
+    Ok((projects, next_start_after))
 }
 
 //
@@ -1,5 +1,3 @@
-use std::cmp::{min,};
-use std::mem::MaybeUninit;
 use primitive_types::U256;
 use cosmwasm_std::{
     entry_point, from_binary, to_binary, Binary, Env, Addr,
@@ -9,25 +7,47 @@ use cosmwasm_std::{
 };
 use rand::RngCore;
 use crate::msg::{
-    ExecuteAnswer, ExecuteMsg, InstantiateMsg, PlatformExecuteMsg, QueryAnswer, QueryMsg, ResponseStatus,
-    ResponseStatus::Failure, ResponseStatus::Success, PlatformQueryMsg, ValidatePermitResponse,
-    ExecuteReceiveMsg,
+    ExecuteAnswer, ExecuteMsg, InstantiateMsg, MigrateMsg, PlatformExecuteMsg, QueryAnswer, QueryMsg,
+    ResponseStatus::Success, PlatformQueryMsg, ValidatePermitResponse, CategoriesResponse, PausedResponse,
+    ExecuteReceiveMsg, FulfillmentInfo, BondInfo, Comment, SortOrder, BackingChange, TextLimits,
 };
-use crate::reward::{RewardMessage, Snip24InstantiateMsg, InitConfig, InitialBalance, Snip24RewardInit, VestingReward, VestingRewardStatus, Snip24Info};
+use crate::sponsor::{StoredMatch, SponsorMatch, MAX_MATCHES};
+use crate::membership::{
+    MembershipInit, MembershipInfo, Member, StoredMembership, StoredMember, MAX_MEMBERSHIP_TIERS,
+    MAX_PREPAID_PERIODS, MAX_WITHDRAW_PERIODS,
+};
+use crate::survey::{
+    StoredSurvey, Survey, SurveyAnswer, SurveyQuestion, SurveyResponse, MAX_SURVEY_QUESTIONS,
+};
+use crate::reward::{RewardMessage, Snip24InstantiateMsg, InitConfig, InitialBalance, Snip24RewardInit, VestingReward, VestingRewardStatus, Snip24Info, PER_MILLE,
+    LinearVesting, LinearVestingStatus, BucketRewardStatus, StoredAllocationBucket, MAX_ALLOCATION_BUCKETS,
+    MAX_BUCKET_RECIPIENTS};
 use crate::state::{
     get_subtitle, set_subtitle,
-    add_funds, clear_funds, get_categories, get_creator, get_deadline,
+    add_funds, clear_funds, transfer_funds, get_categories, get_creator, get_deadline,
     get_description, get_funded_message, get_funder, get_goal, get_pledged_message,
-    get_prng_seed, get_status, get_title, get_total, is_paid_out, paid_out,
+    get_prng_seed, get_title, get_total,
     read_viewing_key, set_categories, set_creator, set_deadline,
     set_description, set_funded_message, set_goal, set_pledged_message, set_prng_seed,
-    set_status, set_title, set_total, write_viewing_key, EXPIRED, FUNDRAISING,
-    SUCCESSFUL, set_config, get_config, set_deadman, get_deadman,
-    push_comment, get_comments, set_spam_flag, get_spam_count, set_snip24_reward, set_reward_messages, 
+    set_status, set_title, set_total, write_viewing_key, init_status, sync_status,
+    get_status, get_effective_status, ProjectStatus, set_config, get_config, set_deadman, get_deadman,
+    push_comment, get_comments, set_spam_flag, get_spam_count, clear_spam_flags, set_snip24_reward, set_reward_messages, 
     get_reward_messages, get_snip24_reward, set_snip24_reward_address, get_snip24_reward_address, 
     set_creator_snip24_allocation_received, get_creator_snip24_allocation_received, set_funder, 
     set_pledge_minmax, get_pledge_minmax, set_bin_data, SPAM_COUNT_KEY, set_cover_img, get_cover_img,
+    set_fulfillment_public_key, get_fulfillment_public_key, set_fulfillment_info, get_fulfillment_infos,
+    fulfillment_info_count, MAX_FULFILLMENT_INFO_SIZE, push_survey, get_survey, get_surveys,
+    get_survey_response, set_survey_response, get_survey_responses, StoredBond, set_bond, get_bond,
+    calculate_bond_share, claim_bond_share, set_bond_release_block, get_bond_release_block, get_default_votes,
+    set_default_vote, get_default_vote, calculate_bond_release_block, set_contract_version, CONTRACT_VERSION,
+    set_text_limits, get_text_limits, push_match, set_match, get_matches, find_match, set_match_rewards,
+    get_match_rewards, get_backer_total, get_rewarded_total, rebalance_matches, set_membership,
+    get_membership, set_member, get_member, add_revenue_change, get_revenue_change, close_membership,
+    set_weighted_total, get_vested_claimed, set_vested_claimed,
+    get_allocation_buckets, get_bucket_received, set_bucket_received,
 };
+use crate::error::ContractError;
+use crate::migrate::migrate_storage;
 use crate::utils::space_pad;
 use crate::viewing_key::{ViewingKey, VIEWING_KEY_SIZE};
 use secret_toolkit::{
@@ -45,6 +65,10 @@ pub const PREFIX_REVOKED_PERMITS: &str = "revoked_permits";
 pub const RESPONSE_BLOCK_SIZE: usize = 256;
 pub const SNIP24_INSTANTIATE_REPLY_ID: u64 = 1;
 pub const PER_MILLE_DENOM: u16 = 1000;
+pub const DEFAULT_PAGE_SIZE: u32 = 10;
+pub const MAX_PAGE_SIZE: u32 = 50;
+// the platform hides projects flagged as spam by this many addresses
+pub const SPAM_HIDE_THRESHOLD: u32 = 10;
 
 #[entry_point]
 pub fn instantiate(
@@ -52,17 +76,28 @@ pub fn instantiate(
     env: Env,
     _info: MessageInfo,
     msg: InstantiateMsg,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_VERSION)?;
+
     let prng_seed = sha_256(base64::encode(msg.entropy).as_bytes()).to_vec();
     set_prng_seed(deps.storage, &prng_seed)?; 
 
-    let creator = deps.api.addr_canonicalize(&msg.creator.as_str())?;
+    let creator = deps.api.addr_canonicalize(msg.creator.as_str())?;
     set_creator(deps.storage, &creator)?;
 
-    if env.block.height > msg.deadline {
-        return Err(StdError::generic_err(
-            "Cannot create project with deadline in the past",
-        ));
+    let text_limits = msg.text_limits.unwrap_or_default();
+    if let Some(membership) = msg.membership {
+        validate_membership(&membership, &text_limits, &msg.bond, &msg.snip24_reward_init)?;
+        set_membership(deps.storage, &StoredMembership {
+            period: membership.period,
+            start: env.block.height,
+            tiers: membership.tiers,
+            withdrawn: 0,
+            rate: 0,
+            closed: None,
+        })?;
+    } else if env.block.height > msg.deadline {
+        return Err(ContractError::DeadlineInPast {});
     }
     set_deadline(deps.storage, msg.deadline)?;
     set_deadman(deps.storage, msg.deadman)?;
@@ -79,44 +114,50 @@ pub fn instantiate(
     set_pledge_minmax(deps.storage, msg.minimum_pledge.u128(), msg.maximum_pledge.u128())?;
     set_bin_data(deps.storage, SPAM_COUNT_KEY, &(0_u32))?;
 
-    validate_snip24_reward_init(msg.snip24_reward_init.clone())?;
+    set_bond(deps.storage, &StoredBond {
+        amount: msg.bond.unwrap_or_default().u128(),
+        deposited: false,
+        forfeit_total: None,
+        returned: false,
+    })?;
+    set_bond_release_block(
+        deps.storage,
+        calculate_bond_release_block(msg.deadline, msg.deadman, msg.snip24_reward_init.as_ref()),
+    )?;
+
+    validate_snip24_reward_init(msg.snip24_reward_init.clone(), msg.deadline)?;
     set_snip24_reward(deps.storage, deps.api, msg.snip24_reward_init.clone())?;
-    let snip24_allocation_received: Vec<bool>;
-    if msg.snip24_reward_init.is_none() {
-        snip24_allocation_received = vec![];
-    } else {
-        let snip24_reward_init = msg.snip24_reward_init.unwrap();
-        snip24_allocation_received = snip24_reward_init.creator_vesting_schedule.into_iter().map(|_| false).collect();
-    }
+    let snip24_allocation_received: Vec<bool> = match msg.snip24_reward_init {
+        None => vec![],
+        Some(snip24_reward_init) => snip24_reward_init.creator_vesting_schedule.into_iter().map(|_| false).collect(),
+    };
     set_creator_snip24_allocation_received(deps.storage, snip24_allocation_received)?;
 
     set_snip24_reward_address(deps.storage, None)?;
 
     let goal = msg.goal.u128();
-    if goal == 0 {
-        return Err(StdError::generic_err("Goal must be greater than 0"));
+    if goal == 0 && get_membership(deps.storage)?.is_none() {
+        return Err(ContractError::InvalidGoal {});
     }
     set_goal(deps.storage, goal)?;
 
-    set_categories(deps.storage, msg.categories)?;
+    set_text_limits(deps.storage, &text_limits)?;
 
-    set_status(deps.storage, FUNDRAISING)?;
+    init_status(deps.storage)?;
     set_total(deps.storage, 0_u128)?;
-
-    let register_msg = PlatformExecuteMsg::Register {
-        contract_addr: env.contract.address,
-        contract_code_hash: env.contract.code_hash.clone(),
-    };
+    set_weighted_total(deps.storage, 0_u128)?;
 
     set_config(
         deps.storage, 
         deps.api.addr_canonicalize(msg.source_contract.as_str())?, 
-        msg.source_hash.clone(),
+        msg.source_hash,
         deps.api.addr_canonicalize(msg.snip20_contract.as_str())?,
         msg.snip20_hash.clone(),
     )?;
 
-    let cosmos_msg = register_msg.to_cosmos_msg(msg.source_hash, msg.source_contract.into_string(), None)?;
+    validate_categories(deps.as_ref(), &msg.categories)?;
+    set_categories(deps.storage, msg.categories)?;
+
     let snip20_register_receive_msg = register_receive_msg(
         env.contract.code_hash, 
         None, 
@@ -135,7 +176,6 @@ pub fn instantiate(
     )?;
 
     let resp = Response::new()
-        .add_message(cosmos_msg)
         .add_message(snip20_register_receive_msg)
         .add_message(snip20_set_viewing_key_msg);
     Ok(resp)
@@ -143,81 +183,232 @@ pub fn instantiate(
 
 fn validate_snip24_reward_init(
     reward_init: Option<Snip24RewardInit>,
-) -> StdResult<()> {
-    if reward_init.is_some() {
-        let reward_init = reward_init.unwrap();
-        if reward_init.contributor_vesting_schedule.len() < 1 {
-            return Err(StdError::generic_err("Projects with snip24 rewards must have a contributor vesting schedule"));
+    deadline: u64,
+) -> Result<(), ContractError> {
+    if let Some(reward_init) = reward_init {
+        if reward_init.contributor_vesting_schedule.is_empty() && reward_init.contributor_linear_vesting.is_none() {
+            return Err(ContractError::InvalidSnip24Reward {
+                reason: String::from("Projects with snip24 rewards must have a contributor vesting schedule"),
+            });
+        }
+        if reward_init.creator_vesting_schedule.is_empty() && reward_init.creator_linear_vesting.is_none() {
+            return Err(ContractError::InvalidSnip24Reward {
+                reason: String::from("Projects with snip24 rewards must have a creator vesting schedule"),
+            });
+        }
+        for vesting in reward_init.contributor_linear_vesting.iter().chain(reward_init.creator_linear_vesting.iter()) {
+            if vesting.amount.is_zero() {
+                return Err(ContractError::InvalidSnip24Reward {
+                    reason: String::from("Linear vesting amount must be greater than 0"),
+                });
+            }
+            if vesting.start >= vesting.end || vesting.cliff < vesting.start || vesting.cliff > vesting.end {
+                return Err(ContractError::InvalidSnip24Reward {
+                    reason: String::from("Linear vesting must have start <= cliff <= end and start before end"),
+                });
+            }
         }
-        if reward_init.creator_vesting_schedule.len() < 1 {
-            return Err(StdError::generic_err("Projects with snip24 rewards must have a creator vesting schedule"));
+        let allocation_buckets = reward_init.allocation_buckets.unwrap_or_default();
+        if allocation_buckets.len() > MAX_ALLOCATION_BUCKETS {
+            return Err(ContractError::InvalidSnip24Reward {
+                reason: format!("Projects can have at most {} allocation buckets", MAX_ALLOCATION_BUCKETS),
+            });
+        }
+        for (i, bucket) in allocation_buckets.iter().enumerate() {
+            if bucket.name.is_empty() || allocation_buckets[..i].iter().any(|other| other.name == bucket.name) {
+                return Err(ContractError::InvalidSnip24Reward {
+                    reason: String::from("Allocation buckets must have unique, non-empty names"),
+                });
+            }
+            if bucket.recipients.is_empty() || bucket.recipients.len() > MAX_BUCKET_RECIPIENTS {
+                return Err(ContractError::InvalidSnip24Reward {
+                    reason: format!("Allocation bucket {} must have between 1 and {} recipients", bucket.name, MAX_BUCKET_RECIPIENTS),
+                });
+            }
+            if bucket.recipients.iter().enumerate().any(|(j, recipient)| bucket.recipients[..j].contains(recipient)) {
+                return Err(ContractError::InvalidSnip24Reward {
+                    reason: format!("Allocation bucket {} lists a recipient more than once", bucket.name),
+                });
+            }
+            if bucket.vesting_schedule.is_empty() {
+                return Err(ContractError::InvalidSnip24Reward {
+                    reason: format!("Allocation bucket {} must have a vesting schedule", bucket.name),
+                });
+            }
         }
-        if reward_init.maximum_contribution.is_some() {
-            let max_contribution = reward_init.maximum_contribution.unwrap();
-            if (reward_init.minimum_contribution.is_some()) {
-                let min_contribution = reward_init.minimum_contribution.unwrap();
+        if let Some(max_contribution) = reward_init.maximum_contribution {
+            if let Some(min_contribution) = reward_init.minimum_contribution {
                 if max_contribution < min_contribution {
-                    return Err(StdError::generic_err("Max contribution must be greater than min contribution for snip24 rewards"));
+                    return Err(ContractError::InvalidSnip24Reward {
+                        reason: String::from("Max contribution must be greater than min contribution for snip24 rewards"),
+                    });
                 }
             }
         }
+        if let Some(bonus_curve) = reward_init.bonus_curve {
+            if (bonus_curve.multiplier as u128) < PER_MILLE {
+                return Err(ContractError::InvalidSnip24Reward {
+                    reason: format!("Bonus multiplier must be at least {}", PER_MILLE),
+                });
+            }
+            if bonus_curve.full_until > deadline {
+                return Err(ContractError::InvalidSnip24Reward {
+                    reason: String::from("Full bonus must end by the deadline"),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+fn validate_membership(
+    membership: &MembershipInit,
+    text_limits: &TextLimits,
+    bond: &Option<Uint128>,
+    snip24_reward_init: &Option<Snip24RewardInit>,
+) -> Result<(), ContractError> {
+    let invalid = |reason: &str| Err(ContractError::InvalidMembership { reason: reason.to_string() });
+    if membership.period == 0 {
+        return invalid("Membership period must be at least one block");
+    }
+    if membership.tiers.is_empty() || membership.tiers.len() > MAX_MEMBERSHIP_TIERS {
+        return invalid(&format!("Membership must have between 1 and {} tiers", MAX_MEMBERSHIP_TIERS));
+    }
+    for tier in &membership.tiers {
+        if tier.price.is_zero() {
+            return invalid("Tier price must be greater than 0");
+        }
+        check_text_len("tier name", &tier.name, text_limits.max_title_len)?;
+    }
+    // both are settled when a campaign pays out, which a membership never does
+    if bond.is_some_and(|bond| !bond.is_zero()) {
+        return invalid("Membership projects cannot require a bond");
+    }
+    if snip24_reward_init.is_some() {
+        return invalid("Membership projects cannot have snip24 rewards");
     }
     Ok(())
 }
 
 #[entry_point]
 pub fn execute(
-    deps: DepsMut, 
+    mut deps: DepsMut, 
     env: Env, 
     info: MessageInfo, 
     msg: ExecuteMsg
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     let mut fresh_entropy = to_binary(&msg)?.0;
     fresh_entropy.extend(to_binary(&env)?.0);
     fresh_entropy.extend(to_binary(&info)?.0);
     supply_more_entropy(deps.storage, fresh_entropy.as_slice())?;
-    let response = match msg {
-        ExecuteMsg::ChangeText {
-            title,
-            subtitle,
-            description,
-            pledged_message,
-            funded_message,
-            reward_messages,
-            categories,
-            ..
-        } => try_change_text(
-            deps,
-            env,
-            info,
-            title,
-            subtitle,
-            description,
-            pledged_message,
-            funded_message,
-            reward_messages,
-            categories,
-        ),
-        ExecuteMsg::Cancel { .. } => try_cancel(deps, env, info),
-        ExecuteMsg::Receive {
-            sender,
-            from, 
-            amount,
-            msg,
-        } => try_receive(deps, env, info, sender, from, amount, msg),
-        ExecuteMsg::Refund { .. } => try_refund(deps, env, info),
-        ExecuteMsg::PayOut { .. } => try_pay_out(deps, env, info),
-        ExecuteMsg::ClaimReward { idx, .. } => try_claim_reward(deps, env, info, idx),
-        ExecuteMsg::Comment { comment, .. } => try_comment(deps, env, info, comment),
-        ExecuteMsg::FlagSpam { flag, .. } => try_flag_spam(deps, env, info, flag),
-        ExecuteMsg::GenerateViewingKey { entropy, .. } => {
-            try_generate_viewing_key(deps, env, info, entropy)
+    // the platform registry is notified of any status transition made below
+    let prior_status = get_status(deps.storage)?;
+    let response = {
+        let deps = deps.branch();
+        match msg {
+            ExecuteMsg::ChangeText {
+                title,
+                subtitle,
+                description,
+                pledged_message,
+                funded_message,
+                reward_messages,
+                categories,
+                ..
+            } => try_change_text(
+                deps,
+                env,
+                info,
+                title,
+                subtitle,
+                description,
+                pledged_message,
+                funded_message,
+                reward_messages,
+                categories,
+            ),
+            ExecuteMsg::Cancel { .. } => try_cancel(deps, env, info),
+            ExecuteMsg::Receive {
+                sender,
+                from, 
+                amount,
+                msg,
+            } => try_receive(deps, env, info, sender, from, amount, msg),
+            ExecuteMsg::Refund { .. } => try_refund(deps, env, info),
+            ExecuteMsg::PayOut { .. } => try_pay_out(deps, env, info),
+            ExecuteMsg::ClaimReward { idx, .. } => try_claim_reward(deps, env, info, idx),
+            ExecuteMsg::ClaimBucketReward { bucket, idx, .. } => try_claim_bucket_reward(deps, env, info, bucket, idx),
+            ExecuteMsg::ClaimVested { .. } => try_claim_vested(deps, env, info),
+            ExecuteMsg::TransferPledge { recipient, amount, .. } => {
+                try_transfer_pledge(deps, env, info, recipient, amount)
+            }
+            ExecuteMsg::SetMatchRewards { rewarded, .. } => try_set_match_rewards(deps, env, info, rewarded),
+            ExecuteMsg::RefundMatch { .. } => try_refund_match(deps, env, info),
+            ExecuteMsg::ClaimMatchReward { idx, .. } => try_claim_match_reward(deps, env, info, idx),
+            ExecuteMsg::CancelMembership { .. } => try_cancel_membership(deps, env, info),
+            ExecuteMsg::WithdrawMembership { .. } => try_withdraw_membership(deps, env, info),
+            ExecuteMsg::ClaimBond { .. } => try_claim_bond(deps, env, info),
+            ExecuteMsg::ClaimBondShare { .. } => try_claim_bond_share(deps, env, info),
+            ExecuteMsg::VoteDefault { default, .. } => try_vote_default(deps, env, info, default),
+            ExecuteMsg::Comment { comment, .. } => try_comment(deps, env, info, comment),
+            ExecuteMsg::FlagSpam { flag, .. } => try_flag_spam(deps, env, info, flag),
+            ExecuteMsg::ClearSpamFlags {} => try_clear_spam_flags(deps, env, info),
+            ExecuteMsg::Delist {} => try_delist(deps, env, info),
+            ExecuteMsg::GenerateViewingKey { entropy, .. } => {
+                try_generate_viewing_key(deps, env, info, entropy)
+            }
+            ExecuteMsg::SetFulfillmentKey { public_key, .. } => {
+                try_set_fulfillment_key(deps, env, info, public_key)
+            }
+            ExecuteMsg::SubmitFulfillmentInfo { info: fulfillment_info, .. } => {
+                try_submit_fulfillment_info(deps, env, info, fulfillment_info)
+            }
+            ExecuteMsg::CreateSurvey { questions, threshold, closes_at, .. } => {
+                try_create_survey(deps, env, info, questions, threshold, closes_at)
+            }
+            ExecuteMsg::AnswerSurvey { survey_id, answers, .. } => {
+                try_answer_survey(deps, env, info, survey_id, answers)
+            }
         }
     };
-    pad_response(response)
+    pad_response(response.and_then(|response| add_status_callback(deps, prior_status, response)))
+}
+
+// tells the platform registry about a status transition stored by this execute
+fn add_status_callback(
+    deps: DepsMut,
+    prior_status: ProjectStatus,
+    response: Response,
+) -> Result<Response, ContractError> {
+    let status = get_status(deps.storage)?;
+    if status == prior_status {
+        return Ok(response);
+    }
+    let config = get_config(deps.storage)?;
+    let callback = PlatformExecuteMsg::UpdateStatus { status }.to_cosmos_msg(
+        config.platform_hash,
+        deps.api.addr_humanize(&config.platform_contract)?.into_string(),
+        None,
+    )?;
+    Ok(response.add_message(callback))
+}
+
+// tells the platform registry a backer's total pledge after it changed
+fn backing_callback(
+    deps: Deps,
+    backer: Addr,
+    amount: u128,
+    change: BackingChange,
+) -> StdResult<CosmosMsg> {
+    let config = get_config(deps.storage)?;
+    PlatformExecuteMsg::UpdateBacking { backer, amount: Uint128::from(amount), change }.to_cosmos_msg(
+        config.platform_hash,
+        deps.api.addr_humanize(&config.platform_contract)?.into_string(),
+        None,
+    )
 }
 
-fn pad_response(response: StdResult<Response>) -> StdResult<Response> {
+fn pad_response(response: Result<Response, ContractError>) -> Result<Response, ContractError> {
     response.map(|mut response| {
         response.data = response.data.map(|mut data| {
             space_pad(RESPONSE_BLOCK_SIZE, &mut data.0);
@@ -232,12 +423,12 @@ fn try_generate_viewing_key(
     env: Env,
     info: MessageInfo,
     entropy: String,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     let prng_seed = get_prng_seed(deps.storage)?;
 
-    let key = ViewingKey::new(&env, &info, &prng_seed, (&entropy).as_ref());
+    let key = ViewingKey::new(&env, &info, &prng_seed, entropy.as_ref());
 
-    let message_sender = deps.api.addr_canonicalize(&info.sender.as_str())?;
+    let message_sender = deps.api.addr_canonicalize(info.sender.as_str())?;
 
     write_viewing_key(deps.storage, &message_sender, &key);
 
@@ -246,6 +437,7 @@ fn try_generate_viewing_key(
     Ok(resp)
 }
 
+#[allow(clippy::too_many_arguments)]
 fn try_change_text(
     deps: DepsMut,
     env: Env,
@@ -257,79 +449,135 @@ fn try_change_text(
     funded_message: Option<String>,
     reward_messages: Option<Vec<RewardMessage>>,
     categories: Option<Vec<u16>>,
-) -> StdResult<Response> {
-    let status;
-    let msg;
-
-    let sender_address_raw = deps.api.addr_canonicalize(&info.sender.as_str())?;
+) -> Result<Response, ContractError> {
+    let sender_address_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
     let creator = get_creator(deps.storage)?;
     if sender_address_raw != creator {
-        return Err(StdError::generic_err("Unauthorized"));
+        return Err(ContractError::Unauthorized {});
     }
 
-    let project_status = get_status(deps.storage)?;
-    let deadline = get_deadline(deps.storage)?;
-
-    if project_status == SUCCESSFUL || project_status == EXPIRED || is_paid_out(deps.storage) {
-        status = Failure;
-        msg = String::from("Cannot change a project that has been completed");
-    } else if env.block.height > deadline {
-        // was still FUNDRAISING but deadline expired
-        set_status(deps.storage, EXPIRED)?;
-        status = Failure;
-        msg = String::from("Cannot change a project that has been completed");
-    } else {
-        let mut updates: Vec<String> = vec![];
+    let project_status = sync_status(deps.storage, &env.block)?;
+    if project_status != ProjectStatus::Fundraising {
+        return Err(ContractError::NotFundraising {});
+    }
 
-        if title.is_some() {
-            set_title(deps.storage, title.unwrap())?;
-            updates.push(String::from("title"));
+    let limits = get_text_limits(deps.storage);
+    if let Some(ref title) = title {
+        check_text_len("title", title, limits.max_title_len)?;
+    }
+    if let Some(ref subtitle) = subtitle {
+        check_text_len("subtitle", subtitle, limits.max_subtitle_len)?;
+    }
+    if let Some(ref description) = description {
+        check_text_len("description", description, limits.max_description_len)?;
+    }
+    if let Some(ref pledged_message) = pledged_message {
+        check_text_len("pledged message", pledged_message, limits.max_message_len)?;
+    }
+    if let Some(ref funded_message) = funded_message {
+        check_text_len("funded message", funded_message, limits.max_message_len)?;
+    }
+    if let Some(ref reward_messages) = reward_messages {
+        for reward_message in reward_messages {
+            check_text_len("reward message", &reward_message.message, limits.max_message_len)?;
         }
-
-        if subtitle.is_some() {
-            set_subtitle(deps.storage, subtitle.unwrap())?;
-            updates.push(String::from("subtitle"));
+    }
+    if let Some(ref categories) = categories {
+        if categories.len() > limits.max_categories as usize {
+            return Err(ContractError::TooManyCategories { max: limits.max_categories });
         }
+        validate_categories(deps.as_ref(), categories)?;
+    }
 
-        if description.is_some() {
-            set_description(deps.storage, description.unwrap())?;
-            updates.push(String::from("description"));
-        }
+    let mut updates: Vec<String> = vec![];
 
-        if pledged_message.is_some() {
-            set_pledged_message(deps.storage, pledged_message.unwrap())?;
-            updates.push(String::from("pledged message"));
-        }
+    if let Some(title) = title {
+        set_title(deps.storage, title)?;
+        updates.push(String::from("title"));
+    }
 
-        if funded_message.is_some() {
-            set_funded_message(deps.storage, funded_message.unwrap())?;
-            updates.push(String::from("funded message"));
-        }
+    if let Some(subtitle) = subtitle {
+        set_subtitle(deps.storage, subtitle)?;
+        updates.push(String::from("subtitle"));
+    }
 
-        if reward_messages.is_some() {
-            set_reward_messages(deps.storage, reward_messages.unwrap())?;
-            updates.push(String::from("reward messages"));
-        }
+    if let Some(description) = description {
+        set_description(deps.storage, description)?;
+        updates.push(String::from("description"));
+    }
 
-        if categories.is_some() {
-            set_categories(deps.storage, categories.unwrap())?;
-            updates.push(String::from("categories"));
-        }
+    if let Some(pledged_message) = pledged_message {
+        set_pledged_message(deps.storage, pledged_message)?;
+        updates.push(String::from("pledged message"));
+    }
 
-        if updates.len() > 0 {
-            status = Success;
-            msg = format!("Updated {}", updates.join(", "));
-        } else {
-            status = Failure;
-            msg = format!("Nothing was updated");
-        }
+    if let Some(funded_message) = funded_message {
+        set_funded_message(deps.storage, funded_message)?;
+        updates.push(String::from("funded message"));
+    }
+
+    if let Some(reward_messages) = reward_messages {
+        set_reward_messages(deps.storage, reward_messages)?;
+        updates.push(String::from("reward messages"));
+    }
+
+    if let Some(categories) = categories {
+        set_categories(deps.storage, categories)?;
+        updates.push(String::from("categories"));
+    }
+
+    if updates.is_empty() {
+        return Err(ContractError::NothingToUpdate {});
     }
 
     let mut resp = Response::default();
-    resp.data = Some(to_binary(&ExecuteAnswer::ChangeText { status, msg })?);
+    resp.data = Some(to_binary(&ExecuteAnswer::ChangeText {
+        status: Success,
+        msg: format!("Updated {}", updates.join(", ")),
+    })?);
     Ok(resp)
 }
 
+// checks categories against the registry kept by the platform contract
+fn validate_categories(deps: Deps, categories: &Vec<u16>) -> Result<(), ContractError> {
+    let config = get_config(deps.storage)?;
+    let response: CategoriesResponse = PlatformQueryMsg::Categories {}.query(
+        deps.querier,
+        config.platform_hash.to_string(),
+        deps.api.addr_humanize(&config.platform_contract)?.into_string(),
+    )?;
+    let registry = response.categories.categories;
+    for id in categories {
+        match registry.iter().find(|category| category.id == *id) {
+            None => return Err(ContractError::UnknownCategory { id: *id }),
+            Some(category) if !category.active => return Err(ContractError::RetiredCategory { id: *id }),
+            Some(_) => {}
+        }
+    }
+    Ok(())
+}
+
+// contributions and pay outs are blocked while the platform is paused
+fn check_not_paused(deps: Deps) -> Result<(), ContractError> {
+    let config = get_config(deps.storage)?;
+    let response: PausedResponse = PlatformQueryMsg::Paused {}.query(
+        deps.querier,
+        config.platform_hash.to_string(),
+        deps.api.addr_humanize(&config.platform_contract)?.into_string(),
+    )?;
+    if response.paused.paused {
+        return Err(ContractError::PlatformPaused {});
+    }
+    Ok(())
+}
+
+fn check_text_len(field: &str, text: &str, max: u32) -> Result<(), ContractError> {
+    if text.len() > max as usize {
+        return Err(ContractError::TextTooLong { field: field.to_string(), max });
+    }
+    Ok(())
+}
+
 fn try_receive(
     deps: DepsMut,
     env: Env,
@@ -338,18 +586,22 @@ fn try_receive(
     from: Addr,
     amount: Uint128,
     msg: Option<Binary>,
-) -> StdResult<Response> {
-    let status;
+) -> Result<Response, ContractError> {
     let message;
+    let mut callback = None;
 
-    let token_address = deps.api.addr_canonicalize(&info.sender.as_str())?;
+    let token_address = deps.api.addr_canonicalize(info.sender.as_str())?;
     let config = get_config(deps.storage)?;
     if token_address != config.snip20_contract {
-        return Err(StdError::generic_err("Sender is incorrect SNIP-20 contract"));
+        return Err(ContractError::InvalidToken {});
     }
+    check_not_paused(deps.as_ref())?;
 
     let mut anonymous = false;
-    
+    let mut deposit_bond = false;
+    let mut match_ratio = None;
+    let mut subscribe_tier = None;
+
     if let Some(bin_msg) = msg {
         match from_binary(&bin_msg)? {
             ExecuteReceiveMsg::ReceiveContribution {
@@ -357,77 +609,179 @@ fn try_receive(
             } => {
                 anonymous = anon
             }
+            ExecuteReceiveMsg::DepositBond {} => {
+                deposit_bond = true
+            }
+            ExecuteReceiveMsg::DepositMatch { ratio } => {
+                match_ratio = Some(ratio)
+            }
+            ExecuteReceiveMsg::Subscribe { tier } => {
+                subscribe_tier = Some(tier)
+            }
         }
     }
 
-    let project_status = get_status(deps.storage)?;
-    let deadline = get_deadline(deps.storage)?;
+    let project_status = sync_status(deps.storage, &env.block)?;
+    let bond = get_bond(deps.storage)?;
+    let sender_address_raw = deps.api.addr_canonicalize(from.as_str())?;
+    let amount = amount.u128();
+    let membership = get_membership(deps.storage)?;
+    if membership.is_some() && subscribe_tier.is_none() {
+        return Err(ContractError::MembershipProject {});
+    }
 
-    if project_status == EXPIRED || is_paid_out(deps.storage) {
-        //TODO: change to stderror?
+    if deposit_bond {
+        if sender_address_raw != get_creator(deps.storage)? {
+            return Err(ContractError::Unauthorized {});
+        }
+        if project_status != ProjectStatus::Fundraising {
+            return Err(ContractError::NotFundraising {});
+        }
+        if bond.amount == 0 {
+            return Err(ContractError::BondNotRequired {});
+        }
+        if bond.deposited {
+            return Err(ContractError::BondAlreadyDeposited {});
+        }
+        if amount != bond.amount {
+            return Err(ContractError::BondAmountMismatch { amount: bond.amount });
+        }
 
-        status = Failure;
-        message = String::from("Project is not accepting contributions")
-    } else if env.block.height > deadline {
-        if project_status == FUNDRAISING {
-            set_status(deps.storage, EXPIRED)?;
+        set_bond(deps.storage, &StoredBond {
+            deposited: true,
+            ..bond
+        })?;
+        message = format!("Successfully deposited bond of {}", bond.amount);
+    } else if let Some(tier) = subscribe_tier {
+        let membership = membership.ok_or(ContractError::NotMembershipProject {})?;
+        if project_status != ProjectStatus::Fundraising {
+            return Err(ContractError::NotAcceptingPledges {});
+        }
+        if sender_address_raw == get_creator(deps.storage)? {
+            return Err(ContractError::OwnProject {});
+        }
+        let price = membership.tiers
+            .get(tier as usize)
+            .ok_or(ContractError::InvalidTier { tier })?
+            .price
+            .u128();
+        if amount == 0 || !amount.is_multiple_of(price) {
+            return Err(ContractError::InvalidMembershipPayment { price });
         }
-        status = Failure;
-        message = String::from("Project is not accepting contributions")
-    } else {
-        let amount = amount.u128();
 
+        let current = membership.period_at(env.block.height);
+        let member = match get_member(deps.storage, &sender_address_raw) {
+            Some(member) if member.end > current => {
+                if member.tier != tier {
+                    return Err(ContractError::TierMismatch {});
+                }
+                member
+            }
+            _ => StoredMember { tier, start: current, end: current },
+        };
+        let periods = amount / price;
+        if (member.end - current) as u128 + periods > MAX_PREPAID_PERIODS as u128 {
+            return Err(ContractError::TooManyPeriods { max: MAX_PREPAID_PERIODS });
+        }
+        let end = member.end + periods as u64;
+        // the tier price is revenue for every period up to the new end
+        add_revenue_change(deps.storage, member.end, price as i128)?;
+        add_revenue_change(deps.storage, end, -(price as i128))?;
+        set_member(deps.storage, &sender_address_raw, &StoredMember { end, ..member })?;
+
+        message = format!("Successfully subscribed to tier {} until period {}", tier, end);
+    } else if let Some(ratio) = match_ratio {
+        if !project_status.accepts_pledges(env.block.height, get_deadline(deps.storage)?) {
+            return Err(ContractError::NotAcceptingPledges {});
+        }
+        if bond.amount > 0 && !bond.deposited {
+            return Err(ContractError::AwaitingBond {});
+        }
         if amount == 0 {
-            status = Failure;
-            message = String::from("No coins sent");
-        } else {
-            let total = get_total(deps.storage)?;
-            let sender_address_raw = deps.api.addr_canonicalize(&from.as_str())?;
+            return Err(ContractError::ZeroAmount {});
+        }
+        if ratio == 0 {
+            return Err(ContractError::InvalidMatchRatio {});
+        }
+        if sender_address_raw == get_creator(deps.storage)? {
+            return Err(ContractError::OwnProject {});
+        }
+        if find_match(deps.storage, &sender_address_raw)?.is_some() {
+            return Err(ContractError::MatchExists {});
+        }
+        if get_matches(deps.storage)?.len() as u32 >= MAX_MATCHES {
+            return Err(ContractError::TooManyMatches { max: MAX_MATCHES });
+        }
 
-            // make sure it is not the project creator
-            if sender_address_raw == get_creator(deps.storage)? {
-                return Err(StdError::generic_err("Cannot fund your own project"));
-            }
+        let snip24_rewards_received = get_snip24_reward(deps.storage, deps.api)?
+            .map_or(vec![], |init| init.contributor_vesting_schedule.into_iter().map(|_| false).collect());
+        let rewarded = get_match_rewards(deps.storage);
+        push_match(deps.storage, &StoredMatch {
+            sponsor: sender_address_raw,
+            ratio,
+            cap: amount,
+            matched: 0,
+            rewarded,
+            snip24_rewards_received,
+            refunded: false,
+        })?;
+        // the match applies to pledges already made
+        rebalance_matches(deps.storage)?;
+        if get_total(deps.storage)? >= get_goal(deps.storage)? {
+            set_status(deps.storage, ProjectStatus::Succeeded)?;
+        }
 
-            let snip24_reward_init = get_snip24_reward(deps.storage, deps.api)?;
-            let snip24_rewards_received: Vec<bool>;
-            if snip24_reward_init.is_none() {
-                snip24_rewards_received = vec![];
-            } else {
-                let snip24_reward_init = snip24_reward_init.unwrap();
-                snip24_rewards_received = snip24_reward_init.contributor_vesting_schedule.into_iter().map(|_| false).collect();
-            }
-            add_funds(deps.storage, &sender_address_raw, anonymous, amount, snip24_rewards_received)?;
+        message = format!("Successfully deposited match of up to {}", amount);
+    } else {
+        if !project_status.accepts_pledges(env.block.height, get_deadline(deps.storage)?) {
+            return Err(ContractError::NotAcceptingPledges {});
+        }
+        if bond.amount > 0 && !bond.deposited {
+            return Err(ContractError::AwaitingBond {});
+        }
+        if amount == 0 {
+            return Err(ContractError::ZeroAmount {});
+        }
+        // make sure it is not the project creator
+        if sender_address_raw == get_creator(deps.storage)? {
+            return Err(ContractError::OwnProject {});
+        }
 
-            let goal = get_goal(deps.storage)?;
+        let pledged = get_funder(deps.storage, &sender_address_raw).map_or(0, |funder| funder.amount);
+        let pledge_minmax = get_pledge_minmax(deps.storage)?;
+        if pledged + amount < pledge_minmax.min || pledged + amount > pledge_minmax.max {
+            return Err(ContractError::PledgeOutOfBounds { min: pledge_minmax.min, max: pledge_minmax.max });
+        }
 
-            if total + amount >= goal {
-                set_status(deps.storage, SUCCESSFUL)?;
+        let snip24_reward_init = get_snip24_reward(deps.storage, deps.api)?;
+        let mut weighted = amount;
+        let snip24_rewards_received: Vec<bool> = match snip24_reward_init {
+            None => vec![],
+            Some(snip24_reward_init) => {
+                if let Some(bonus_curve) = snip24_reward_init.bonus_curve {
+                    let multiplier = bonus_curve.multiplier_at(env.block.height, get_deadline(deps.storage)?);
+                    weighted = (U256::from(amount) * U256::from(multiplier) / U256::from(PER_MILLE)).as_u128();
+                }
+                snip24_reward_init.contributor_vesting_schedule.into_iter().map(|_| false).collect()
             }
+        };
+        add_funds(deps.storage, &sender_address_raw, anonymous, amount, weighted, snip24_rewards_received)?;
+        rebalance_matches(deps.storage)?;
+        callback = Some(backing_callback(deps.as_ref(), from, pledged + amount, BackingChange::Pledge)?);
+
+        let goal = get_goal(deps.storage)?;
 
-            status = Success;
-            message = format!("Successfully contributed {}", amount);
+        // sponsor matches count toward the goal
+        if get_total(deps.storage)? >= goal {
+            set_status(deps.storage, ProjectStatus::Succeeded)?;
         }
-    }
 
-    let mut messages = vec![];
-    if status == Failure {
-        // return coins to sender
-        let snip20_transfer_msg = transfer_msg(
-            from.into_string(), 
-            amount, 
-            None, 
-            None, 
-            256, 
-            config.snip20_hash, 
-            deps.api.addr_humanize(&config.snip20_contract)?.into_string(),
-        )?;
-        messages.push(snip20_transfer_msg);
+        message = format!("Successfully contributed {}", amount);
     }
 
-    let mut resp = Response::new().add_messages(messages);
+    let mut resp = Response::new().add_messages(callback);
     resp.data = Some(to_binary(&ExecuteAnswer::Receive {
-        status,
+        status: Success,
         msg: message,
     })?);
     Ok(resp)
@@ -435,33 +789,32 @@ fn try_receive(
 
 fn try_cancel(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
-) -> StdResult<Response> {
-    let mut response_status: ResponseStatus = Failure;
-    let mut msg: String = String::from("");
-
-    let sender_address_raw = deps.api.addr_canonicalize(&info.sender.as_str())?;
+) -> Result<Response, ContractError> {
+    let sender_address_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
     let creator = get_creator(deps.storage)?;
     if sender_address_raw != creator {
-        return Err(StdError::generic_err("Unauthorized"));
+        return Err(ContractError::Unauthorized {});
     }
 
-    let status = get_status(deps.storage)?;
+    let status = sync_status(deps.storage, &env.block)?;
 
-    if status == EXPIRED {
-        msg = String::from("Cannot cancel an expired project");
-    } else if status == SUCCESSFUL || is_paid_out(deps.storage) {
-        msg = String::from("Cannot cancel a funded project");
-    } else if status == FUNDRAISING {
-        response_status = Success;
-        set_status(deps.storage, EXPIRED)?;
+    match status {
+        // cancelling a successful project refunds backers and forfeits the creator bond to them
+        ProjectStatus::Fundraising | ProjectStatus::Succeeded => {
+            set_status(deps.storage, ProjectStatus::Cancelled)?;
+            close_membership(deps.storage, env.block.height)?;
+        }
+        _ => {
+            return Err(ContractError::NotCancellable {});
+        }
     }
 
     let mut resp = Response::default();
     resp.data = Some(to_binary(&ExecuteAnswer::Cancel {
-        status: response_status,
-        msg,
+        status: Success,
+        msg: String::from(""),
     })?);
     Ok(resp)
 }
@@ -470,45 +823,44 @@ pub fn try_refund(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-) -> StdResult<Response> {
-    let response_status;
-    let msg;
-
-    let mut messages = vec![];
-    let status = get_status(deps.storage)?;
-    let deadline = get_deadline(deps.storage)?;
-    let deadman = get_deadman(deps.storage)?;
-    if is_paid_out(deps.storage) || (status == SUCCESSFUL && deadline + deadman > env.block.height) {
-        response_status = Failure;
-        msg = String::from("Cannot receive refund after project successfully funded");
-    } else {
-        let sender_address_raw = deps.api.addr_canonicalize(&info.sender.as_str())?;
-        let refund_amount = clear_funds(deps.storage, &sender_address_raw)?;
+) -> Result<Response, ContractError> {
+    let status = sync_status(deps.storage, &env.block)?;
+    if !status.is_refundable() {
+        return Err(ContractError::NotRefundable {});
+    }
 
-        if refund_amount == 0 {
-            response_status = Failure;
-            msg = String::from("No funds to refund");
-        } else {
-            let config = get_config(deps.storage)?;
-            let snip20_transfer_msg = transfer_msg(
-                info.sender.into_string(), 
-                Uint128::from(refund_amount), 
-                None, 
-                None, 
-                256, 
-                config.snip20_hash, 
-                deps.api.addr_humanize(&config.snip20_contract)?.into_string(),
-            )?;
-            messages.push(snip20_transfer_msg);
-            response_status = Success;
-            msg = format!("{} refunded", refund_amount);
-        }
+    let sender_address_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if get_funder(deps.storage, &sender_address_raw).is_err() {
+        return Err(ContractError::NothingToRefund {});
+    }
+    let bond_share = claim_bond_share(deps.storage, &sender_address_raw)?;
+    let refund_amount = clear_funds(deps.storage, &sender_address_raw)? + bond_share;
+    if refund_amount == 0 {
+        return Err(ContractError::NothingToRefund {});
     }
+    // matches follow backers out while pledges are still open
+    if status == ProjectStatus::Fundraising {
+        rebalance_matches(deps.storage)?;
+    }
+
+    let backing_msg = backing_callback(deps.as_ref(), info.sender.clone(), 0, BackingChange::Refund)?;
+    let config = get_config(deps.storage)?;
+    let snip20_transfer_msg = transfer_msg(
+        info.sender.into_string(), 
+        Uint128::from(refund_amount), 
+        None, 
+        None, 
+        256, 
+        config.snip20_hash, 
+        deps.api.addr_humanize(&config.snip20_contract)?.into_string(),
+    )?;
 
-    let mut resp = Response::new().add_messages(messages);
+    let mut resp = Response::new()
+        .add_message(snip20_transfer_msg)
+        .add_message(backing_msg);
     resp.data = Some(to_binary(&ExecuteAnswer::Refund {
-        status: response_status,
-        msg,
+        status: Success,
+        msg: format!("{} refunded", refund_amount),
     })?);
     Ok(resp)
 }
@@ -517,141 +869,121 @@ fn try_pay_out(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-) -> StdResult<Response> {
-    let response_status;
-    let msg;
-
-    let sender_address_raw = deps.api.addr_canonicalize(&info.sender.as_str())?;
+) -> Result<Response, ContractError> {
+    let sender_address_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
     let creator = get_creator(deps.storage)?;
     if sender_address_raw != creator {
-        return Err(StdError::generic_err("Unauthorized"));
+        return Err(ContractError::Unauthorized {});
     }
 
-    if is_paid_out(deps.storage) {
-        return Err(StdError::generic_err("Already paid out"));
-    }
-
-    let mut transfer_message: Option<CosmosMsg> = None;
-    let mut instantiate_message: Option<CosmosMsg> = None;
-    let status = get_status(deps.storage)?;
+    let status = sync_status(deps.storage, &env.block)?;
     let deadline = get_deadline(deps.storage)?;
-    let deadman = get_deadman(deps.storage)?;
 
     // time has completed and it is successful
     //   and deadman time has not elapsed
-    if env.block.height > deadline && status == SUCCESSFUL {
-        if deadline + deadman < env.block.height {
-            response_status = Failure;
-            msg = String::from(
-                "Project was funded, but the deadman time has elapsed and funding has expired"
-            );
-        } else {
-            let total = get_total(deps.storage)?;
-            let config = get_config(deps.storage)?;
-            transfer_message = Some(transfer_msg(
-                info.sender.clone().into_string(), 
-                Uint128::from(total), 
-                None, 
-                None, 
-                256, 
-                config.snip20_hash, 
-                deps.api.addr_humanize(&config.snip20_contract)?.into_string(),
-            )?);
-            msg = format!("Pay out {} tokens", total);
-    
-            paid_out(deps.storage)?;
-
-            // handle snip24 reward
-
-            // create the snip24 contract and allocate coins to project contract
-            // all vesting will be handled by this contract
-            let snip24_reward_init = get_snip24_reward(deps.storage, deps.api)?;
-            if snip24_reward_init.is_some() {
-                let snip24_reward_init = snip24_reward_init.unwrap();
-                let mut initial_balance = Uint128::from(0_u128);
-                for event in snip24_reward_init.contributor_vesting_schedule {
-                    initial_balance = initial_balance + event.amount;
-                }
-                for event in snip24_reward_init.creator_vesting_schedule {
-                    initial_balance = initial_balance + event.amount;
-                }
+    match status {
+        ProjectStatus::PaidOut => return Err(ContractError::AlreadyPaidOut {}),
+        ProjectStatus::DeadmanExpired => return Err(ContractError::DeadmanExpired {}),
+        ProjectStatus::Succeeded if env.block.height > deadline => {}
+        _ => return Err(ContractError::NotPayable {}),
+    }
+    check_not_paused(deps.as_ref())?;
 
-                // Creating a message to create new snip24 token
-                instantiate_message = Some(CosmosMsg::Wasm(WasmMsg::Instantiate {
-                    code_id: snip24_reward_init.reward_snip24_code_id,
-                    code_hash: snip24_reward_init.reward_snip24_code_hash,
-                    msg: to_binary(&Snip24InstantiateMsg {
-                        admin: snip24_reward_init.admin,
-                        name: snip24_reward_init.name.clone(),
-                        symbol: snip24_reward_init.symbol.clone(),
-                        decimals: snip24_reward_init.decimals,
-                        initial_balances: Some(vec![
-                            InitialBalance {
-                                // allocate all coins to the project contract
-                                address: env.contract.address.clone(),
-                                amount: initial_balance,
-                            }
-                        ]),
-                        config: Some(InitConfig {
-                            public_total_supply: Some(snip24_reward_init.public_total_supply),
-                            enable_deposit: Some(snip24_reward_init.enable_deposit),
-                            enable_redeem: Some(snip24_reward_init.enable_redeem),
-                            enable_mint: Some(snip24_reward_init.enable_mint),
-                            enable_burn: Some(snip24_reward_init.enable_burn),
-                        }),
-                        prng_seed: to_binary(
-                            &sha_256(
-                                [
-                                    &get_random_number_generator(deps.storage).next_u64().to_be_bytes(), 
-                                    to_binary(&env)?.0.as_slice(),
-                                    to_binary(&info)?.0.as_slice(),
-                                ].concat().as_slice()
-                            )
-                        )?, 
-                    })?,
-                    funds: vec![],
-                    label: format!("{}-{}-{}", snip24_reward_init.name, snip24_reward_init.symbol, env.block.height),
-                }));
-            }
+    let total = get_total(deps.storage)?;
+    let config = get_config(deps.storage)?;
+    let transfer_message = transfer_msg(
+        info.sender.clone().into_string(), 
+        Uint128::from(total), 
+        None, 
+        None, 
+        256, 
+        config.snip20_hash, 
+        deps.api.addr_humanize(&config.snip20_contract)?.into_string(),
+    )?;
+
+    set_status(deps.storage, ProjectStatus::PaidOut)?;
 
-            response_status = Success;
+    // handle snip24 reward
+
+    // create the snip24 contract and allocate coins to project contract
+    // all vesting will be handled by this contract
+    let mut submessages: Vec<SubMsg> = vec![];
+    let snip24_reward_init = get_snip24_reward(deps.storage, deps.api)?;
+    if let Some(snip24_reward_init) = snip24_reward_init {
+        let mut initial_balance = Uint128::from(0_u128);
+        for event in snip24_reward_init.contributor_vesting_schedule {
+            initial_balance += event.amount;
         }
-    } else {
-        if env.block.height > deadline && status == FUNDRAISING {
-            set_status(deps.storage, EXPIRED)?;
+        for event in snip24_reward_init.creator_vesting_schedule {
+            initial_balance += event.amount;
+        }
+        for vesting in snip24_reward_init.contributor_linear_vesting.iter().chain(snip24_reward_init.creator_linear_vesting.iter()) {
+            initial_balance += vesting.amount;
+        }
+        for bucket in snip24_reward_init.allocation_buckets.iter().flatten() {
+            for event in bucket.vesting_schedule.iter() {
+                initial_balance += event.amount;
+            }
         }
-        response_status = Failure;
-        msg = String::from(
-            "Cannot receive pay out unless project successfully funded and deadline past",
-        );
-    }
 
-    let mut submessages: Vec<SubMsg> = vec![];
-    if instantiate_message.is_some() {
-        let instantiate_submsg = SubMsg::reply_on_success(instantiate_message.unwrap(), SNIP24_INSTANTIATE_REPLY_ID);
-        submessages.push(instantiate_submsg);
-    }
-    if transfer_message.is_some() {
-        submessages.push(SubMsg::new(transfer_message.unwrap()));
+        // Creating a message to create new snip24 token
+        let instantiate_message = CosmosMsg::Wasm(WasmMsg::Instantiate {
+            admin: None,
+            code_id: snip24_reward_init.reward_snip24_code_id,
+            code_hash: snip24_reward_init.reward_snip24_code_hash,
+            msg: to_binary(&Snip24InstantiateMsg {
+                admin: snip24_reward_init.admin,
+                name: snip24_reward_init.name.clone(),
+                symbol: snip24_reward_init.symbol.clone(),
+                decimals: snip24_reward_init.decimals,
+                initial_balances: Some(vec![
+                    InitialBalance {
+                        // allocate all coins to the project contract
+                        address: env.contract.address.clone(),
+                        amount: initial_balance,
+                    }
+                ]),
+                config: Some(InitConfig {
+                    public_total_supply: Some(snip24_reward_init.public_total_supply),
+                    enable_deposit: Some(snip24_reward_init.enable_deposit),
+                    enable_redeem: Some(snip24_reward_init.enable_redeem),
+                    enable_mint: Some(snip24_reward_init.enable_mint),
+                    enable_burn: Some(snip24_reward_init.enable_burn),
+                }),
+                prng_seed: to_binary(
+                    &sha_256(
+                        [
+                            &get_random_number_generator(deps.storage).next_u64().to_be_bytes(), 
+                            to_binary(&env)?.0.as_slice(),
+                            to_binary(&info)?.0.as_slice(),
+                        ].concat().as_slice()
+                    )
+                )?, 
+            })?,
+            funds: vec![],
+            label: format!("{}-{}-{}", snip24_reward_init.name, snip24_reward_init.symbol, env.block.height),
+        });
+        submessages.push(SubMsg::reply_on_success(instantiate_message, SNIP24_INSTANTIATE_REPLY_ID));
     }
+    submessages.push(SubMsg::new(transfer_message));
+
     let mut resp = Response::new().add_submessages(submessages);
     resp.data = Some(to_binary(&ExecuteAnswer::PayOut {
-        status: response_status,
-        msg,
+        status: Success,
+        msg: format!("Pay out {} tokens", total),
     })?);
     Ok(resp)
 }
 
+// rewards for a pledge of reward weight `amount`, or for a rewarded sponsor match of `amount`
 fn calculate_contributor_snip24_rewards(
     storage: &dyn Storage,
     api: &dyn Api,
-    address: CanonicalAddr,
+    amount: u128,
 ) -> StdResult<Option<Vec<VestingReward>>> {
-    let result: Vec<VestingReward>;
     let snip24_reward_init = get_snip24_reward(storage, api)?;
-    match snip24_reward_init {
+    let result: Vec<VestingReward> = match snip24_reward_init {
         Some(snip24_reward_init) => { 
-            let funder = get_funder(storage, &address)?;
 /* total calc needs fix
             let valid_amount: u128;
             let min_contribution = snip24_reward_init.minimum_contribution.unwrap_or(Uint128::from(0_u128)).u128();
@@ -668,20 +1000,20 @@ fn calculate_contributor_snip24_rewards(
                 }
 */
 
-                let total = get_total(storage)?;
+                let total = get_rewarded_total(storage)?;
 
                 let mut contributor_tokens = Uint128::from(0_u128);
                 for event in snip24_reward_init.contributor_vesting_schedule.clone() {
-                    contributor_tokens = contributor_tokens + event.amount;
+                    contributor_tokens += event.amount;
                 }
 
                 // assume linear allocation (TODO: others)
                 let total_reward_u256: U256 = U256::from(contributor_tokens.u128())
                     //.checked_mul(U256::from(valid_amount)).expect("Overflow when calculating reward")
-                    .checked_mul(U256::from(funder.amount)).expect("Overflow when calculating reward")
+                    .checked_mul(U256::from(amount)).expect("Overflow when calculating reward")
                     .checked_div(U256::from(total)).expect("Div by zero when calculating reward");
 
-                result = snip24_reward_init.contributor_vesting_schedule
+                snip24_reward_init.contributor_vesting_schedule
                     .into_iter()
                     .map(|event| {
                         let partial_reward_u256: U256 = total_reward_u256
@@ -692,7 +1024,7 @@ fn calculate_contributor_snip24_rewards(
                             amount: partial_reward_u256.as_u128(),
                         }
                     })
-                    .collect();
+                    .collect()
 /*
             }
 */
@@ -706,17 +1038,16 @@ fn calculate_creator_snip24_allocation(
     storage: &dyn Storage,
     api: &dyn Api,
 ) -> StdResult<Option<Vec<VestingReward>>> {
-    let result: Vec<VestingReward>;
     let snip24_reward_init = get_snip24_reward(storage, api)?;
-    match snip24_reward_init {
+    let result: Vec<VestingReward> = match snip24_reward_init {
         Some(snip24_reward_init) => { 
             let mut creator_tokens = Uint128::from(0_u128);
             for event in snip24_reward_init.creator_vesting_schedule.clone() {
-                creator_tokens = creator_tokens + event.amount;
+                creator_tokens += event.amount;
             }
             let total_allocation_u256: U256 = U256::from(creator_tokens.u128());
             
-            result = snip24_reward_init.creator_vesting_schedule
+            snip24_reward_init.creator_vesting_schedule
             .into_iter()
             .map(|event| {
                 let partial_reward_u256: U256 = total_allocation_u256
@@ -727,7 +1058,7 @@ fn calculate_creator_snip24_allocation(
                     amount: partial_reward_u256.as_u128(),
                 }
             })
-            .collect();
+            .collect()
         },
         None => { return Ok(None) }
     };
@@ -739,204 +1070,973 @@ fn try_claim_reward(
     env: Env,
     info: MessageInfo,
     idx: u32,
-) -> StdResult<Response> {
-    let response_status;
-    let msg;
-    let idx: usize = idx as usize;
+) -> Result<Response, ContractError> {
+    let status = get_effective_status(deps.storage, &env.block)?;
+    if status != ProjectStatus::PaidOut {
+        return Err(ContractError::NotPaidOut {});
+    }
 
-    let mut transfer_message: Option<CosmosMsg> = None;
-    let status = get_status(deps.storage)?;
-    if status == SUCCESSFUL && is_paid_out(deps.storage) {
-        let sender_address_raw = deps.api.addr_canonicalize(&info.sender.as_str())?;
-        let is_creator = get_creator(deps.storage)? == sender_address_raw;
-        if is_creator {
-            let creator_allocation = calculate_creator_snip24_allocation(deps.storage, deps.api)?;
-            if creator_allocation.is_none() {
-                return Err(StdError::generic_err("No snip24 reward for this project"));
-            }
-            let creator_allocation = creator_allocation.unwrap();
-            let mut allocation_received = get_creator_snip24_allocation_received(deps.storage)?;
-            let snip24_rewards: Vec<VestingRewardStatus> = creator_allocation
-                .into_iter()
-                .enumerate()
-                .map(|(index, reward)| {
-                    VestingRewardStatus { 
-                        amount: Uint128::from(reward.amount), 
-                        block: reward.block, 
-                        received: allocation_received[index],
-                    }
-                })
-                .collect();
-            if snip24_rewards[idx].received {
-                response_status = Failure;
-                msg = String::from("Already claimed reward");
-            } else if env.block.height < snip24_rewards[idx].block {
-                response_status = Failure;
-                msg = String::from("Vesting time has not been reached");
-            } else {
-                let reward_snip24_address = get_snip24_reward_address(deps.storage)?;
-                let reward_snip24_init = get_snip24_reward(deps.storage, deps.api)?;
-                if reward_snip24_address.is_none() || reward_snip24_init.is_none() {
-                    return Err(StdError::generic_err("No reward snip24 contract"));
-                }
-                let snip24_reward_address = reward_snip24_address.unwrap();
-                let snip24_reward_init = reward_snip24_init.unwrap();
-                transfer_message = Some(transfer_msg(
-                    info.sender.clone().into_string(), 
-                    Uint128::from(snip24_rewards[idx].amount.u128()), 
-                    None, 
-                    None, 
-                    256, 
-                    snip24_reward_init.reward_snip24_code_hash, 
-                    deps.api.addr_humanize(&snip24_reward_address)?.into_string(),
-                )?);
- 
-                allocation_received[idx] = true;
-                set_creator_snip24_allocation_received(deps.storage, allocation_received)?;
-
-                response_status = Success;
-                msg = format!("Receive {} tokens", snip24_rewards[idx].amount.u128());
-            }
-        } else { // !is_creator
-            let mut funder = get_funder(deps.storage, &sender_address_raw)?;
-            let contributor_reward = calculate_contributor_snip24_rewards(deps.storage, deps.api, sender_address_raw.clone())?;
-            if contributor_reward.is_none() {
-                return Err(StdError::generic_err("No snip24 reward for this project"));
-            }
-            let contributor_reward = contributor_reward.unwrap();
-            let snip24_rewards: Vec<VestingRewardStatus> = contributor_reward
-                .into_iter()
-                .enumerate()
-                .map(|(index, reward)| {
-                    VestingRewardStatus {
-                        amount: Uint128::from(reward.amount),
-                        block: reward.block,
-                        received: funder.snip24_rewards_received[index],
-                    }
-                })
-                .collect();
-            
-            if snip24_rewards[idx].received {
-                response_status = Failure;
-                msg = String::from("Already claimed reward");
-            } else if env.block.height < snip24_rewards[idx].block {
-                response_status = Failure;
-                msg = String::from("Vesting time has not been reached");
-            } else {
-                let config = get_config(deps.storage)?;
-                transfer_message = Some(transfer_msg(
-                    info.sender.clone().into_string(), 
-                    Uint128::from(snip24_rewards[idx].amount.u128()), 
-                    None, 
-                    None, 
-                    256, 
-                    config.snip20_hash, 
-                    deps.api.addr_humanize(&config.snip20_contract)?.into_string(),
-                )?);
-
-                funder.snip24_rewards_received[idx] = true;
-                set_funder(
-                    deps.storage, 
-                    &sender_address_raw, 
-                    funder.idx, 
-                    funder.anonymous, 
-                    funder.amount, 
-                    funder.snip24_rewards_received
-                )?;
-
-                response_status = Success;
-                msg = format!("Receive {} tokens", snip24_rewards[idx].amount.u128());
-            }
+    let transfer_message: CosmosMsg;
+    let amount: u128;
+    let sender_address_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let is_creator = get_creator(deps.storage)? == sender_address_raw;
+    if is_creator {
+        let creator_allocation = calculate_creator_snip24_allocation(deps.storage, deps.api)?
+            .ok_or(ContractError::NoReward {})?;
+        let mut allocation_received = get_creator_snip24_allocation_received(deps.storage)?;
+        let reward = creator_allocation
+            .get(idx as usize)
+            .ok_or(ContractError::InvalidRewardIdx { idx })?;
+        if allocation_received[idx as usize] {
+            return Err(ContractError::AlreadyClaimed {});
+        }
+        if env.block.height < reward.block {
+            return Err(ContractError::NotVested { block: reward.block });
         }
-    } else {
-        response_status = Failure;
-        msg = String::from("Cannot claim reward");
-    }
 
-    let mut submessages: Vec<SubMsg> = vec![];
-    if transfer_message.is_some() {
-        submessages.push(SubMsg::new(transfer_message.unwrap()));
-    }
-    let mut resp = Response::new().add_submessages(submessages);
-    resp.data = Some(to_binary(&ExecuteAnswer::ClaimReward {
-        status: response_status,
-        msg,
-    })?);
+        let reward_snip24_address = get_snip24_reward_address(deps.storage)?;
+        let reward_snip24_init = get_snip24_reward(deps.storage, deps.api)?;
+        if reward_snip24_address.is_none() || reward_snip24_init.is_none() {
+            return Err(ContractError::NoRewardContract {});
+        }
+        let snip24_reward_address = reward_snip24_address.unwrap();
+        let snip24_reward_init = reward_snip24_init.unwrap();
+        amount = reward.amount;
+        transfer_message = transfer_msg(
+            info.sender.clone().into_string(), 
+            Uint128::from(amount), 
+            None, 
+            None, 
+            256, 
+            snip24_reward_init.reward_snip24_code_hash, 
+            deps.api.addr_humanize(&snip24_reward_address)?.into_string(),
+        )?;
+
+        allocation_received[idx as usize] = true;
+        set_creator_snip24_allocation_received(deps.storage, allocation_received)?;
+    } else { // !is_creator
+        let mut funder = get_funder(deps.storage, &sender_address_raw)
+            .map_err(|_| ContractError::NoPledge {})?;
+        let contributor_reward = calculate_contributor_snip24_rewards(deps.storage, deps.api, funder.weighted)?
+            .ok_or(ContractError::NoReward {})?;
+        let reward = contributor_reward
+            .get(idx as usize)
+            .ok_or(ContractError::InvalidRewardIdx { idx })?;
+        if funder.snip24_rewards_received[idx as usize] {
+            return Err(ContractError::AlreadyClaimed {});
+        }
+        if env.block.height < reward.block {
+            return Err(ContractError::NotVested { block: reward.block });
+        }
+
+        let config = get_config(deps.storage)?;
+        amount = reward.amount;
+        transfer_message = transfer_msg(
+            info.sender.clone().into_string(), 
+            Uint128::from(amount), 
+            None, 
+            None, 
+            256, 
+            config.snip20_hash, 
+            deps.api.addr_humanize(&config.snip20_contract)?.into_string(),
+        )?;
+
+        funder.snip24_rewards_received[idx as usize] = true;
+        set_funder(deps.storage, &sender_address_raw, &funder)?;
+    }
+
+    let mut resp = Response::new().add_submessage(SubMsg::new(transfer_message));
+    resp.data = Some(to_binary(&ExecuteAnswer::ClaimReward {
+        status: Success,
+        msg: format!("Receive {} tokens", amount),
+    })?);
+    Ok(resp)
+}
+
+// share of each vesting event of a bucket for one recipient, any remainder stays with the project
+// share of a bucket recipient in each vesting event, the last recipient also gets what is left
+// of the even split so the whole bucket is paid out
+fn calculate_bucket_allocation(bucket: &StoredAllocationBucket, recipient: &CanonicalAddr) -> Vec<VestingReward> {
+    let recipients = bucket.recipients.len() as u128;
+    let last = bucket.recipients.last() == Some(recipient);
+    bucket.vesting_schedule
+        .iter()
+        .map(|event| {
+            let amount = event.amount.u128();
+            VestingReward {
+                block: event.block,
+                amount: amount / recipients + if last { amount % recipients } else { 0 },
+            }
+        })
+        .collect()
+}
+
+fn try_claim_bucket_reward(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    bucket: u16,
+    idx: u32,
+) -> Result<Response, ContractError> {
+    let status = get_effective_status(deps.storage, &env.block)?;
+    if status != ProjectStatus::PaidOut {
+        return Err(ContractError::NotPaidOut {});
+    }
+
+    let sender_address_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let stored_bucket = get_allocation_buckets(deps.storage)?
+        .into_iter()
+        .nth(bucket as usize)
+        .ok_or(ContractError::InvalidBucket { bucket })?;
+    if !stored_bucket.recipients.contains(&sender_address_raw) {
+        return Err(ContractError::NotBucketRecipient {});
+    }
+    let allocation = calculate_bucket_allocation(&stored_bucket, &sender_address_raw);
+    let reward = allocation
+        .get(idx as usize)
+        .ok_or(ContractError::InvalidRewardIdx { idx })?;
+    let mut received = get_bucket_received(deps.storage, bucket, &sender_address_raw, allocation.len());
+    if received[idx as usize] {
+        return Err(ContractError::AlreadyClaimed {});
+    }
+    if env.block.height < reward.block {
+        return Err(ContractError::NotVested { block: reward.block });
+    }
+
+    let snip24_reward_address = get_snip24_reward_address(deps.storage)?
+        .ok_or(ContractError::NoRewardContract {})?;
+    let snip24_reward_init = get_snip24_reward(deps.storage, deps.api)?
+        .ok_or(ContractError::NoRewardContract {})?;
+    let transfer_message = transfer_msg(
+        info.sender.into_string(), 
+        Uint128::from(reward.amount), 
+        None, 
+        None, 
+        256, 
+        snip24_reward_init.reward_snip24_code_hash, 
+        deps.api.addr_humanize(&snip24_reward_address)?.into_string(),
+    )?;
+    received[idx as usize] = true;
+    set_bucket_received(deps.storage, bucket, &sender_address_raw, &received)?;
+
+    let mut resp = Response::new().add_message(transfer_message);
+    resp.data = Some(to_binary(&ExecuteAnswer::ClaimBucketReward {
+        status: Success,
+        msg: format!("Receive {} tokens", reward.amount),
+    })?);
+    Ok(resp)
+}
+
+// reward weight of a contributor, its weighted pledge plus a rewarded sponsor match
+fn contributor_reward_weight(storage: &dyn Storage, address: &CanonicalAddr) -> StdResult<u128> {
+    Ok(get_funder(storage, address).map_or(0, |funder| funder.weighted)
+        + find_match(storage, address)?
+            .filter(|(_, sponsor_match)| sponsor_match.rewarded)
+            .map_or(0, |(_, sponsor_match)| sponsor_match.matched))
+}
+
+// linear vesting that applies to an address with its whole allocation, either the creator's
+// allocation or a contributor share by pledge weight and rewarded sponsor match
+fn linear_vesting_allocation(
+    storage: &dyn Storage,
+    api: &dyn Api,
+    address: &CanonicalAddr,
+) -> StdResult<Option<(LinearVesting, u128)>> {
+    let snip24_reward_init = match get_snip24_reward(storage, api)? {
+        Some(snip24_reward_init) => snip24_reward_init,
+        None => return Ok(None),
+    };
+    if *address == get_creator(storage)? {
+        return Ok(snip24_reward_init.creator_linear_vesting.map(|vesting| {
+            let total = vesting.amount.u128();
+            (vesting, total)
+        }));
+    }
+    let vesting = match snip24_reward_init.contributor_linear_vesting {
+        Some(vesting) => vesting,
+        None => return Ok(None),
+    };
+    let weight = contributor_reward_weight(storage, address)?;
+    let rewarded_total = get_rewarded_total(storage)?;
+    if weight == 0 || rewarded_total == 0 {
+        return Ok(None);
+    }
+    let total = (U256::from(vesting.amount.u128()) * U256::from(weight) / U256::from(rewarded_total)).as_u128();
+    Ok(Some((vesting, total)))
+}
+
+fn try_claim_vested(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let status = get_effective_status(deps.storage, &env.block)?;
+    if status != ProjectStatus::PaidOut {
+        return Err(ContractError::NotPaidOut {});
+    }
+
+    let sender_address_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let (vesting, total) = linear_vesting_allocation(deps.storage, deps.api, &sender_address_raw)?
+        .ok_or(ContractError::NoReward {})?;
+    if env.block.height < vesting.cliff {
+        return Err(ContractError::NotVested { block: vesting.cliff });
+    }
+    let claimed = get_vested_claimed(deps.storage, &sender_address_raw);
+    let amount = vesting.vested(total, env.block.height).saturating_sub(claimed);
+    if amount == 0 {
+        return Err(ContractError::AlreadyClaimed {});
+    }
+
+    let snip24_reward_address = get_snip24_reward_address(deps.storage)?
+        .ok_or(ContractError::NoRewardContract {})?;
+    let snip24_reward_init = get_snip24_reward(deps.storage, deps.api)?
+        .ok_or(ContractError::NoRewardContract {})?;
+    let transfer_message = transfer_msg(
+        info.sender.into_string(), 
+        Uint128::from(amount), 
+        None, 
+        None, 
+        256, 
+        snip24_reward_init.reward_snip24_code_hash, 
+        deps.api.addr_humanize(&snip24_reward_address)?.into_string(),
+    )?;
+    set_vested_claimed(deps.storage, &sender_address_raw, claimed + amount)?;
+
+    let mut resp = Response::new().add_message(transfer_message);
+    resp.data = Some(to_binary(&ExecuteAnswer::ClaimVested {
+        status: Success,
+        msg: format!("Receive {} tokens", amount),
+    })?);
+    Ok(resp)
+}
+
+fn try_transfer_pledge(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: Addr,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let amount = amount.u128();
+
+    let sender_address_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let recipient_address_raw = deps.api.addr_canonicalize(recipient.as_str())?;
+    let sender = get_funder(deps.storage, &sender_address_raw);
+    if sender.is_err() || sender.as_ref().unwrap().amount == 0 {
+        return Err(ContractError::NoPledge {});
+    }
+    let sender = sender.unwrap();
+
+    let status = sync_status(deps.storage, &env.block)?;
+    if !matches!(status, ProjectStatus::Fundraising | ProjectStatus::Succeeded | ProjectStatus::PaidOut) {
+        return Err(ContractError::NotTransferable {});
+    }
+    if amount == 0 {
+        return Err(ContractError::ZeroAmount {});
+    }
+    if amount > sender.amount {
+        return Err(ContractError::InvalidTransferAmount {});
+    }
+    if recipient_address_raw == sender_address_raw || recipient_address_raw == get_creator(deps.storage)? {
+        return Err(ContractError::InvalidRecipient {});
+    }
+
+    let pledge_minmax = get_pledge_minmax(deps.storage)?;
+    let remaining = sender.amount - amount;
+    let recipient_funder = get_funder(deps.storage, &recipient_address_raw).ok();
+    let recipient_amount = recipient_funder.as_ref().map_or(0, |funder| funder.amount) + amount;
+    if (remaining > 0 && remaining < pledge_minmax.min) ||
+        recipient_amount < pledge_minmax.min || recipient_amount > pledge_minmax.max {
+        return Err(ContractError::PledgeOutOfBounds { min: pledge_minmax.min, max: pledge_minmax.max });
+    }
+    if recipient_funder.is_some_and(|funder| {
+        // reward status is per pledge, so it cannot be merged with a different one
+        funder.amount > 0 && (
+            funder.snip24_rewards_received != sender.snip24_rewards_received ||
+            funder.bond_share_claimed != sender.bond_share_claimed
+        )
+    }) {
+        return Err(ContractError::RewardStatusMismatch {});
+    }
+
+    let sender_weight = contributor_reward_weight(deps.storage, &sender_address_raw)?;
+    let moved_weight = transfer_funds(deps.storage, &sender_address_raw, &recipient_address_raw, amount)?;
+
+    // linearly vested tokens already claimed go with the weight they were claimed against, rounded
+    // up so the transfer can never free up tokens to claim twice
+    let claimed = get_vested_claimed(deps.storage, &sender_address_raw);
+    if claimed > 0 && sender_weight > 0 {
+        let moved_claimed = if moved_weight == sender_weight {
+            claimed
+        } else {
+            ((U256::from(claimed) * U256::from(moved_weight) + U256::from(sender_weight - 1))
+                / U256::from(sender_weight)).as_u128()
+        };
+        set_vested_claimed(deps.storage, &sender_address_raw, claimed - moved_claimed)?;
+        let recipient_claimed = get_vested_claimed(deps.storage, &recipient_address_raw);
+        set_vested_claimed(deps.storage, &recipient_address_raw, recipient_claimed + moved_claimed)?;
+    }
+
+    // a default vote cannot outweigh the pledge left behind
+    if get_default_vote(deps.storage, &sender_address_raw) > remaining {
+        set_default_vote(deps.storage, &sender_address_raw, remaining)?;
+    }
+
+    let mut resp = Response::new()
+        .add_message(backing_callback(deps.as_ref(), info.sender, remaining, BackingChange::Transfer)?)
+        .add_message(backing_callback(deps.as_ref(), recipient, recipient_amount, BackingChange::Transfer)?);
+    resp.data = Some(to_binary(&ExecuteAnswer::TransferPledge {
+        status: Success,
+        msg: format!("Transferred {}", amount),
+    })?);
+    Ok(resp)
+}
+
+fn try_set_match_rewards(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    rewarded: bool,
+) -> Result<Response, ContractError> {
+    let sender_address_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if sender_address_raw != get_creator(deps.storage)? {
+        return Err(ContractError::Unauthorized {});
+    }
+    let status = sync_status(deps.storage, &env.block)?;
+    if !status.accepts_pledges(env.block.height, get_deadline(deps.storage)?) {
+        return Err(ContractError::NotAcceptingPledges {});
+    }
+
+    set_match_rewards(deps.storage, rewarded)?;
+
+    let mut resp = Response::default();
+    resp.data = Some(to_binary(&ExecuteAnswer::SetMatchRewards {
+        status: Success,
+        msg: format!("New matches earn rewards: {}", rewarded),
+    })?);
+    Ok(resp)
+}
+
+fn try_refund_match(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let sender_address_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let (idx, sponsor_match) = find_match(deps.storage, &sender_address_raw)?
+        .ok_or(ContractError::NoMatch {})?;
+    if sponsor_match.refunded {
+        return Err(ContractError::NothingToRefund {});
+    }
+
+    let status = sync_status(deps.storage, &env.block)?;
+    let deadline = get_deadline(deps.storage)?;
+    let refund_amount = match status {
+        // the matched part leaves the total along with the backers' pledges
+        ProjectStatus::Failed
+        | ProjectStatus::Cancelled
+        | ProjectStatus::DeadmanExpired
+        | ProjectStatus::Delisted => {
+            let total = get_total(deps.storage)?;
+            set_total(deps.storage, total - sponsor_match.matched)?;
+            set_match(deps.storage, idx, &StoredMatch {
+                matched: 0,
+                refunded: true,
+                ..sponsor_match.clone()
+            })?;
+            sponsor_match.cap
+        }
+        // the matched part stays with the project, only the unused cap is returned
+        ProjectStatus::PaidOut | ProjectStatus::Defaulted => {
+            set_match(deps.storage, idx, &StoredMatch {
+                refunded: true,
+                ..sponsor_match.clone()
+            })?;
+            sponsor_match.cap - sponsor_match.matched
+        }
+        ProjectStatus::Succeeded if env.block.height > deadline => {
+            set_match(deps.storage, idx, &StoredMatch {
+                refunded: true,
+                ..sponsor_match.clone()
+            })?;
+            sponsor_match.cap - sponsor_match.matched
+        }
+        _ => return Err(ContractError::MatchLocked {}),
+    };
+    if refund_amount == 0 {
+        return Err(ContractError::NothingToRefund {});
+    }
+
+    let config = get_config(deps.storage)?;
+    let snip20_transfer_msg = transfer_msg(
+        info.sender.into_string(), 
+        Uint128::from(refund_amount), 
+        None, 
+        None, 
+        256, 
+        config.snip20_hash, 
+        deps.api.addr_humanize(&config.snip20_contract)?.into_string(),
+    )?;
+
+    let mut resp = Response::new().add_message(snip20_transfer_msg);
+    resp.data = Some(to_binary(&ExecuteAnswer::RefundMatch {
+        status: Success,
+        msg: format!("{} refunded", refund_amount),
+    })?);
+    Ok(resp)
+}
+
+fn try_claim_match_reward(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    idx: u32,
+) -> Result<Response, ContractError> {
+    let status = get_effective_status(deps.storage, &env.block)?;
+    if status != ProjectStatus::PaidOut {
+        return Err(ContractError::NotPaidOut {});
+    }
+
+    let sender_address_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let (match_idx, mut sponsor_match) = find_match(deps.storage, &sender_address_raw)?
+        .ok_or(ContractError::NoMatch {})?;
+    if !sponsor_match.rewarded || sponsor_match.matched == 0 {
+        return Err(ContractError::NoReward {});
+    }
+    let match_reward = calculate_contributor_snip24_rewards(deps.storage, deps.api, sponsor_match.matched)?
+        .ok_or(ContractError::NoReward {})?;
+    let reward = match_reward
+        .get(idx as usize)
+        .ok_or(ContractError::InvalidRewardIdx { idx })?;
+    if sponsor_match.snip24_rewards_received[idx as usize] {
+        return Err(ContractError::AlreadyClaimed {});
+    }
+    if env.block.height < reward.block {
+        return Err(ContractError::NotVested { block: reward.block });
+    }
+
+    let reward_snip24_address = get_snip24_reward_address(deps.storage)?;
+    let reward_snip24_init = get_snip24_reward(deps.storage, deps.api)?;
+    if reward_snip24_address.is_none() || reward_snip24_init.is_none() {
+        return Err(ContractError::NoRewardContract {});
+    }
+    let amount = reward.amount;
+    let transfer_message = transfer_msg(
+        info.sender.into_string(), 
+        Uint128::from(amount), 
+        None, 
+        None, 
+        256, 
+        reward_snip24_init.unwrap().reward_snip24_code_hash, 
+        deps.api.addr_humanize(&reward_snip24_address.unwrap())?.into_string(),
+    )?;
+
+    sponsor_match.snip24_rewards_received[idx as usize] = true;
+    set_match(deps.storage, match_idx, &sponsor_match)?;
+
+    let mut resp = Response::new().add_message(transfer_message);
+    resp.data = Some(to_binary(&ExecuteAnswer::ClaimMatchReward {
+        status: Success,
+        msg: format!("Receive {} tokens", amount),
+    })?);
+    Ok(resp)
+}
+
+fn try_cancel_membership(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let membership = get_membership(deps.storage)?.ok_or(ContractError::NotMembershipProject {})?;
+    let sender_address_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let member = get_member(deps.storage, &sender_address_raw).ok_or(ContractError::NoMembership {})?;
+    let refund_from = membership.refund_from(env.block.height);
+    if member.end <= refund_from {
+        return Err(ContractError::NothingToRefund {});
+    }
+
+    let price = membership.tiers[member.tier as usize].price.u128();
+    add_revenue_change(deps.storage, refund_from, -(price as i128))?;
+    add_revenue_change(deps.storage, member.end, price as i128)?;
+    let refund_amount = (member.end - refund_from) as u128 * price;
+    set_member(deps.storage, &sender_address_raw, &StoredMember { end: refund_from, ..member })?;
+
+    let config = get_config(deps.storage)?;
+    let snip20_transfer_msg = transfer_msg(
+        info.sender.into_string(), 
+        Uint128::from(refund_amount), 
+        None, 
+        None, 
+        256, 
+        config.snip20_hash, 
+        deps.api.addr_humanize(&config.snip20_contract)?.into_string(),
+    )?;
+
+    let mut resp = Response::new().add_message(snip20_transfer_msg);
+    resp.data = Some(to_binary(&ExecuteAnswer::CancelMembership {
+        status: Success,
+        msg: format!("{} refunded", refund_amount),
+    })?);
+    Ok(resp)
+}
+
+fn try_withdraw_membership(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let sender_address_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if sender_address_raw != get_creator(deps.storage)? {
+        return Err(ContractError::Unauthorized {});
+    }
+    let mut membership = get_membership(deps.storage)?.ok_or(ContractError::NotMembershipProject {})?;
+    check_not_paused(deps.as_ref())?;
+
+    let until = membership.paid_until(env.block.height).min(membership.withdrawn + MAX_WITHDRAW_PERIODS);
+    if until <= membership.withdrawn {
+        return Err(ContractError::NothingToWithdraw {});
+    }
+    let mut amount = 0_u128;
+    for period in membership.withdrawn..until {
+        membership.rate = (membership.rate as i128 + get_revenue_change(deps.storage, period)) as u128;
+        amount += membership.rate;
+    }
+    membership.withdrawn = until;
+    set_membership(deps.storage, &membership)?;
+
+    // periods without members are still marked as withdrawn
+    let mut resp = Response::new();
+    if amount > 0 {
+        let config = get_config(deps.storage)?;
+        resp = resp.add_message(transfer_msg(
+            info.sender.into_string(), 
+            Uint128::from(amount), 
+            None, 
+            None, 
+            256, 
+            config.snip20_hash, 
+            deps.api.addr_humanize(&config.snip20_contract)?.into_string(),
+        )?);
+    }
+    resp.data = Some(to_binary(&ExecuteAnswer::WithdrawMembership {
+        status: Success,
+        msg: format!("Withdrew {} for periods up to {}", amount, until),
+    })?);
+    Ok(resp)
+}
+
+fn try_claim_bond(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let sender_address_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if sender_address_raw != get_creator(deps.storage)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let status = sync_status(deps.storage, &env.block)?;
+    let bond = get_bond(deps.storage)?;
+    if !bond.deposited {
+        return Err(ContractError::NoBond {});
+    }
+    if bond.returned {
+        return Err(ContractError::BondAlreadyReturned {});
+    }
+    if bond.forfeit_total.is_some() {
+        return Err(ContractError::BondForfeited {});
+    }
+    let release_block = get_bond_release_block(deps.storage)?;
+    let claimable = match status {
+        ProjectStatus::Failed | ProjectStatus::Cancelled | ProjectStatus::Delisted => true,
+        ProjectStatus::PaidOut => env.block.height >= release_block,
+        _ => false,
+    };
+    if !claimable {
+        return Err(ContractError::BondLocked { release_block });
+    }
+
+    set_bond(deps.storage, &StoredBond {
+        returned: true,
+        ..bond
+    })?;
+    let config = get_config(deps.storage)?;
+    let snip20_transfer_msg = transfer_msg(
+        info.sender.into_string(), 
+        Uint128::from(bond.amount), 
+        None, 
+        None, 
+        256, 
+        config.snip20_hash, 
+        deps.api.addr_humanize(&config.snip20_contract)?.into_string(),
+    )?;
+
+    let mut resp = Response::new().add_message(snip20_transfer_msg);
+    resp.data = Some(to_binary(&ExecuteAnswer::ClaimBond {
+        status: Success,
+        msg: format!("{} bond returned", bond.amount),
+    })?);
+    Ok(resp)
+}
+
+fn try_claim_bond_share(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    // persist a pending deadman expiry so the bond is forfeited first
+    sync_status(deps.storage, &env.block)?;
+    let sender_address_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let share = claim_bond_share(deps.storage, &sender_address_raw)?;
+    if share == 0 {
+        return Err(ContractError::NoBondShare {});
+    }
+
+    let config = get_config(deps.storage)?;
+    let snip20_transfer_msg = transfer_msg(
+        info.sender.into_string(), 
+        Uint128::from(share), 
+        None, 
+        None, 
+        256, 
+        config.snip20_hash, 
+        deps.api.addr_humanize(&config.snip20_contract)?.into_string(),
+    )?;
+
+    let mut resp = Response::new().add_message(snip20_transfer_msg);
+    resp.data = Some(to_binary(&ExecuteAnswer::ClaimBondShare {
+        status: Success,
+        msg: format!("{} bond share received", share),
+    })?);
+    Ok(resp)
+}
+
+fn try_vote_default(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    default: bool,
+) -> Result<Response, ContractError> {
+    let sender_address_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let funder = get_funder(deps.storage, &sender_address_raw);
+    if funder.is_err() || funder.as_ref().unwrap().amount == 0 {
+        return Err(ContractError::NoPledge {});
+    }
+    let funder = funder.unwrap();
+
+    let status = sync_status(deps.storage, &env.block)?;
+    if status != ProjectStatus::PaidOut {
+        return Err(ContractError::NotPaidOut {});
+    }
+    if !get_bond(deps.storage)?.deposited {
+        return Err(ContractError::NoBond {});
+    }
+    if env.block.height >= get_bond_release_block(deps.storage)? {
+        return Err(ContractError::BondReleased {});
+    }
+
+    let weight = if default { funder.amount } else { 0 };
+    let default_votes = set_default_vote(deps.storage, &sender_address_raw, weight)?;
+    // majority of pledged amount forfeits the bond to backers
+    let msg = if default_votes * 2 > get_backer_total(deps.storage)? {
+        set_status(deps.storage, ProjectStatus::Defaulted)?;
+        String::from("Vote recorded, creator has defaulted")
+    } else {
+        String::from("Vote recorded")
+    };
+
+    let mut resp = Response::default();
+    resp.data = Some(to_binary(&ExecuteAnswer::VoteDefault {
+        default_votes: Uint128::from(default_votes),
+        status: Success,
+        msg,
+    })?);
     Ok(resp)
 }
 
 pub fn try_comment(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     comment: String,
-) -> StdResult<Response> {
-    let mut response_status = Failure;
-    let msg;
+) -> Result<Response, ContractError> {
+    let status = sync_status(deps.storage, &env.block)?;
+    if status != ProjectStatus::Fundraising {
+        return Err(ContractError::NotFundraising {});
+    }
 
-    let status = get_status(deps.storage)?;
+    let creator = get_creator(deps.storage)?;
+    let from_creator = deps.api.addr_canonicalize(info.sender.as_str())? == creator;
+    push_comment(deps.storage, comment, from_creator)?;
+
+    let mut resp = Response::default();
+    resp.data = Some(to_binary(&ExecuteAnswer::Cancel {
+        status: Success,
+        msg: String::from("Comment added"),
+    })?);
+    Ok(resp)
+}
+
+pub fn try_flag_spam(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    flag: bool,
+) -> Result<Response, ContractError> {
+    let was_hidden = get_spam_count(deps.storage)? >= SPAM_HIDE_THRESHOLD;
+    set_spam_flag(deps.storage, &deps.api.addr_canonicalize(info.sender.as_str())?, flag)?;
+    let spam_count = get_spam_count(deps.storage)?;
+    let hidden = spam_count >= SPAM_HIDE_THRESHOLD;
+
+    let mut resp = Response::default();
+    if hidden != was_hidden {
+        let config = get_config(deps.storage)?;
+        let callback = PlatformExecuteMsg::UpdateHidden { hidden }.to_cosmos_msg(
+            config.platform_hash,
+            deps.api.addr_humanize(&config.platform_contract)?.into_string(),
+            None,
+        )?;
+        resp = resp.add_message(callback);
+    }
+    resp.data = Some(to_binary(&ExecuteAnswer::FlagSpam {
+        spam_count,
+        status: Success,
+        msg: String::from("Flag spam updated"),
+    })?);
+    Ok(resp)
+}
+
+fn try_clear_spam_flags(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = get_config(deps.storage)?;
+    if deps.api.addr_canonicalize(info.sender.as_str())? != config.platform_contract {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // the platform unhides the project itself
+    clear_spam_flags(deps.storage)?;
+
+    let mut resp = Response::default();
+    resp.data = Some(to_binary(&ExecuteAnswer::ClearSpamFlags { status: Success })?);
+    Ok(resp)
+}
 
-    if status == EXPIRED {
-        msg = String::from("Cannot comment on an expired project");
-    } else if status == SUCCESSFUL || is_paid_out(deps.storage) {
-        msg = String::from("Cannot comment on a funded project");
+fn try_delist(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = get_config(deps.storage)?;
+    if deps.api.addr_canonicalize(info.sender.as_str())? != config.platform_contract {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // a project that already ended keeps its status, the platform still delists it
+    let status = sync_status(deps.storage, &env.block)?;
+    let msg = if status.can_transition_to(ProjectStatus::Delisted) {
+        set_status(deps.storage, ProjectStatus::Delisted)?;
+        close_membership(deps.storage, env.block.height)?;
+        String::from("Project delisted, pledges are refundable")
     } else {
-        let creator = get_creator(deps.storage)?;
-        let from_creator: bool;
-        if deps.api.addr_canonicalize(info.sender.as_str())? == creator {
-            from_creator = true;
-        } else {
-            from_creator = false;
-        }
-        push_comment(deps.storage, comment, from_creator)?;
-        msg = String::from("Comment added");
-        response_status = Success;
+        format!("Project delisted with final status {:?}", status)
+    };
+
+    let mut resp = Response::default();
+    resp.data = Some(to_binary(&ExecuteAnswer::Delist { status: Success, msg })?);
+    Ok(resp)
+}
+
+fn try_set_fulfillment_key(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    public_key: Binary,
+) -> Result<Response, ContractError> {
+    let sender_address_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let creator = get_creator(deps.storage)?;
+    if sender_address_raw != creator {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if public_key.is_empty() {
+        return Err(ContractError::EmptyFulfillmentKey {});
+    }
+    set_fulfillment_public_key(deps.storage, Some(public_key.0))?;
+
+    let mut resp = Response::default();
+    resp.data = Some(to_binary(&ExecuteAnswer::SetFulfillmentKey {
+        status: Success,
+        msg: String::from("Fulfillment public key set"),
+    })?);
+    Ok(resp)
+}
+
+fn try_submit_fulfillment_info(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    fulfillment_info: Binary,
+) -> Result<Response, ContractError> {
+    let sender_address_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let pledged = match get_funder(deps.storage, &sender_address_raw) {
+        Ok(stored_funder) => stored_funder.amount > 0,
+        Err(_) => false,
+    };
+
+    if !pledged && !is_active_member(deps.storage, &sender_address_raw, env.block.height)? {
+        return Err(ContractError::NoPledge {});
+    }
+    if get_fulfillment_public_key(deps.storage).is_none() {
+        return Err(ContractError::NoFulfillmentKey {});
+    }
+    if fulfillment_info.is_empty() || fulfillment_info.len() > MAX_FULFILLMENT_INFO_SIZE {
+        return Err(ContractError::InvalidFulfillmentInfo { max: MAX_FULFILLMENT_INFO_SIZE });
+    }
+    set_fulfillment_info(deps.storage, &sender_address_raw, fulfillment_info.0, env.block.height)?;
+
+    let mut resp = Response::default();
+    resp.data = Some(to_binary(&ExecuteAnswer::SubmitFulfillmentInfo {
+        status: Success,
+        msg: String::from("Fulfillment info submitted"),
+    })?);
+    Ok(resp)
+}
+
+// a member is active while their paid periods cover the current one and the membership is not closed
+fn member_active(membership: &StoredMembership, member: &StoredMember, current: u64) -> bool {
+    member.is_active(current) && membership.closed.is_none_or(|closed| current <= closed)
+}
+
+fn is_active_member(storage: &dyn Storage, addr: &CanonicalAddr, height: u64) -> StdResult<bool> {
+    let membership = match get_membership(storage)? {
+        Some(membership) => membership,
+        None => return Ok(false),
+    };
+    Ok(match get_member(storage, addr) {
+        Some(member) => member_active(&membership, &member, membership.period_at(height)),
+        None => false,
+    })
+}
+
+fn try_create_survey(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    questions: Vec<SurveyQuestion>,
+    threshold: Uint128,
+    closes_at: u64,
+) -> Result<Response, ContractError> {
+    let sender_address_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let creator = get_creator(deps.storage)?;
+    if sender_address_raw != creator {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if questions.is_empty() || questions.len() > MAX_SURVEY_QUESTIONS {
+        return Err(ContractError::InvalidSurvey {
+            reason: format!("Surveys must have between 1 and {} questions", MAX_SURVEY_QUESTIONS),
+        });
+    }
+    if let Some(reason) = questions.iter().find_map(|question| question.validate().err()) {
+        return Err(ContractError::InvalidSurvey { reason });
+    }
+    if closes_at <= env.block.height {
+        return Err(ContractError::InvalidSurvey {
+            reason: String::from("Survey must close at a future block"),
+        });
+    }
+    let valid_threshold = threshold.is_zero() || get_reward_messages(deps.storage)?
+        .iter()
+        .any(|reward_message| reward_message.threshold == threshold);
+    if !valid_threshold {
+        return Err(ContractError::InvalidSurvey {
+            reason: String::from("Survey threshold must be zero or match a reward message threshold"),
+        });
+    }
+
+    let id = push_survey(deps.storage, questions, threshold.u128(), closes_at)?;
+
+    let mut resp = Response::default();
+    resp.data = Some(to_binary(&ExecuteAnswer::CreateSurvey {
+        survey_id: Some(id),
+        status: Success,
+        msg: format!("Created survey {}", id),
+    })?);
+    Ok(resp)
+}
+
+fn try_answer_survey(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    survey_id: u32,
+    answers: Vec<SurveyAnswer>,
+) -> Result<Response, ContractError> {
+    let sender_address_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let amount = match get_funder(deps.storage, &sender_address_raw) {
+        Ok(stored_funder) => stored_funder.amount,
+        Err(_) => 0_u128,
+    };
+
+    let survey = get_survey(deps.storage, survey_id)
+        .map_err(|_| ContractError::SurveyNotFound { id: survey_id })?;
+    if amount == 0 || amount < survey.threshold {
+        return Err(ContractError::BelowSurveyThreshold {});
+    }
+    if env.block.height > survey.closes_at {
+        return Err(ContractError::SurveyClosed {});
+    }
+    if answers.len() != survey.questions.len() {
+        return Err(ContractError::InvalidSurveyAnswers {
+            reason: format!("Expected {} answers", survey.questions.len()),
+        });
+    }
+    let answer_error = survey.questions
+        .iter()
+        .zip(answers.iter())
+        .find_map(|(question, answer)| question.check_answer(answer).err());
+    if let Some(reason) = answer_error {
+        return Err(ContractError::InvalidSurveyAnswers { reason });
     }
 
-    let mut resp = Response::default();
-    resp.data = Some(to_binary(&ExecuteAnswer::Cancel {
-        status: response_status,
-        msg,
-    })?);
-    Ok(resp)
-}
+    set_survey_response(deps.storage, survey_id, &sender_address_raw, answers, env.block.height)?;
 
-pub fn try_flag_spam(
-    deps: DepsMut,
-    _env: Env,
-    info: MessageInfo,
-    flag: bool,
-) -> StdResult<Response> {
-    set_spam_flag(deps.storage, &deps.api.addr_canonicalize(&info.sender.as_str())?, flag)?;
-    let spam_count = get_spam_count(deps.storage)?;
     let mut resp = Response::default();
-    resp.data = Some(to_binary(&ExecuteAnswer::FlagSpam {
-        spam_count,
+    resp.data = Some(to_binary(&ExecuteAnswer::AnswerSurvey {
         status: Success,
-        msg: String::from("Flag spam updated"),
+        msg: String::from("Survey answered"),
     })?);
     Ok(resp)
 }
 
 #[entry_point]
-pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> StdResult<Response> {
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let from_version = migrate_storage(deps, &env)?;
+    Ok(Response::new()
+        .add_attribute("from_version", from_version.to_string())
+        .add_attribute("to_version", CONTRACT_VERSION.to_string()))
+}
+
+#[entry_point]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
     match msg.id {
         SNIP24_INSTANTIATE_REPLY_ID => handle_instantiate_reply(deps, msg),
-        id => Err(StdError::generic_err(format!("Unknown reply id: {}", id))),
+        id => Err(ContractError::UnknownReplyId { id }),
     }
 }
 
-fn handle_instantiate_reply(deps: DepsMut, msg: Reply) -> StdResult<Response> {
+fn handle_instantiate_reply(deps: DepsMut, msg: Reply) -> Result<Response, ContractError> {
     // Handle the msg data and save the contract address
     // See: https://github.com/CosmWasm/cw-plus/blob/main/packages/utils/src/parse_reply.rs
-    let res = parse_reply_instantiate_data(msg);
-    if res.is_ok() {
-        let res = res.unwrap();
-        // Save res.contract_address
-        set_snip24_reward_address(deps.storage, Some(deps.api.addr_canonicalize(&res.contract_address)?))?;
-    } else {
-        let err = res.err().unwrap().to_string();
-        return Err(StdError::generic_err(err));
+    match parse_reply_instantiate_data(msg) {
+        Ok(res) => {
+            // Save res.contract_address
+            set_snip24_reward_address(deps.storage, Some(deps.api.addr_canonicalize(&res.contract_address)?))?;
+        }
+        Err(err) => return Err(StdError::generic_err(err.to_string()).into()),
     }
 
     Ok(Response::new())
@@ -945,21 +2045,32 @@ fn handle_instantiate_reply(deps: DepsMut, msg: Reply) -> StdResult<Response> {
 #[entry_point]
 pub fn query(
     deps: Deps, 
-    _env: Env, 
+    env: Env, 
     msg: QueryMsg
-) -> StdResult<Binary> {
+) -> Result<Binary, ContractError> {
     match msg {
-        QueryMsg::Status {} => query_status(deps),
-        QueryMsg::StatusWithPermit { permit } => query_status_with_permit(deps, &permit),
-        QueryMsg::Comments { page, page_size } => query_comments(deps, page, page_size),
-        _ => authenticated_queries(deps, msg),
+        QueryMsg::Status {} => Ok(query_status(deps, env)?),
+        QueryMsg::StatusWithPermit { permit } => Ok(query_status_with_permit(deps, env, &permit)?),
+        QueryMsg::Comments { start_after, limit, order } => {
+            query_comments(deps, start_after, limit, order.unwrap_or(SortOrder::Ascending))
+        }
+        QueryMsg::Matches {} => Ok(query_matches(deps)?),
+        QueryMsg::FulfillmentInfoWithPermit { permit, start_after, limit, order } => {
+            query_fulfillment_info_with_permit(deps, &permit, start_after, limit, order.unwrap_or(SortOrder::Ascending))
+        }
+        QueryMsg::SurveysWithPermit { permit } => Ok(query_surveys_with_permit(deps, &permit)?),
+        QueryMsg::SurveyResultsWithPermit { permit, survey_id, start_after, limit, order } => {
+            query_survey_results_with_permit(deps, &permit, survey_id, start_after, limit, order.unwrap_or(SortOrder::Ascending))
+        }
+        _ => authenticated_queries(deps, env, msg),
     }
 }
 
 fn authenticated_queries(
     deps: Deps,
+    env: Env,
     msg: QueryMsg,
-) -> StdResult<Binary> {
+) -> Result<Binary, ContractError> {
     let (addresses, key) = msg.get_validation_params();
 
     for address in addresses {
@@ -967,45 +2078,135 @@ fn authenticated_queries(
 
         let expected_key = read_viewing_key(deps.storage, &canonical_addr);
 
-        if expected_key.is_none() {
-            // Checking the key will take significant time. We don't want to exit immediately if it isn't set
-            // in a way which will allow to time the command and determine if a viewing key doesn't exist
-            key.check_viewing_key(&[0u8; VIEWING_KEY_SIZE]);
-        } else if key.check_viewing_key(expected_key.unwrap().as_slice()) {
-            return match msg {
-                // Base
-                QueryMsg::StatusAuth { address, .. } => query_status_auth(deps, &address),
-                _ => panic!("This query type does not require authentication"),
-            };
+        match expected_key {
+            None => {
+                // Checking the key will take significant time. We don't want to exit immediately if it isn't set
+                // in a way which will allow to time the command and determine if a viewing key doesn't exist
+                key.check_viewing_key(&[0u8; VIEWING_KEY_SIZE]);
+            }
+            Some(expected_key) if key.check_viewing_key(expected_key.as_slice()) => {
+                return match msg {
+                    // Base
+                    QueryMsg::StatusAuth { address, .. } => Ok(query_status_auth(deps, env, &address)?),
+                    QueryMsg::FulfillmentInfo { address, start_after, limit, order, .. } => {
+                        query_fulfillment_info(deps, &address, start_after, limit, order.unwrap_or(SortOrder::Ascending))
+                    }
+                    QueryMsg::Surveys { address, .. } => Ok(query_surveys(deps, &address)?),
+                    QueryMsg::SurveyResults { address, survey_id, start_after, limit, order, .. } => {
+                        query_survey_results(deps, &address, survey_id, start_after, limit, order.unwrap_or(SortOrder::Ascending))
+                    }
+                    _ => panic!("This query type does not require authentication"),
+                };
+            }
+            Some(_) => {}
         }
     }
 
-    Err(StdError::generic_err("Unauthorized"))
+    Err(ContractError::Unauthorized {})
 }
 
-fn query_comments(deps: Deps, page: u32, page_size: u32) -> StdResult<Binary> {
-    let comments = get_comments(deps.storage, page, page_size)?;
-    to_binary(&QueryAnswer::Comments { comments })
+fn check_page_size(page_size: u32) -> Result<u32, ContractError> {
+    if !(1..=MAX_PAGE_SIZE).contains(&page_size) {
+        return Err(ContractError::InvalidPageSize { max: MAX_PAGE_SIZE });
+    }
+    Ok(page_size)
 }
 
-fn query_status(deps: Deps) -> StdResult<Binary> {
-    let status_string;
+fn query_comments(
+    deps: Deps,
+    start_after: Option<u32>,
+    limit: Option<u32>,
+    order: SortOrder,
+) -> Result<Binary, ContractError> {
+    let limit = check_page_size(limit.unwrap_or(DEFAULT_PAGE_SIZE))?;
+    let comments = get_comments(deps.storage, start_after, limit, order)?
+        .into_iter()
+        .map(|(id, comment)| Comment {
+            id,
+            comment: comment.comment,
+            from_creator: comment.from_creator,
+        })
+        .collect();
+    Ok(to_binary(&QueryAnswer::Comments { comments })?)
+}
 
-    let status = get_status(deps.storage)?;
-    if status == FUNDRAISING {
-        status_string = String::from("fundraising");
-    } else if status == EXPIRED {
-        status_string = String::from("expired");
-    } else if status == SUCCESSFUL {
-        status_string = String::from("successful");
-    } else {
-        return Err(StdError::generic_err("Error getting status"));
+fn query_matches(deps: Deps) -> StdResult<Binary> {
+    let matches = get_matches(deps.storage)?
+        .into_iter()
+        .map(|sponsor_match| Ok(SponsorMatch {
+            sponsor: deps.api.addr_humanize(&sponsor_match.sponsor)?,
+            ratio: sponsor_match.ratio,
+            cap: Uint128::from(sponsor_match.cap),
+            matched: Uint128::from(sponsor_match.matched),
+            rewarded: sponsor_match.rewarded,
+            refunded: sponsor_match.refunded,
+        }))
+        .collect::<StdResult<Vec<SponsorMatch>>>()?;
+    to_binary(&QueryAnswer::Matches {
+        matches,
+        rewarded: get_match_rewards(deps.storage),
+    })
+}
+
+fn bond_info(deps: Deps, status: ProjectStatus) -> StdResult<Option<BondInfo>> {
+    let bond = get_bond(deps.storage)?;
+    if bond.amount == 0 {
+        return Ok(None);
+    }
+    Ok(Some(BondInfo {
+        amount: Uint128::from(bond.amount),
+        deposited: bond.deposited,
+        forfeited: bond.deposited && (bond.forfeit_total.is_some() || status == ProjectStatus::DeadmanExpired),
+        returned: bond.returned,
+        release_block: get_bond_release_block(deps.storage)?,
+        default_votes: Uint128::from(get_default_votes(deps.storage)),
+    }))
+}
+
+fn membership_info(membership: StoredMembership, height: u64) -> MembershipInfo {
+    MembershipInfo {
+        period: membership.period,
+        start: membership.start,
+        current_period: membership.period_at(height),
+        tiers: membership.tiers,
+        closed: membership.closed,
     }
+}
+
+fn snip24_info(deps: Deps) -> StdResult<Option<Snip24Info>> {
+    let snip24_address = get_snip24_reward_address(deps.storage)?
+        .map(|addr| deps.api.addr_humanize(&addr))
+        .transpose()?;
+    Ok(get_snip24_reward(deps.storage, deps.api)?.map(|token| Snip24Info {
+        name: token.name,
+        symbol: token.symbol,
+        decimals: token.decimals,
+        public_total_supply: token.public_total_supply,
+        enable_deposit: token.enable_deposit,
+        enable_redeem: token.enable_redeem,
+        enable_mint: token.enable_mint,
+        enable_burn: token.enable_burn,
+        minimum_contribution: token.minimum_contribution,
+        maximum_contribution: token.maximum_contribution,
+        contributor_vesting_schedule: token.contributor_vesting_schedule,
+        creator_vesting_schedule: token.creator_vesting_schedule,
+        contribution_weight: token.contribution_weight,
+        bonus_curve: token.bonus_curve,
+        contributor_linear_vesting: token.contributor_linear_vesting,
+        creator_linear_vesting: token.creator_linear_vesting,
+        allocation_buckets: token.allocation_buckets.unwrap_or_default(),
+        contract_address: snip24_address,
+        contract_hash: Some(token.reward_snip24_code_hash),
+    }))
+}
+
+fn query_status(deps: Deps, env: Env) -> StdResult<Binary> {
+    let status = get_effective_status(deps.storage, &env.block)?;
 
     let creator = get_creator(deps.storage)?;
     let creator = deps.api.addr_humanize(&creator)?;
 
-    let po = is_paid_out(deps.storage);
+    let po = status == ProjectStatus::PaidOut;
 
     let goal = get_goal(deps.storage)?;
     let goal = Uint128::from(goal);
@@ -1032,31 +2233,11 @@ fn query_status(deps: Deps) -> StdResult<Binary> {
     let snip20_address = deps.api.addr_humanize(&config.snip20_contract)?;
     let minmax_pledge = get_pledge_minmax(deps.storage)?;
 
-    let snip24 = get_snip24_reward(deps.storage, deps.api)?;
-    let snip24_address = get_snip24_reward_address(deps.storage)?.and_then(|addr| Some(deps.api.addr_humanize(&addr).unwrap()));
-    let snip24_info: Option<Snip24Info> = snip24.and_then(|token| { 
-        return Some(Snip24Info {
-            name: token.name,
-            symbol: token.symbol,
-            decimals: token.decimals,
-            public_total_supply: token.public_total_supply,
-            enable_deposit: token.enable_deposit,
-            enable_redeem: token.enable_redeem,
-            enable_mint: token.enable_mint,
-            enable_burn: token.enable_burn,
-            minimum_contribution: token.minimum_contribution,
-            maximum_contribution: token.maximum_contribution,
-            contributor_vesting_schedule: token.contributor_vesting_schedule,
-            creator_vesting_schedule: token.creator_vesting_schedule,
-            contribution_weight: token.contribution_weight,
-            contract_address: snip24_address,
-            contract_hash: Some(token.reward_snip24_code_hash),
-        })
-    });
+    let snip24_info = snip24_info(deps)?;
 
     to_binary(&QueryAnswer::Status {
         creator,
-        status: status_string,
+        status,
         paid_out: po,
         goal,
         total,
@@ -1072,34 +2253,26 @@ fn query_status(deps: Deps) -> StdResult<Binary> {
         minimum_pledge: Uint128::from(minmax_pledge.min),
         maximum_pledge: Uint128::from(minmax_pledge.max),
         snip24_info,
+        fulfillment_public_key: get_fulfillment_public_key(deps.storage).map(Binary),
+        bond: bond_info(deps, status)?,
+        membership: get_membership(deps.storage)?.map(|membership| membership_info(membership, env.block.height)),
     })
 }
 
 fn query_status_auth(
     deps: Deps,
+    env: Env,
     address: &Addr,
 ) -> StdResult<Binary> {
-    let status_string;
-
-    let status = get_status(deps.storage)?;
-
-    if status == FUNDRAISING {
-        status_string = String::from("fundraising");
-    } else if status == EXPIRED {
-        status_string = String::from("expired");
-    } else if status == SUCCESSFUL {
-        status_string = String::from("successful");
-    } else {
-        return Err(StdError::generic_err("Error getting status"));
-    }
+    let status = get_effective_status(deps.storage, &env.block)?;
 
-    let sender_address_raw = deps.api.addr_canonicalize(&address.as_str())?;
+    let sender_address_raw = deps.api.addr_canonicalize(address.as_str())?;
     let creator = get_creator(deps.storage)?;
     let is_creator = creator == sender_address_raw;
 
     let creator = deps.api.addr_humanize(&creator)?;
 
-    let po = is_paid_out(deps.storage);
+    let po = status == ProjectStatus::PaidOut;
 
     let goal = get_goal(deps.storage)?;
     let goal = Uint128::from(goal);
@@ -1123,27 +2296,7 @@ fn query_status_auth(
     let snip20_address = deps.api.addr_humanize(&config.snip20_contract)?;
     let minmax_pledge = get_pledge_minmax(deps.storage)?;
 
-    let snip24 = get_snip24_reward(deps.storage, deps.api)?;
-    let snip24_address = get_snip24_reward_address(deps.storage)?.and_then(|addr| Some(deps.api.addr_humanize(&addr).unwrap()));
-    let snip24_info: Option<Snip24Info> = snip24.and_then(|token| { 
-        return Some(Snip24Info {
-            name: token.name,
-            symbol: token.symbol,
-            decimals: token.decimals,
-            public_total_supply: token.public_total_supply,
-            enable_deposit: token.enable_deposit,
-            enable_redeem: token.enable_redeem,
-            enable_mint: token.enable_mint,
-            enable_burn: token.enable_burn,
-            minimum_contribution: token.minimum_contribution,
-            maximum_contribution: token.maximum_contribution,
-            contributor_vesting_schedule: token.contributor_vesting_schedule,
-            creator_vesting_schedule: token.creator_vesting_schedule,
-            contribution_weight: token.contribution_weight,
-            contract_address: snip24_address,
-            contract_hash: Some(token.reward_snip24_code_hash),
-        })
-    });
+    let snip24_info = snip24_info(deps)?;
 
     let stored_funder = get_funder(deps.storage, &sender_address_raw);
 
@@ -1152,6 +2305,36 @@ fn query_status_auth(
     let mut reward_messages: Vec<RewardMessage> = vec![];
     let mut snip24_rewards: Option<Vec<VestingRewardStatus>> = None;
     let mut contribution: Option<Uint128> = None;
+    let mut bond_share: Option<Uint128> = None;
+    let mut member: Option<Member> = None;
+    let linear_vesting = linear_vesting_allocation(deps.storage, deps.api, &sender_address_raw)?
+        .map(|(vesting, total)| LinearVestingStatus {
+            total: Uint128::from(total),
+            vested: Uint128::from(vesting.vested(total, env.block.height)),
+            claimed: Uint128::from(get_vested_claimed(deps.storage, &sender_address_raw)),
+        });
+    let bucket_rewards: Vec<BucketRewardStatus> = get_allocation_buckets(deps.storage)?
+        .into_iter()
+        .enumerate()
+        .filter(|(_, bucket)| bucket.recipients.contains(&sender_address_raw))
+        .map(|(idx, bucket)| {
+            let allocation = calculate_bucket_allocation(&bucket, &sender_address_raw);
+            let received = get_bucket_received(deps.storage, idx as u16, &sender_address_raw, allocation.len());
+            BucketRewardStatus {
+                bucket: idx as u16,
+                name: bucket.name,
+                rewards: allocation
+                    .into_iter()
+                    .zip(received)
+                    .map(|(reward, received)| VestingRewardStatus {
+                        amount: Uint128::from(reward.amount),
+                        block: reward.block,
+                        received,
+                    })
+                    .collect(),
+            }
+        })
+        .collect();
 
     if is_creator {
         pledged_message = Some(get_pledged_message(deps.storage));
@@ -1160,9 +2343,8 @@ fn query_status_auth(
 
         let creator_allocation = calculate_creator_snip24_allocation(deps.storage, deps.api)?;
         let allocation_received = get_creator_snip24_allocation_received(deps.storage)?;
-        if creator_allocation.is_some() {
+        if let Some(creator_allocation) = creator_allocation {
             snip24_rewards = Some(creator_allocation
-                .unwrap()
                 .into_iter()
                 .enumerate()
                 .map(|(idx, reward)| {
@@ -1176,48 +2358,73 @@ fn query_status_auth(
             );
         }
     } else {
-        match stored_funder {
-            Ok(stored_funder) => {
-                if stored_funder.amount > 0 {
-                    if status != EXPIRED {
-                        pledged_message = Some(get_pledged_message(deps.storage));
-                    }
-                    if status == SUCCESSFUL && is_paid_out(deps.storage) {
-                        funded_message = Some(get_funded_message(deps.storage));
-                        reward_messages = get_reward_messages(deps.storage)?
-                            .into_iter()
-                            .filter(|reward_message| {
-                                stored_funder.amount >= reward_message.threshold.u128()
-                            })
-                            .collect();
-                    }
+        if let Ok(stored_funder) = stored_funder {
+            if stored_funder.amount > 0 {
+                if matches!(status, ProjectStatus::Fundraising | ProjectStatus::Succeeded | ProjectStatus::PaidOut) {
+                    pledged_message = Some(get_pledged_message(deps.storage));
                 }
-                contribution = Some(Uint128::from(stored_funder.amount));
-
-                let contributor_rewards = calculate_contributor_snip24_rewards(deps.storage, deps.api, sender_address_raw)?;
-                if contributor_rewards.is_some() {
-                    snip24_rewards = Some(contributor_rewards
-                        .unwrap()
+                if status == ProjectStatus::PaidOut {
+                    funded_message = Some(get_funded_message(deps.storage));
+                    reward_messages = get_reward_messages(deps.storage)?
                         .into_iter()
-                        .enumerate()
-                        .map(|(idx, reward)| {
-                            VestingRewardStatus { 
-                                amount: Uint128::from(reward.amount), 
-                                block: reward.block, 
-                                received: stored_funder.snip24_rewards_received[idx],
-                            }
+                        .filter(|reward_message| {
+                            stored_funder.amount >= reward_message.threshold.u128()
                         })
-                        .collect()
-                    );
+                        .collect();
                 }
             }
-            Err(_) => {}
-        };
+            contribution = Some(Uint128::from(stored_funder.amount));
+
+            let bond = get_bond(deps.storage)?;
+            let forfeited = bond.forfeit_total.is_some() || status == ProjectStatus::DeadmanExpired;
+            if forfeited && !stored_funder.bond_share_claimed {
+                bond_share = Some(Uint128::from(
+                    calculate_bond_share(&bond, get_backer_total(deps.storage)?, stored_funder.amount)
+                ));
+            }
+
+            let contributor_rewards = calculate_contributor_snip24_rewards(deps.storage, deps.api, stored_funder.weighted)?;
+            if let Some(contributor_rewards) = contributor_rewards {
+                snip24_rewards = Some(contributor_rewards
+                    .into_iter()
+                    .enumerate()
+                    .map(|(idx, reward)| {
+                        VestingRewardStatus { 
+                            amount: Uint128::from(reward.amount), 
+                            block: reward.block, 
+                            received: stored_funder.snip24_rewards_received[idx],
+                        }
+                    })
+                    .collect()
+                );
+            }
+        }
+
+        // members see the reward messages up to their tier price while the membership is active
+        let membership = get_membership(deps.storage)?;
+        let stored_member = get_member(deps.storage, &sender_address_raw);
+        if let (Some(membership), Some(stored_member)) = (membership, stored_member) {
+            let current = membership.period_at(env.block.height);
+            let active = member_active(&membership, &stored_member, current);
+            if active {
+                let price = membership.tiers[stored_member.tier as usize].price;
+                reward_messages = get_reward_messages(deps.storage)?
+                    .into_iter()
+                    .filter(|reward_message| reward_message.threshold <= price)
+                    .collect();
+            }
+            member = Some(Member {
+                tier: stored_member.tier,
+                start_period: stored_member.start,
+                end_period: stored_member.end,
+                active,
+            });
+        }
     }
 
     to_binary(&QueryAnswer::StatusAuth {
         creator,
-        status: status_string,
+        status,
         paid_out: po,
         goal,
         total,
@@ -1233,18 +2440,148 @@ fn query_status_auth(
         minimum_pledge: Uint128::from(minmax_pledge.min),
         maximum_pledge: Uint128::from(minmax_pledge.max),
         snip24_info,
+        fulfillment_public_key: get_fulfillment_public_key(deps.storage).map(Binary),
+        bond: bond_info(deps, status)?,
+        membership: get_membership(deps.storage)?.map(|membership| membership_info(membership, env.block.height)),
         pledged_message,
         funded_message,
         reward_messages,
         snip24_rewards,
         contribution,
+        bond_share,
+        member,
+        linear_vesting,
+        bucket_rewards: if bucket_rewards.is_empty() { None } else { Some(bucket_rewards) },
     })
 }
 
-fn query_status_with_permit(
+fn query_fulfillment_info(
     deps: Deps,
-    permit: &Permit,
+    address: &Addr,
+    start_after: Option<u32>,
+    limit: Option<u32>,
+    order: SortOrder,
+) -> Result<Binary, ContractError> {
+    let sender_address_raw = deps.api.addr_canonicalize(address.as_str())?;
+    if sender_address_raw != get_creator(deps.storage)? {
+        return Err(ContractError::Unauthorized {});
+    }
+    let limit = check_page_size(limit.unwrap_or(DEFAULT_PAGE_SIZE))?;
+
+    let fulfillment_info: StdResult<Vec<FulfillmentInfo>> = get_fulfillment_infos(deps.storage, start_after, limit, order)?
+        .into_iter()
+        .map(|(idx, funder_addr, stored_info)| -> StdResult<FulfillmentInfo> {
+            // members who subscribed without pledging have no funder record
+            let (anonymous, contribution) = match get_funder(deps.storage, &funder_addr) {
+                Ok(stored_funder) => (stored_funder.anonymous, stored_funder.amount),
+                Err(_) => (false, 0),
+            };
+            let address = if anonymous {
+                None
+            } else {
+                Some(deps.api.addr_humanize(&funder_addr)?)
+            };
+            Ok(FulfillmentInfo {
+                idx,
+                address,
+                contribution: Uint128::from(contribution),
+                info: Binary(stored_info.info),
+                block: stored_info.block,
+            })
+        })
+        .collect();
+    let count = fulfillment_info_count(deps.storage)?;
+
+    Ok(to_binary(&QueryAnswer::FulfillmentInfo {
+        fulfillment_info: fulfillment_info?,
+        count,
+    })?)
+}
+
+fn to_survey(id: u32, survey: StoredSurvey, answers: Option<Vec<SurveyAnswer>>) -> Survey {
+    Survey {
+        id,
+        questions: survey.questions,
+        threshold: Uint128::from(survey.threshold),
+        closes_at: survey.closes_at,
+        response_count: survey.response_count,
+        answers,
+    }
+}
+
+fn query_surveys(
+    deps: Deps,
+    address: &Addr,
 ) -> StdResult<Binary> {
+    let sender_address_raw = deps.api.addr_canonicalize(address.as_str())?;
+    let is_creator = get_creator(deps.storage)? == sender_address_raw;
+    let amount = match get_funder(deps.storage, &sender_address_raw) {
+        Ok(stored_funder) => stored_funder.amount,
+        Err(_) => 0_u128,
+    };
+
+    let surveys: Vec<Survey> = get_surveys(deps.storage)?
+        .into_iter()
+        .enumerate()
+        .filter(|(_, survey)| is_creator || (amount > 0 && amount >= survey.threshold))
+        .map(|(id, survey)| {
+            let id = id as u32;
+            let answers = get_survey_response(deps.storage, id, &sender_address_raw)
+                .ok()
+                .map(|response| response.answers);
+            to_survey(id, survey, answers)
+        })
+        .collect();
+
+    to_binary(&QueryAnswer::Surveys { surveys })
+}
+
+fn query_survey_results(
+    deps: Deps,
+    address: &Addr,
+    survey_id: u32,
+    start_after: Option<u32>,
+    limit: Option<u32>,
+    order: SortOrder,
+) -> Result<Binary, ContractError> {
+    let sender_address_raw = deps.api.addr_canonicalize(address.as_str())?;
+    if sender_address_raw != get_creator(deps.storage)? {
+        return Err(ContractError::Unauthorized {});
+    }
+    let limit = check_page_size(limit.unwrap_or(DEFAULT_PAGE_SIZE))?;
+
+    let survey = get_survey(deps.storage, survey_id)?;
+    let choice_counts = survey.choice_counts.clone();
+    let responses: StdResult<Vec<SurveyResponse>> = get_survey_responses(deps.storage, survey_id, start_after, limit, order)?
+        .into_iter()
+        .map(|(funder_addr, response)| -> StdResult<SurveyResponse> {
+            let stored_funder = get_funder(deps.storage, &funder_addr)?;
+            let address = if stored_funder.anonymous {
+                None
+            } else {
+                Some(deps.api.addr_humanize(&funder_addr)?)
+            };
+            Ok(SurveyResponse {
+                idx: response.idx,
+                address,
+                answers: response.answers,
+                block: response.block,
+            })
+        })
+        .collect();
+
+    Ok(to_binary(&QueryAnswer::SurveyResults {
+        survey: to_survey(survey_id, survey, None),
+        choice_counts,
+        responses: responses?,
+    })?)
+}
+
+// validates a permit against the platform contract, which stores revoked permits
+fn validate_permit(
+    deps: Deps,
+    permit: &Permit,
+) -> StdResult<Addr> {
     let get_validate_permit = PlatformQueryMsg::ValidatePermit { permit: permit.clone() };
     let config = get_config(deps.storage)?;
     let validate_permit_response: ValidatePermitResponse = get_validate_permit.query(
@@ -1252,7 +2589,626 @@ fn query_status_with_permit(
         config.platform_hash.to_string(),
         deps.api.addr_humanize(&config.platform_contract)?.into_string(),
     )?;
-    let address = validate_permit_response.validate_permit.address;
+    Ok(validate_permit_response.validate_permit.address)
+}
+
+fn query_status_with_permit(
+    deps: Deps,
+    env: Env,
+    permit: &Permit,
+) -> StdResult<Binary> {
+    let address = validate_permit(deps, permit)?;
+    query_status_auth(deps, env, &address)
+}
+
+fn query_fulfillment_info_with_permit(
+    deps: Deps,
+    permit: &Permit,
+    start_after: Option<u32>,
+    limit: Option<u32>,
+    order: SortOrder,
+) -> Result<Binary, ContractError> {
+    let address = validate_permit(deps, permit)?;
+    query_fulfillment_info(deps, &address, start_after, limit, order)
+}
 
-    query_status_auth(deps, &address)
+fn query_surveys_with_permit(
+    deps: Deps,
+    permit: &Permit,
+) -> StdResult<Binary> {
+    let address = validate_permit(deps, permit)?;
+    query_surveys(deps, &address)
+}
+
+fn query_survey_results_with_permit(
+    deps: Deps,
+    permit: &Permit,
+    survey_id: u32,
+    start_after: Option<u32>,
+    limit: Option<u32>,
+    order: SortOrder,
+) -> Result<Binary, ContractError> {
+    let address = validate_permit(deps, permit)?;
+    query_survey_results(deps, &address, survey_id, start_after, limit, order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+    use cosmwasm_std::{from_slice, ContractResult, OwnedDeps, SystemError, SystemResult, WasmQuery};
+    use crate::membership::MembershipTier;
+    use crate::msg::{CategoriesInnerResponse, PausedInnerResponse};
+    use crate::reward::{AllocationBucket, RewardBonusCurve, VestingEvent};
+    use serde::Deserialize;
+
+    const TOKEN: &str = "snip20";
+    const CREATOR: &str = "creator";
+    const DEADLINE: u64 = 20_000;
+    const GOAL: u128 = 1_000;
+
+    // dependencies with a platform that is not paused and has no categories
+    fn deps() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::Smart { msg, .. } => {
+                let answer = match from_slice(msg) {
+                    Ok(PlatformQueryMsg::Paused {}) => to_binary(&PausedResponse {
+                        paused: PausedInnerResponse { paused: false },
+                    }),
+                    Ok(PlatformQueryMsg::Categories {}) => to_binary(&CategoriesResponse {
+                        categories: CategoriesInnerResponse { categories: vec![] },
+                    }),
+                    _ => Err(StdError::generic_err("unexpected platform query")),
+                };
+                SystemResult::Ok(ContractResult::from(answer))
+            }
+            _ => SystemResult::Err(SystemError::UnsupportedRequest { kind: String::from("wasm") }),
+        });
+        deps
+    }
+
+    fn env_at(height: u64) -> Env {
+        let mut env = mock_env();
+        env.block.height = height;
+        env
+    }
+
+    fn canonical(deps: Deps, addr: &str) -> CanonicalAddr {
+        deps.api.addr_canonicalize(addr).unwrap()
+    }
+
+    // reward token with only linear vesting, 1000 tokens to contributors over the 1000 blocks
+    // after the deadline
+    fn linear_reward() -> Snip24RewardInit {
+        let vesting = LinearVesting {
+            amount: Uint128::from(1_000_u128),
+            start: DEADLINE,
+            cliff: DEADLINE,
+            end: DEADLINE + 1_000,
+        };
+        Snip24RewardInit {
+            reward_snip24_code_id: 1,
+            reward_snip24_code_hash: String::from("reward_hash"),
+            name: String::from("Reward"),
+            admin: None,
+            symbol: String::from("RWD"),
+            decimals: 6,
+            public_total_supply: true,
+            enable_deposit: false,
+            enable_redeem: false,
+            enable_mint: false,
+            enable_burn: false,
+            contributor_vesting_schedule: vec![],
+            minimum_contribution: None,
+            maximum_contribution: None,
+            contribution_weight: 1,
+            bonus_curve: None,
+            contributor_linear_vesting: Some(vesting.clone()),
+            creator_vesting_schedule: vec![],
+            creator_linear_vesting: Some(vesting),
+            creator_addresses: None,
+            allocation_buckets: None,
+        }
+    }
+
+    fn init_msg(reward: Option<Snip24RewardInit>) -> InstantiateMsg {
+        InstantiateMsg {
+            creator: Addr::unchecked(CREATOR),
+            title: String::from("Project"),
+            subtitle: None,
+            description: String::from("A project"),
+            cover_img: String::from(""),
+            pledged_message: None,
+            funded_message: None,
+            reward_messages: vec![],
+            goal: Uint128::from(GOAL),
+            deadline: DEADLINE,
+            deadman: 1_000,
+            categories: vec![],
+            entropy: String::from("entropy"),
+            source_contract: Addr::unchecked("platform"),
+            source_hash: String::from("platform_hash"),
+            snip20_contract: Addr::unchecked(TOKEN),
+            snip20_hash: String::from("snip20_hash"),
+            minimum_pledge: Uint128::from(1_u128),
+            maximum_pledge: Uint128::from(10_000_u128),
+            bond: None,
+            text_limits: None,
+            snip24_reward_init: reward,
+            membership: None,
+            padding: None,
+        }
+    }
+
+    fn instantiate_project(deps: DepsMut, reward: Option<Snip24RewardInit>) {
+        instantiate(deps, mock_env(), mock_info(CREATOR, &[]), init_msg(reward)).unwrap();
+    }
+
+    // snip-20 send of `amount` to the project
+    fn receive(
+        deps: DepsMut,
+        height: u64,
+        from: &str,
+        amount: u128,
+        msg: ExecuteReceiveMsg,
+    ) -> Result<Response, ContractError> {
+        execute(deps, env_at(height), mock_info(TOKEN, &[]), ExecuteMsg::Receive {
+            sender: Addr::unchecked(from),
+            from: Addr::unchecked(from),
+            amount: Uint128::from(amount),
+            msg: Some(to_binary(&msg).unwrap()),
+        })
+    }
+
+    fn pledge(deps: DepsMut, height: u64, from: &str, amount: u128) -> Result<Response, ContractError> {
+        receive(deps, height, from, amount, ExecuteReceiveMsg::ReceiveContribution { anon: false })
+    }
+
+    fn transfer(deps: DepsMut, height: u64, from: &str, to: &str, amount: u128) -> Result<Response, ContractError> {
+        execute(deps, env_at(height), mock_info(from, &[]), ExecuteMsg::TransferPledge {
+            recipient: Addr::unchecked(to),
+            amount: Uint128::from(amount),
+            padding: None,
+        })
+    }
+
+    fn claim_vested(deps: DepsMut, height: u64, sender: &str) -> Result<Response, ContractError> {
+        execute(deps, env_at(height), mock_info(sender, &[]), ExecuteMsg::ClaimVested { padding: None })
+    }
+
+    // the part of a snip-20 transfer message read by the tests
+    #[derive(Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    enum TransferMsg {
+        Transfer { amount: Uint128 },
+    }
+
+    // amount of the reward token transferred by a claim
+    fn transferred(response: &Response) -> u128 {
+        match &response.messages[0].msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) => match from_binary(msg).unwrap() {
+                TransferMsg::Transfer { amount } => amount.u128(),
+            },
+            msg => panic!("unexpected message {:?}", msg),
+        }
+    }
+
+    // funds the project with the given pledges and pays it out after the deadline, with the
+    // reward token already instantiated
+    fn paid_out_project(pledges: &[(&str, u128)]) -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        paid_out_project_with(linear_reward(), pledges)
+    }
+
+    fn paid_out_project_with(
+        reward: Snip24RewardInit,
+        pledges: &[(&str, u128)],
+    ) -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = deps();
+        instantiate_project(deps.as_mut(), Some(reward));
+        for (from, amount) in pledges {
+            pledge(deps.as_mut(), DEADLINE - 1, from, *amount).unwrap();
+        }
+        execute(deps.as_mut(), env_at(DEADLINE + 1), mock_info(CREATOR, &[]), ExecuteMsg::PayOut { padding: None })
+            .unwrap();
+        let reward_address = canonical(deps.as_ref(), "reward");
+        set_snip24_reward_address(deps.as_mut().storage, Some(reward_address)).unwrap();
+        deps
+    }
+
+    #[test]
+    fn claimed_vesting_moves_with_a_whole_pledge() {
+        let mut deps = paid_out_project(&[("alice", 600), ("bob", 400)]);
+
+        // half way through, alice's 600 of 1000 tokens have vested by half
+        claim_vested(deps.as_mut(), DEADLINE + 500, "alice").unwrap();
+        assert_eq!(get_vested_claimed(&deps.storage, &canonical(deps.as_ref(), "alice")), 300);
+
+        transfer(deps.as_mut(), DEADLINE + 500, "alice", "carol", 600).unwrap();
+        assert_eq!(get_vested_claimed(&deps.storage, &canonical(deps.as_ref(), "alice")), 0);
+        assert_eq!(get_vested_claimed(&deps.storage, &canonical(deps.as_ref(), "carol")), 300);
+
+        // nothing more has vested for the pledge, whoever holds it
+        assert!(matches!(claim_vested(deps.as_mut(), DEADLINE + 500, "carol"), Err(ContractError::AlreadyClaimed {})));
+        assert!(matches!(claim_vested(deps.as_mut(), DEADLINE + 500, "alice"), Err(ContractError::NoReward {})));
+
+        claim_vested(deps.as_mut(), DEADLINE + 1_000, "carol").unwrap();
+        assert_eq!(get_vested_claimed(&deps.storage, &canonical(deps.as_ref(), "carol")), 600);
+    }
+
+    #[test]
+    fn claimed_vesting_moves_pro_rata_with_part_of_a_pledge() {
+        let mut deps = paid_out_project(&[("alice", 600), ("bob", 400)]);
+
+        claim_vested(deps.as_mut(), DEADLINE + 500, "alice").unwrap();
+        transfer(deps.as_mut(), DEADLINE + 500, "alice", "carol", 200).unwrap();
+        assert_eq!(get_vested_claimed(&deps.storage, &canonical(deps.as_ref(), "alice")), 200);
+        assert_eq!(get_vested_claimed(&deps.storage, &canonical(deps.as_ref(), "carol")), 100);
+        assert!(matches!(claim_vested(deps.as_mut(), DEADLINE + 500, "alice"), Err(ContractError::AlreadyClaimed {})));
+        assert!(matches!(claim_vested(deps.as_mut(), DEADLINE + 500, "carol"), Err(ContractError::AlreadyClaimed {})));
+
+        // the two halves together never claim more than the 600 tokens of the original pledge
+        claim_vested(deps.as_mut(), DEADLINE + 1_000, "alice").unwrap();
+        claim_vested(deps.as_mut(), DEADLINE + 1_000, "carol").unwrap();
+        assert_eq!(get_vested_claimed(&deps.storage, &canonical(deps.as_ref(), "alice")), 400);
+        assert_eq!(get_vested_claimed(&deps.storage, &canonical(deps.as_ref(), "carol")), 200);
+    }
+
+    #[test]
+    fn illegal_status_transition_is_a_typed_error() {
+        let mut deps = paid_out_project(&[("alice", 1_000)]);
+        assert_eq!(
+            set_status(deps.as_mut().storage, ProjectStatus::Fundraising),
+            Err(ContractError::InvalidTransition { from: ProjectStatus::PaidOut, to: ProjectStatus::Fundraising }),
+        );
+    }
+
+    #[test]
+    fn bucket_remainder_goes_to_the_last_recipient() {
+        let reward = Snip24RewardInit {
+            allocation_buckets: Some(vec![AllocationBucket {
+                name: String::from("team"),
+                recipients: vec![Addr::unchecked("dave"), Addr::unchecked("erin"), Addr::unchecked("frank")],
+                vesting_schedule: vec![VestingEvent { block: DEADLINE + 10, amount: Uint128::from(100_u128) }],
+            }]),
+            ..linear_reward()
+        };
+        let mut deps = paid_out_project_with(reward, &[("alice", 1_000)]);
+
+        let claimed: Vec<u128> = ["dave", "erin", "frank"]
+            .iter()
+            .map(|recipient| {
+                let response = execute(deps.as_mut(), env_at(DEADLINE + 10), mock_info(recipient, &[]),
+                    ExecuteMsg::ClaimBucketReward { bucket: 0, idx: 0, padding: None }).unwrap();
+                transferred(&response)
+            })
+            .collect();
+        assert_eq!(claimed, vec![33, 33, 34]);
+    }
+
+    #[test]
+    fn reward_without_buckets_has_no_allocation_buckets() {
+        let mut deps = deps();
+        instantiate_project(deps.as_mut(), Some(linear_reward()));
+        let reward = get_snip24_reward(&deps.storage, &deps.api).unwrap().unwrap();
+        assert_eq!(reward.allocation_buckets, None);
+    }
+
+    #[test]
+    fn pledges_and_matches_close_at_the_deadline() {
+        let mut deps = deps();
+        instantiate_project(deps.as_mut(), None);
+
+        // a funded project keeps taking pledges and matches up to the deadline
+        pledge(deps.as_mut(), DEADLINE - 1, "alice", GOAL).unwrap();
+        assert_eq!(get_status(&deps.storage).unwrap(), ProjectStatus::Succeeded);
+        pledge(deps.as_mut(), DEADLINE, "bob", 100).unwrap();
+        receive(deps.as_mut(), DEADLINE, "sponsor", 100, ExecuteReceiveMsg::DepositMatch { ratio: 500 }).unwrap();
+
+        assert!(matches!(pledge(deps.as_mut(), DEADLINE + 1, "carol", 100), Err(ContractError::NotAcceptingPledges {})));
+        assert!(matches!(
+            receive(deps.as_mut(), DEADLINE + 1, "other", 100, ExecuteReceiveMsg::DepositMatch { ratio: 500 }),
+            Err(ContractError::NotAcceptingPledges {}),
+        ));
+    }
+
+    #[test]
+    fn status_expires_without_an_execute() {
+        let mut deps = deps();
+        instantiate_project(deps.as_mut(), None);
+        pledge(deps.as_mut(), DEADLINE - 1, "alice", GOAL - 1).unwrap();
+
+        // the stored status is stale until something executes, queries see the deadline anyway
+        assert_eq!(get_status(&deps.storage).unwrap(), ProjectStatus::Fundraising);
+        assert_eq!(get_effective_status(&deps.storage, &env_at(DEADLINE).block).unwrap(), ProjectStatus::Fundraising);
+        assert_eq!(get_effective_status(&deps.storage, &env_at(DEADLINE + 1).block).unwrap(), ProjectStatus::Failed);
+
+        assert!(matches!(pledge(deps.as_mut(), DEADLINE + 1, "bob", 100), Err(ContractError::NotAcceptingPledges {})));
+        assert!(matches!(
+            receive(deps.as_mut(), DEADLINE + 1, "sponsor", 100, ExecuteReceiveMsg::DepositMatch { ratio: 500 }),
+            Err(ContractError::NotAcceptingPledges {}),
+        ));
+    }
+
+    const BOND: u128 = 100;
+
+    // project with a deposited creator bond, funded by the given pledges before the deadline
+    fn bonded_project(pledges: &[(&str, u128)]) -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+        let mut deps = deps();
+        let msg = InstantiateMsg { bond: Some(Uint128::from(BOND)), ..init_msg(None) };
+        instantiate(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), msg).unwrap();
+
+        assert!(matches!(pledge(deps.as_mut(), DEADLINE - 2, "alice", 100), Err(ContractError::AwaitingBond {})));
+        assert!(matches!(
+            receive(deps.as_mut(), DEADLINE - 2, CREATOR, BOND - 1, ExecuteReceiveMsg::DepositBond {}),
+            Err(ContractError::BondAmountMismatch { amount: BOND }),
+        ));
+        receive(deps.as_mut(), DEADLINE - 2, CREATOR, BOND, ExecuteReceiveMsg::DepositBond {}).unwrap();
+
+        for (from, amount) in pledges {
+            pledge(deps.as_mut(), DEADLINE - 1, from, *amount).unwrap();
+        }
+        deps
+    }
+
+    fn claim_bond_share(deps: DepsMut, height: u64, sender: &str) -> Result<Response, ContractError> {
+        execute(deps, env_at(height), mock_info(sender, &[]), ExecuteMsg::ClaimBondShare { padding: None })
+    }
+
+    fn claim_bond(deps: DepsMut, height: u64) -> Result<Response, ContractError> {
+        execute(deps, env_at(height), mock_info(CREATOR, &[]), ExecuteMsg::ClaimBond { padding: None })
+    }
+
+    #[test]
+    fn bond_is_shared_by_backers_when_the_deadman_expires() {
+        let mut deps = bonded_project(&[("alice", 600), ("bob", 400)]);
+        let expired = DEADLINE + 1_001;
+
+        assert_eq!(transferred(&claim_bond_share(deps.as_mut(), expired, "alice").unwrap()), 60);
+        assert!(matches!(claim_bond_share(deps.as_mut(), expired, "alice"), Err(ContractError::NoBondShare {})));
+        assert!(matches!(claim_bond(deps.as_mut(), expired), Err(ContractError::BondForfeited {})));
+
+        // a refund includes the backer's unclaimed share
+        let refund = execute(deps.as_mut(), env_at(expired), mock_info("bob", &[]), ExecuteMsg::Refund { padding: None })
+            .unwrap();
+        assert_eq!(transferred(&refund), 440);
+    }
+
+    #[test]
+    fn bond_is_shared_by_backers_when_a_majority_votes_default() {
+        let mut deps = bonded_project(&[("alice", 600), ("bob", 400)]);
+        execute(deps.as_mut(), env_at(DEADLINE + 1), mock_info(CREATOR, &[]), ExecuteMsg::PayOut { padding: None })
+            .unwrap();
+
+        let vote = |deps: DepsMut, sender: &str| execute(deps, env_at(DEADLINE + 2), mock_info(sender, &[]),
+            ExecuteMsg::VoteDefault { default: true, padding: None });
+        vote(deps.as_mut(), "bob").unwrap();
+        assert_eq!(get_status(&deps.storage).unwrap(), ProjectStatus::PaidOut);
+        vote(deps.as_mut(), "alice").unwrap();
+        assert_eq!(get_status(&deps.storage).unwrap(), ProjectStatus::Defaulted);
+
+        assert!(matches!(claim_bond(deps.as_mut(), DEADLINE + 1_000), Err(ContractError::BondForfeited {})));
+        assert_eq!(transferred(&claim_bond_share(deps.as_mut(), DEADLINE + 2, "bob").unwrap()), 40);
+    }
+
+    #[test]
+    fn bond_returns_to_the_creator_after_the_release_block() {
+        let mut deps = bonded_project(&[("alice", GOAL)]);
+        execute(deps.as_mut(), env_at(DEADLINE + 1), mock_info(CREATOR, &[]), ExecuteMsg::PayOut { padding: None })
+            .unwrap();
+
+        assert!(matches!(
+            claim_bond(deps.as_mut(), DEADLINE + 999),
+            Err(ContractError::BondLocked { release_block }) if release_block == DEADLINE + 1_000,
+        ));
+        assert_eq!(transferred(&claim_bond(deps.as_mut(), DEADLINE + 1_000).unwrap()), BOND);
+        assert!(matches!(claim_bond(deps.as_mut(), DEADLINE + 1_000), Err(ContractError::BondAlreadyReturned {})));
+        assert!(matches!(claim_bond_share(deps.as_mut(), DEADLINE + 1_000, "alice"), Err(ContractError::NoBondShare {})));
+    }
+
+    #[test]
+    fn transferred_pledges_stay_within_the_pledge_bounds() {
+        let mut deps = deps();
+        let msg = InstantiateMsg { minimum_pledge: Uint128::from(100_u128), ..init_msg(None) };
+        instantiate(deps.as_mut(), mock_env(), mock_info(CREATOR, &[]), msg).unwrap();
+        pledge(deps.as_mut(), DEADLINE - 1, "alice", 600).unwrap();
+        pledge(deps.as_mut(), DEADLINE - 1, "bob", 9_950).unwrap();
+
+        let out_of_bounds = |result| matches!(result, Err(ContractError::PledgeOutOfBounds { min: 100, max: 10_000 }));
+        // the part left behind and the part received must each be a valid pledge
+        assert!(out_of_bounds(transfer(deps.as_mut(), DEADLINE, "alice", "carol", 550)));
+        assert!(out_of_bounds(transfer(deps.as_mut(), DEADLINE, "alice", "carol", 50)));
+        assert!(out_of_bounds(transfer(deps.as_mut(), DEADLINE, "alice", "bob", 100)));
+        assert!(matches!(transfer(deps.as_mut(), DEADLINE, "alice", "carol", 601), Err(ContractError::InvalidTransferAmount {})));
+        assert!(matches!(transfer(deps.as_mut(), DEADLINE, "alice", CREATOR, 600), Err(ContractError::InvalidRecipient {})));
+
+        let response = transfer(deps.as_mut(), DEADLINE, "alice", "carol", 200).unwrap();
+        assert!(response.attributes.is_empty());
+        assert_eq!(get_funder(&deps.storage, &canonical(deps.as_ref(), "alice")).unwrap().amount, 400);
+        assert_eq!(get_funder(&deps.storage, &canonical(deps.as_ref(), "carol")).unwrap().amount, 200);
+        assert_eq!(get_backer_total(&deps.storage).unwrap(), 10_550);
+    }
+
+    #[test]
+    fn transferred_pledges_keep_their_reward_status() {
+        let reward = Snip24RewardInit {
+            contributor_vesting_schedule: vec![VestingEvent { block: DEADLINE + 10, amount: Uint128::from(100_u128) }],
+            ..linear_reward()
+        };
+        let mut deps = paid_out_project_with(reward, &[("alice", 600), ("bob", 400)]);
+        let claim_reward = |deps: DepsMut, sender: &str| execute(deps, env_at(DEADLINE + 10), mock_info(sender, &[]),
+            ExecuteMsg::ClaimReward { idx: 0, padding: None });
+
+        assert_eq!(transferred(&claim_reward(deps.as_mut(), "alice").unwrap()), 60);
+        // bob has not claimed yet, so alice's claimed pledge cannot be merged into his
+        assert!(matches!(transfer(deps.as_mut(), DEADLINE + 10, "alice", "bob", 300), Err(ContractError::RewardStatusMismatch {})));
+
+        transfer(deps.as_mut(), DEADLINE + 10, "alice", "carol", 300).unwrap();
+        assert!(matches!(claim_reward(deps.as_mut(), "carol"), Err(ContractError::AlreadyClaimed {})));
+        assert_eq!(transferred(&claim_reward(deps.as_mut(), "bob").unwrap()), 40);
+    }
+
+    fn deposit_match(deps: DepsMut, from: &str, cap: u128, ratio: u16) -> Result<Response, ContractError> {
+        receive(deps, DEADLINE - 2, from, cap, ExecuteReceiveMsg::DepositMatch { ratio })
+    }
+
+    fn refund_match(deps: DepsMut, height: u64, sender: &str) -> Result<Response, ContractError> {
+        execute(deps, env_at(height), mock_info(sender, &[]), ExecuteMsg::RefundMatch { padding: None })
+    }
+
+    #[test]
+    fn match_counts_toward_the_goal_up_to_its_cap() {
+        let mut deps = deps();
+        instantiate_project(deps.as_mut(), None);
+        deposit_match(deps.as_mut(), "sponsor", 300, 500).unwrap();
+        assert_eq!(get_total(&deps.storage).unwrap(), 0);
+
+        pledge(deps.as_mut(), DEADLINE - 1, "alice", 400).unwrap();
+        assert_eq!(get_total(&deps.storage).unwrap(), 600);
+        assert_eq!(get_status(&deps.storage).unwrap(), ProjectStatus::Fundraising);
+
+        // half of bob's pledge would take the match past its cap
+        pledge(deps.as_mut(), DEADLINE - 1, "bob", 300).unwrap();
+        assert_eq!(get_total(&deps.storage).unwrap(), GOAL);
+        assert_eq!(get_status(&deps.storage).unwrap(), ProjectStatus::Succeeded);
+
+        // only the unused part of the cap comes back once the project pays out
+        execute(deps.as_mut(), env_at(DEADLINE + 1), mock_info(CREATOR, &[]), ExecuteMsg::PayOut { padding: None })
+            .unwrap();
+        assert!(matches!(refund_match(deps.as_mut(), DEADLINE + 1, "sponsor"), Err(ContractError::NothingToRefund {})));
+    }
+
+    #[test]
+    fn match_is_refunded_when_the_project_fails() {
+        let mut deps = deps();
+        instantiate_project(deps.as_mut(), None);
+        deposit_match(deps.as_mut(), "sponsor", 300, 500).unwrap();
+        pledge(deps.as_mut(), DEADLINE - 1, "alice", 400).unwrap();
+
+        assert!(matches!(refund_match(deps.as_mut(), DEADLINE, "sponsor"), Err(ContractError::MatchLocked {})));
+        assert_eq!(transferred(&refund_match(deps.as_mut(), DEADLINE + 1, "sponsor").unwrap()), 300);
+        assert_eq!(get_total(&deps.storage).unwrap(), 400);
+        assert!(matches!(refund_match(deps.as_mut(), DEADLINE + 1, "sponsor"), Err(ContractError::NothingToRefund {})));
+    }
+
+    #[test]
+    fn match_earns_rewards_only_when_the_creator_opts_in() {
+        let mut deps = deps();
+        instantiate_project(deps.as_mut(), Some(linear_reward()));
+        let set_match_rewards = |deps: DepsMut, rewarded: bool| execute(deps, env_at(DEADLINE - 2),
+            mock_info(CREATOR, &[]), ExecuteMsg::SetMatchRewards { rewarded, padding: None });
+
+        deposit_match(deps.as_mut(), "plain", 250, 500).unwrap();
+        set_match_rewards(deps.as_mut(), true).unwrap();
+        deposit_match(deps.as_mut(), "sponsor", 500, 1_000).unwrap();
+        set_match_rewards(deps.as_mut(), false).unwrap();
+
+        pledge(deps.as_mut(), DEADLINE - 1, "alice", 500).unwrap();
+        execute(deps.as_mut(), env_at(DEADLINE + 1), mock_info(CREATOR, &[]), ExecuteMsg::PayOut { padding: None })
+            .unwrap();
+        let reward_address = canonical(deps.as_ref(), "reward");
+        set_snip24_reward_address(deps.as_mut().storage, Some(reward_address)).unwrap();
+
+        // the rewarded match shares the contributor allocation with alice's pledge
+        assert_eq!(transferred(&claim_vested(deps.as_mut(), DEADLINE + 1_000, "alice").unwrap()), 500);
+        assert_eq!(transferred(&claim_vested(deps.as_mut(), DEADLINE + 1_000, "sponsor").unwrap()), 500);
+        assert!(matches!(claim_vested(deps.as_mut(), DEADLINE + 1_000, "plain"), Err(ContractError::NoReward {})));
+    }
+
+    const PERIOD: u64 = 100;
+    const PRICE: u128 = 10;
+
+    // membership project with a single tier, its first period starts at the mock env height
+    fn membership_project() -> (OwnedDeps<MockStorage, MockApi, MockQuerier>, u64) {
+        let mut deps = deps();
+        let msg = InstantiateMsg {
+            membership: Some(MembershipInit {
+                period: PERIOD,
+                tiers: vec![MembershipTier { name: String::from("supporter"), price: Uint128::from(PRICE) }],
+            }),
+            ..init_msg(None)
+        };
+        let env = mock_env();
+        instantiate(deps.as_mut(), env.clone(), mock_info(CREATOR, &[]), msg).unwrap();
+        (deps, env.block.height)
+    }
+
+    fn subscribe(deps: DepsMut, height: u64, from: &str, amount: u128) -> Result<Response, ContractError> {
+        receive(deps, height, from, amount, ExecuteReceiveMsg::Subscribe { tier: 0 })
+    }
+
+    fn withdraw_membership(deps: DepsMut, height: u64) -> Result<Response, ContractError> {
+        execute(deps, env_at(height), mock_info(CREATOR, &[]), ExecuteMsg::WithdrawMembership { padding: None })
+    }
+
+    #[test]
+    fn membership_takes_whole_periods_only() {
+        let (mut deps, start) = membership_project();
+
+        assert!(matches!(pledge(deps.as_mut(), start, "alice", 30), Err(ContractError::MembershipProject {})));
+        assert!(matches!(
+            subscribe(deps.as_mut(), start, "alice", 15),
+            Err(ContractError::InvalidMembershipPayment { price: PRICE }),
+        ));
+        assert!(matches!(
+            receive(deps.as_mut(), start, "alice", 30, ExecuteReceiveMsg::Subscribe { tier: 1 }),
+            Err(ContractError::InvalidTier { tier: 1 }),
+        ));
+
+        subscribe(deps.as_mut(), start, "alice", 30).unwrap();
+        // paying again extends the membership
+        subscribe(deps.as_mut(), start + PERIOD, "alice", 20).unwrap();
+        let member = get_member(&deps.storage, &canonical(deps.as_ref(), "alice")).unwrap();
+        assert_eq!((member.start, member.end), (0, 5));
+    }
+
+    #[test]
+    fn membership_revenue_is_withdrawn_as_periods_elapse() {
+        let (mut deps, start) = membership_project();
+        subscribe(deps.as_mut(), start, "alice", 3 * PRICE).unwrap();
+        subscribe(deps.as_mut(), start, "bob", 3 * PRICE).unwrap();
+
+        // nothing is owed for the current period until it has elapsed
+        assert!(matches!(withdraw_membership(deps.as_mut(), start + PERIOD - 1), Err(ContractError::NothingToWithdraw {})));
+        assert_eq!(transferred(&withdraw_membership(deps.as_mut(), start + PERIOD).unwrap()), 2 * PRICE);
+
+        // alice keeps the current period and gets the one after it back
+        let cancel = execute(deps.as_mut(), env_at(start + PERIOD), mock_info("alice", &[]),
+            ExecuteMsg::CancelMembership { padding: None }).unwrap();
+        assert_eq!(transferred(&cancel), PRICE);
+
+        assert_eq!(transferred(&withdraw_membership(deps.as_mut(), start + 3 * PERIOD).unwrap()), 3 * PRICE);
+        assert!(matches!(withdraw_membership(deps.as_mut(), start + 3 * PERIOD), Err(ContractError::NothingToWithdraw {})));
+    }
+
+    #[test]
+    fn early_pledges_earn_a_larger_reward_share() {
+        let mut deps = deps();
+        let reward = Snip24RewardInit {
+            bonus_curve: Some(RewardBonusCurve { multiplier: 1_500, full_until: DEADLINE - 1_000 }),
+            ..linear_reward()
+        };
+        instantiate_project(deps.as_mut(), Some(reward));
+        let weighted = |deps: Deps, addr: &str| get_funder(deps.storage, &canonical(deps, addr)).unwrap().weighted;
+
+        // half way between the end of the full bonus and the deadline the bonus has slid by half
+        pledge(deps.as_mut(), DEADLINE - 500, "carol", 200).unwrap();
+        assert_eq!(weighted(deps.as_ref(), "carol"), 250);
+        execute(deps.as_mut(), env_at(DEADLINE - 500), mock_info("carol", &[]), ExecuteMsg::Refund { padding: None })
+            .unwrap();
+        assert_eq!(get_rewarded_total(&deps.storage).unwrap(), 0);
+
+        pledge(deps.as_mut(), DEADLINE - 1_000, "alice", 500).unwrap();
+        pledge(deps.as_mut(), DEADLINE, "bob", 500).unwrap();
+        assert_eq!(weighted(deps.as_ref(), "alice"), 750);
+        assert_eq!(weighted(deps.as_ref(), "bob"), 500);
+        assert_eq!(get_rewarded_total(&deps.storage).unwrap(), 1_250);
+
+        execute(deps.as_mut(), env_at(DEADLINE + 1), mock_info(CREATOR, &[]), ExecuteMsg::PayOut { padding: None })
+            .unwrap();
+        let reward_address = canonical(deps.as_ref(), "reward");
+        set_snip24_reward_address(deps.as_mut().storage, Some(reward_address)).unwrap();
+        assert_eq!(transferred(&claim_vested(deps.as_mut(), DEADLINE + 1_000, "alice").unwrap()), 600);
+        assert_eq!(transferred(&claim_vested(deps.as_mut(), DEADLINE + 1_000, "bob").unwrap()), 400);
+    }
 }
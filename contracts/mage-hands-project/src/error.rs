@@ -0,0 +1,233 @@
+use cosmwasm_std::StdError;
+use crate::state::ProjectStatus;
+use thiserror::Error;
+
+// Every failure aborts the transaction (reverting any SNIP-20 send that triggered it).
+// Each variant's message starts with a stable code in brackets that clients can match on.
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("[std] {0}")]
+    Std(#[from] StdError),
+
+    #[error("[unauthorized] Unauthorized")]
+    Unauthorized {},
+
+    #[error("[invalid_token] Sender is incorrect SNIP-20 contract")]
+    InvalidToken {},
+
+    // instantiate
+    #[error("[deadline_in_past] Cannot create project with deadline in the past")]
+    DeadlineInPast {},
+
+    #[error("[invalid_goal] Goal must be greater than 0")]
+    InvalidGoal {},
+
+    #[error("[invalid_snip24_reward] {reason}")]
+    InvalidSnip24Reward { reason: String },
+
+    #[error("[text_too_long] {field} must be at most {max} bytes")]
+    TextTooLong { field: String, max: u32 },
+
+    #[error("[too_many_categories] Project can have at most {max} categories")]
+    TooManyCategories { max: u32 },
+
+    #[error("[unknown_category] Category {id} does not exist")]
+    UnknownCategory { id: u16 },
+
+    #[error("[retired_category] Category {id} has been retired")]
+    RetiredCategory { id: u16 },
+
+    // project status
+    #[error("[not_fundraising] Project is no longer fundraising")]
+    NotFundraising {},
+
+    #[error("[not_accepting_pledges] Project is not accepting contributions")]
+    NotAcceptingPledges {},
+
+    #[error("[not_cancellable] Cannot cancel a project that has been paid out or expired")]
+    NotCancellable {},
+
+    #[error("[not_refundable] Cannot receive refund after project successfully funded")]
+    NotRefundable {},
+
+    #[error("[already_paid_out] Already paid out")]
+    AlreadyPaidOut {},
+
+    #[error("[deadman_expired] Project was funded, but the deadman time has elapsed and funding has expired")]
+    DeadmanExpired {},
+
+    #[error("[not_payable] Cannot receive pay out unless project successfully funded and deadline past")]
+    NotPayable {},
+
+    #[error("[not_paid_out] Project has not been paid out")]
+    NotPaidOut {},
+
+    #[error("[invalid_transition] Invalid project status transition from {from:?} to {to:?}")]
+    InvalidTransition { from: ProjectStatus, to: ProjectStatus },
+
+    // pledges
+    #[error("[zero_amount] Amount must be greater than 0")]
+    ZeroAmount {},
+
+    #[error("[own_project] Cannot fund your own project")]
+    OwnProject {},
+
+    #[error("[pledge_out_of_bounds] Pledge is not within the bounds of ({min},{max})")]
+    PledgeOutOfBounds { min: u128, max: u128 },
+
+    #[error("[no_pledge] Only backers with a pledge can do this")]
+    NoPledge {},
+
+    #[error("[nothing_to_refund] No funds to refund")]
+    NothingToRefund {},
+
+    #[error("[nothing_to_update] Nothing was updated")]
+    NothingToUpdate {},
+
+    #[error("[not_transferable] Pledges cannot be transferred for this project")]
+    NotTransferable {},
+
+    #[error("[invalid_transfer_amount] Transfer amount exceeds your pledge")]
+    InvalidTransferAmount {},
+
+    #[error("[invalid_recipient] Cannot transfer a pledge to yourself or the project creator")]
+    InvalidRecipient {},
+
+    #[error("[reward_status_mismatch] Recipient holds a pledge with a different reward status")]
+    RewardStatusMismatch {},
+
+    // sponsor matches
+    #[error("[invalid_match_ratio] Match ratio must be greater than 0")]
+    InvalidMatchRatio {},
+
+    #[error("[match_exists] Sponsor already has a match on this project")]
+    MatchExists {},
+
+    #[error("[too_many_matches] Project can have at most {max} sponsor matches")]
+    TooManyMatches { max: u32 },
+
+    #[error("[no_match] No sponsor match for this address")]
+    NoMatch {},
+
+    #[error("[match_locked] Match cannot be refunded until the project has failed or the deadline has passed")]
+    MatchLocked {},
+
+    // memberships
+    #[error("[invalid_membership] {reason}")]
+    InvalidMembership { reason: String },
+
+    #[error("[membership_project] Membership projects only accept subscriptions")]
+    MembershipProject {},
+
+    #[error("[not_membership_project] Project does not have a membership")]
+    NotMembershipProject {},
+
+    #[error("[invalid_tier] No membership tier {tier}")]
+    InvalidTier { tier: u16 },
+
+    #[error("[invalid_membership_payment] Payment must be a multiple of the tier price {price}")]
+    InvalidMembershipPayment { price: u128 },
+
+    #[error("[too_many_periods] Cannot prepay more than {max} periods ahead")]
+    TooManyPeriods { max: u64 },
+
+    #[error("[tier_mismatch] Cancel the active membership before changing tier")]
+    TierMismatch {},
+
+    #[error("[no_membership] No active membership")]
+    NoMembership {},
+
+    #[error("[nothing_to_withdraw] No membership revenue to withdraw")]
+    NothingToWithdraw {},
+
+    // snip24 rewards
+    #[error("[no_reward] No snip24 reward for this project")]
+    NoReward {},
+
+    #[error("[no_reward_contract] No reward snip24 contract")]
+    NoRewardContract {},
+
+    #[error("[invalid_reward_idx] No reward at index {idx}")]
+    InvalidRewardIdx { idx: u32 },
+
+    #[error("[already_claimed] Already claimed reward")]
+    AlreadyClaimed {},
+
+    #[error("[not_vested] Vesting time has not been reached, vests at block {block}")]
+    NotVested { block: u64 },
+
+    #[error("[invalid_bucket] No allocation bucket {bucket}")]
+    InvalidBucket { bucket: u16 },
+
+    #[error("[not_bucket_recipient] Only recipients of the allocation bucket can claim from it")]
+    NotBucketRecipient {},
+
+    // creator bond
+    #[error("[no_bond] Project does not have a deposited bond")]
+    NoBond {},
+
+    #[error("[bond_not_required] Project does not require a bond")]
+    BondNotRequired {},
+
+    #[error("[awaiting_bond] Project is waiting for the creator to deposit the bond")]
+    AwaitingBond {},
+
+    #[error("[bond_already_deposited] Bond has already been deposited")]
+    BondAlreadyDeposited {},
+
+    #[error("[bond_amount_mismatch] Bond must be exactly {amount}")]
+    BondAmountMismatch { amount: u128 },
+
+    #[error("[bond_already_returned] Bond has already been returned")]
+    BondAlreadyReturned {},
+
+    #[error("[bond_forfeited] Bond has been forfeited")]
+    BondForfeited {},
+
+    #[error("[bond_locked] Bond cannot be claimed before block {release_block}")]
+    BondLocked { release_block: u64 },
+
+    #[error("[bond_released] Bond has been released to the creator")]
+    BondReleased {},
+
+    #[error("[no_bond_share] No bond share to claim")]
+    NoBondShare {},
+
+    // fulfillment info
+    #[error("[empty_fulfillment_key] Fulfillment public key cannot be empty")]
+    EmptyFulfillmentKey {},
+
+    #[error("[no_fulfillment_key] Project creator has not registered a fulfillment public key")]
+    NoFulfillmentKey {},
+
+    #[error("[invalid_fulfillment_info] Fulfillment info must be between 1 and {max} bytes")]
+    InvalidFulfillmentInfo { max: usize },
+
+    // surveys
+    #[error("[invalid_survey] {reason}")]
+    InvalidSurvey { reason: String },
+
+    #[error("[survey_not_found] Survey {id} does not exist")]
+    SurveyNotFound { id: u32 },
+
+    #[error("[below_survey_threshold] Only backers who meet the survey threshold can answer")]
+    BelowSurveyThreshold {},
+
+    #[error("[survey_closed] Survey is closed")]
+    SurveyClosed {},
+
+    #[error("[invalid_survey_answers] {reason}")]
+    InvalidSurveyAnswers { reason: String },
+
+    #[error("[platform_paused] Platform is paused")]
+    PlatformPaused {},
+
+    #[error("[invalid_page_size] Page size must be between 1 and {max}")]
+    InvalidPageSize { max: u32 },
+
+    #[error("[unknown_reply_id] Unknown reply id: {id}")]
+    UnknownReplyId { id: u64 },
+
+    #[error("[invalid_migration] Cannot migrate from version {from} to {to}")]
+    InvalidMigration { from: u32, to: u32 },
+}
@@ -1,8 +1,14 @@
 pub mod contract;
+pub mod error;
+pub mod migrate;
 pub mod msg;
 pub mod state;
 mod utils;
 mod viewing_key;
 mod reward;
+mod survey;
+mod sponsor;
+mod membership;
 mod random;
+#[allow(dead_code)]
 mod parse_reply;
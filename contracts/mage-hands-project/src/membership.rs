@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+use cosmwasm_std::{Uint128,};
+
+// maximum number of tiers in a membership
+pub const MAX_MEMBERSHIP_TIERS: usize = 10;
+// maximum number of periods, counting the current one, a member can have paid for
+pub const MAX_PREPAID_PERIODS: u64 = 24;
+// maximum number of periods paid out by a single withdrawal
+pub const MAX_WITHDRAW_PERIODS: u64 = 120;
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug,)]
+pub struct MembershipTier {
+    pub name: String,
+    // price per period in the snip20 token, reward messages with a threshold at or below it are
+    // shown to members of the tier
+    pub price: Uint128,
+}
+
+// recurring membership instead of a one-shot campaign
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug,)]
+pub struct MembershipInit {
+    // length of a period in blocks
+    pub period: u64,
+    pub tiers: Vec<MembershipTier>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug,)]
+pub struct StoredMembership {
+    pub period: u64,
+    // block the first period starts at
+    pub start: u64,
+    pub tiers: Vec<MembershipTier>,
+    // periods the creator has withdrawn the revenue of
+    pub withdrawn: u64,
+    // revenue of the last withdrawn period
+    pub rate: u128,
+    // period the project was cancelled or delisted in, later periods are not paid for
+    pub closed: Option<u64>,
+}
+
+impl StoredMembership {
+    pub fn period_at(&self, height: u64) -> u64 {
+        height.saturating_sub(self.start) / self.period
+    }
+
+    // first period that has not been paid for yet, stops at the period after closing
+    pub fn paid_until(&self, height: u64) -> u64 {
+        let current = self.period_at(height);
+        self.closed.map_or(current, |closed| current.min(closed + 1))
+    }
+
+    // first period a cancelling member is refunded for, the current period is always kept
+    pub fn refund_from(&self, height: u64) -> u64 {
+        let current = self.period_at(height);
+        self.closed.map_or(current, |closed| current.min(closed)) + 1
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug,)]
+pub struct StoredMember {
+    pub tier: u16,
+    // paid for periods from start up to but not including end
+    pub start: u64,
+    pub end: u64,
+}
+
+impl StoredMember {
+    pub fn is_active(&self, period: u64) -> bool {
+        self.start <= period && period < self.end
+    }
+}
+
+// Membership sent in query responses
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug,)]
+pub struct MembershipInfo {
+    pub period: u64,
+    pub start: u64,
+    pub tiers: Vec<MembershipTier>,
+    pub current_period: u64,
+    pub closed: Option<u64>,
+}
+
+// Member sent in query responses
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug,)]
+pub struct Member {
+    pub tier: u16,
+    pub start_period: u64,
+    pub end_period: u64,
+    pub active: bool,
+}
@@ -0,0 +1,220 @@
+use cosmwasm_std::{CanonicalAddr, DepsMut, Env, StdResult, Storage};
+use cosmwasm_storage::{prefixed, prefixed_read};
+use serde::{Deserialize, Serialize};
+use crate::error::ContractError;
+use crate::state::{
+    get_bin_data, set_bin_data, get_contract_version, set_contract_version, CONTRACT_VERSION,
+    set_funder, StoredFunder, funder_store, STATUS_KEY, ProjectStatus, get_deadline, get_deadman,
+    get_goal, get_total, set_bond, StoredBond, set_bond_release_block, calculate_bond_release_block,
+    get_snip24_reward, set_weighted_total, BOND_KEY, WEIGHTED_TOTAL_KEY, PREFIX_FUNDER_KEY,
+};
+
+// runs each upgrade step from the stored version up to CONTRACT_VERSION, returns the stored version
+pub fn migrate_storage(mut deps: DepsMut, env: &Env) -> Result<u32, ContractError> {
+    let from_version = get_contract_version(deps.storage);
+    if from_version > CONTRACT_VERSION {
+        return Err(ContractError::InvalidMigration { from: from_version, to: CONTRACT_VERSION });
+    }
+
+    if from_version < 2 {
+        migrate_v1_to_v2(deps.branch(), env)?;
+    }
+    if from_version < 3 {
+        migrate_v2_to_v3(deps.branch())?;
+    }
+    if from_version < 4 {
+        migrate_v3_to_v4(deps.storage)?;
+    }
+    if from_version < 5 {
+        migrate_v4_to_v5(deps.storage)?;
+    }
+
+    set_contract_version(deps.storage, CONTRACT_VERSION)?;
+    Ok(from_version)
+}
+
+//
+// Version 1
+//
+
+pub static V1_STATUS_KEY: &[u8] = b"stat";
+pub static V1_PAID_OUT_KEY: &[u8] = b"pout";
+
+pub const V1_FUNDRAISING: u8 = 1_u8;
+pub const V1_EXPIRED: u8 = 2_u8;
+pub const V1_SUCCESSFUL: u8 = 3_u8;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct V1StoredFunder {
+    pub idx: u32,
+    pub anonymous: bool,
+    pub amount: u128,
+    pub snip24_rewards_received: Vec<bool>,
+}
+
+//
+// Version 3
+//
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct V3StoredFunder {
+    pub idx: u32,
+    pub anonymous: bool,
+    pub amount: u128,
+    pub snip24_rewards_received: Vec<bool>,
+    pub bond_share_claimed: bool,
+}
+
+// addresses of every funder, in pledge order
+fn funder_addrs(storage: &dyn Storage) -> StdResult<Vec<CanonicalAddr>> {
+    funder_store().iter(storage)?.collect()
+}
+
+// v2: status state machine
+fn migrate_v1_to_v2(deps: DepsMut, env: &Env) -> StdResult<()> {
+    let deadline = get_deadline(deps.storage)?;
+
+    let status: u8 = get_bin_data(deps.storage, V1_STATUS_KEY)?;
+    let paid_out: bool = get_bin_data(deps.storage, V1_PAID_OUT_KEY).unwrap_or(false);
+    let status = if paid_out {
+        ProjectStatus::PaidOut
+    } else {
+        match status {
+            V1_SUCCESSFUL => ProjectStatus::Succeeded,
+            V1_EXPIRED => {
+                // v1 used EXPIRED for cancelled, failed and deadman expired projects
+                if env.block.height <= deadline {
+                    ProjectStatus::Cancelled
+                } else if get_total(deps.storage)? >= get_goal(deps.storage)? {
+                    ProjectStatus::DeadmanExpired
+                } else {
+                    ProjectStatus::Failed
+                }
+            }
+            _ => ProjectStatus::Fundraising,
+        }
+    };
+    set_bin_data(deps.storage, STATUS_KEY, &status)?;
+    deps.storage.remove(V1_STATUS_KEY);
+    deps.storage.remove(V1_PAID_OUT_KEY);
+
+    Ok(())
+}
+
+// v3: creator bond, which older projects do not have, so their backers have no share to claim
+fn migrate_v2_to_v3(deps: DepsMut) -> StdResult<()> {
+    // projects instantiated since bonds were added already store one
+    if deps.storage.get(BOND_KEY).is_some() {
+        return Ok(());
+    }
+
+    let deadline = get_deadline(deps.storage)?;
+    let deadman = get_deadman(deps.storage)?;
+    set_bond(deps.storage, &StoredBond {
+        amount: 0,
+        deposited: false,
+        forfeit_total: None,
+        returned: false,
+    })?;
+    let snip24_reward_init = get_snip24_reward(deps.storage, deps.api)?;
+    set_bond_release_block(
+        deps.storage,
+        calculate_bond_release_block(deadline, deadman, snip24_reward_init.as_ref()),
+    )?;
+
+    for funder_addr in funder_addrs(deps.storage)? {
+        let funder: V1StoredFunder = get_bin_data(deps.storage, funder_addr.as_slice())?;
+        set_bin_data(deps.storage, funder_addr.as_slice(), &V3StoredFunder {
+            idx: funder.idx,
+            anonymous: funder.anonymous,
+            amount: funder.amount,
+            snip24_rewards_received: funder.snip24_rewards_received,
+            bond_share_claimed: false,
+        })?;
+    }
+
+    Ok(())
+}
+
+// v4: funder records move from the root of storage to their own prefix
+fn migrate_v3_to_v4(storage: &mut dyn Storage) -> StdResult<()> {
+    for funder_addr in funder_addrs(storage)? {
+        // records of projects instantiated since the move are already under the prefix
+        let funder: V3StoredFunder = match get_bin_data(storage, funder_addr.as_slice()) {
+            Ok(funder) => funder,
+            Err(_) => continue,
+        };
+        let mut funder_store = prefixed(storage, PREFIX_FUNDER_KEY);
+        set_bin_data(&mut funder_store, funder_addr.as_slice(), &funder)?;
+        storage.remove(funder_addr.as_slice());
+    }
+
+    Ok(())
+}
+
+// v5: weighted pledge amounts for the early pledge bonus, older pledges had no bonus
+fn migrate_v4_to_v5(storage: &mut dyn Storage) -> StdResult<()> {
+    // projects instantiated since the bonus was added already keep weighted amounts
+    if storage.get(WEIGHTED_TOTAL_KEY).is_some() {
+        return Ok(());
+    }
+
+    for funder_addr in funder_addrs(storage)? {
+        let funder: V3StoredFunder = get_bin_data(&prefixed_read(storage, PREFIX_FUNDER_KEY), funder_addr.as_slice())?;
+        set_funder(storage, &funder_addr, &StoredFunder {
+            idx: funder.idx,
+            anonymous: funder.anonymous,
+            amount: funder.amount,
+            weighted: funder.amount,
+            snip24_rewards_received: funder.snip24_rewards_received,
+            bond_share_claimed: funder.bond_share_claimed,
+        })?;
+    }
+    let total = get_total(storage)?;
+    set_weighted_total(storage, total)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{mock_dependencies, mock_env};
+    use crate::state::{
+        get_bond, get_funder, get_status, get_weighted_total, set_deadline, set_deadman, set_goal,
+        set_snip24_reward, set_total,
+    };
+
+    #[test]
+    fn v1_project_migrates_through_every_step() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        let storage = &mut deps.storage;
+        set_deadline(storage, env.block.height - 1).unwrap();
+        set_deadman(storage, 1_000).unwrap();
+        set_goal(storage, 1_000).unwrap();
+        set_total(storage, 1_500).unwrap();
+        set_bin_data(storage, V1_STATUS_KEY, &V1_SUCCESSFUL).unwrap();
+        let funder_addr = CanonicalAddr::from(vec![7; 20]);
+        funder_store().push(storage, &funder_addr).unwrap();
+        set_bin_data(storage, funder_addr.as_slice(), &V1StoredFunder {
+            idx: 0,
+            anonymous: true,
+            amount: 1_500,
+            snip24_rewards_received: vec![false, true],
+        }).unwrap();
+        set_snip24_reward(storage, &deps.api, None).unwrap();
+
+        assert_eq!(migrate_storage(deps.as_mut(), &env).unwrap(), 1);
+
+        assert_eq!(get_contract_version(&deps.storage), CONTRACT_VERSION);
+        assert_eq!(get_status(&deps.storage).unwrap(), ProjectStatus::Succeeded);
+        assert_eq!(get_bond(&deps.storage).unwrap().amount, 0);
+        assert!(deps.storage.get(funder_addr.as_slice()).is_none());
+        let funder = get_funder(&deps.storage, &funder_addr).unwrap();
+        assert_eq!((funder.idx, funder.anonymous, funder.amount, funder.weighted), (0, true, 1_500, 1_500));
+        assert_eq!(funder.snip24_rewards_received, vec![false, true]);
+        assert!(!funder.bond_share_claimed);
+        assert_eq!(get_weighted_total(&deps.storage).unwrap(), 1_500);
+    }
+}
@@ -1,5 +1,9 @@
-use crate::reward::{Snip24RewardInit, RewardMessage, VestingRewardStatus, Snip24Info};
-use crate::state::StoredComment;
+use crate::reward::{Snip24RewardInit, RewardMessage, VestingRewardStatus, Snip24Info, LinearVestingStatus,
+    BucketRewardStatus};
+use crate::state::{ProjectStatus};
+use crate::sponsor::SponsorMatch;
+use crate::membership::{MembershipInit, MembershipInfo, Member};
+use crate::survey::{Survey, SurveyAnswer, SurveyQuestion, SurveyResponse};
 use crate::viewing_key::ViewingKey;
 use cosmwasm_std::{Addr, Uint128, Binary, };
 use secret_toolkit::permit::Permit;
@@ -36,26 +40,85 @@ pub struct InstantiateMsg {
     // minimum and maximum pledge amounts
     pub minimum_pledge: Uint128,
     pub maximum_pledge: Uint128,
+    // bond the creator must deposit in the contribution token before pledges are accepted
+    pub bond: Option<Uint128>,
+    // limits applied when the creator changes the project text, set by the platform
+    pub text_limits: Option<TextLimits>,
 
     // new snip24
     pub snip24_reward_init: Option<Snip24RewardInit>,
+    // recurring membership, goal and deadline are not used
+    pub membership: Option<MembershipInit>,
 
     pub padding: Option<String>,
 }
 
+// callbacks to the platform registry
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum PlatformExecuteMsg {
-    Register {
-        contract_addr: Addr,
-        contract_code_hash: String,
+    UpdateStatus {
+        status: ProjectStatus,
+    },
+    UpdateHidden {
+        hidden: bool,
+    },
+    // a backer's total pledge, so the platform can list the projects they back and match pledges
+    UpdateBacking {
+        backer: Addr,
+        amount: Uint128,
+        change: BackingChange,
     },
 }
 
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BackingChange {
+    Pledge,
+    Transfer,
+    Refund,
+}
+
 impl HandleCallback for PlatformExecuteMsg {
     const BLOCK_SIZE: usize = 256;
 }
 
+// maximum lengths in bytes for project text, and the maximum number of categories
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TextLimits {
+    pub max_title_len: u32,
+    pub max_subtitle_len: u32,
+    pub max_description_len: u32,
+    // pledged, funded and reward messages
+    pub max_message_len: u32,
+    pub max_cover_img_len: u32,
+    pub max_categories: u32,
+}
+
+impl Default for TextLimits {
+    fn default() -> Self {
+        TextLimits {
+            max_title_len: 128,
+            max_subtitle_len: 256,
+            max_description_len: 32768,
+            max_message_len: 4096,
+            max_cover_img_len: 65536,
+            max_categories: 5,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+// sent by the platform contract, which is the project admin
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct MigrateMsg {}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
@@ -95,6 +158,58 @@ pub enum ExecuteMsg {
         idx: u32,
         padding: Option<String>,
     },
+    // allocation bucket recipient: claim their share of a vesting event of the bucket
+    ClaimBucketReward {
+        bucket: u16,
+        idx: u32,
+        padding: Option<String>,
+    },
+    // claim the linearly vested snip24 reward accrued since the last claim
+    ClaimVested {
+        padding: Option<String>,
+    },
+    // project funder: move all or part of a pledge and its reward status to another address
+    TransferPledge {
+        recipient: Addr,
+        amount: Uint128,
+        padding: Option<String>,
+    },
+    // project creator: whether matches deposited from now on earn snip24 rewards
+    SetMatchRewards {
+        rewarded: bool,
+        padding: Option<String>,
+    },
+    // sponsor: withdraw the whole match if the project failed, or the unused part of the cap after
+    // it succeeded
+    RefundMatch {
+        padding: Option<String>,
+    },
+    // sponsor: claim a snip24 reward that has vested on a rewarded match
+    ClaimMatchReward {
+        idx: u32,
+        padding: Option<String>,
+    },
+    // member: stop the membership and get the periods after the current one refunded
+    CancelMembership {
+        padding: Option<String>,
+    },
+    // project creator: withdraw the membership revenue of the periods that have elapsed
+    WithdrawMembership {
+        padding: Option<String>,
+    },
+    // project creator: get the bond back after the last vesting event, or if the project failed
+    ClaimBond {
+        padding: Option<String>,
+    },
+    // project funder: receive a pro rata share of a forfeited creator bond
+    ClaimBondShare {
+        padding: Option<String>,
+    },
+    // project funder: vote that the creator has defaulted after pay out (state must be PAID_OUT)
+    VoteDefault {
+        default: bool,
+        padding: Option<String>,
+    },
     // comment on the project
     Comment {
         comment: String,
@@ -105,10 +220,39 @@ pub enum ExecuteMsg {
         flag: bool,
         padding: Option<String>,
     },
+    // platform only: sent by a platform moderator
+    ClearSpamFlags {},
+    // platform only: sent when a moderator delists the project
+    Delist {},
     GenerateViewingKey {
         entropy: String,
         padding: Option<String>,
     },
+    // project creator: register the public key backers use to encrypt fulfillment info
+    SetFulfillmentKey {
+        public_key: Binary,
+        padding: Option<String>,
+    },
+    // project funder or active member: submit shipping details encrypted with the creator's fulfillment key
+    SubmitFulfillmentInfo {
+        info: Binary,
+        padding: Option<String>,
+    },
+    // project creator: publish a survey for backers who contributed at least `threshold`
+    CreateSurvey {
+        questions: Vec<SurveyQuestion>,
+        // zero or one of the reward message thresholds
+        threshold: Uint128,
+        // last block height at which answers are accepted
+        closes_at: u64,
+        padding: Option<String>,
+    },
+    // project funder: answer a survey, replacing any previous answers
+    AnswerSurvey {
+        survey_id: u32,
+        answers: Vec<SurveyAnswer>,
+        padding: Option<String>,
+    },
 }
 
 #[derive(Serialize, Deserialize)]
@@ -117,13 +261,23 @@ pub enum ExecuteReceiveMsg {
     ReceiveContribution {
         anon: bool,
     },
+    // project creator: deposit the creator bond
+    DepositBond {},
+    // sponsor: deposit a cap that adds `ratio` tokens per thousand pledged by backers until the
+    // deadline
+    DepositMatch {
+        ratio: u16,
+    },
+    // member: prepay one or more periods of a membership tier, extending an active membership
+    Subscribe {
+        tier: u16,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
 #[serde(rename_all = "snake_case")]
 pub enum ResponseStatus {
     Success,
-    Failure,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -153,6 +307,51 @@ pub enum ExecuteAnswer {
         status: ResponseStatus,
         msg: String,
     },
+    ClaimBucketReward {
+        status: ResponseStatus,
+        msg: String,
+    },
+    ClaimVested {
+        status: ResponseStatus,
+        msg: String,
+    },
+    TransferPledge {
+        status: ResponseStatus,
+        msg: String,
+    },
+    SetMatchRewards {
+        status: ResponseStatus,
+        msg: String,
+    },
+    RefundMatch {
+        status: ResponseStatus,
+        msg: String,
+    },
+    ClaimMatchReward {
+        status: ResponseStatus,
+        msg: String,
+    },
+    CancelMembership {
+        status: ResponseStatus,
+        msg: String,
+    },
+    WithdrawMembership {
+        status: ResponseStatus,
+        msg: String,
+    },
+    ClaimBond {
+        status: ResponseStatus,
+        msg: String,
+    },
+    ClaimBondShare {
+        status: ResponseStatus,
+        msg: String,
+    },
+    VoteDefault {
+        default_votes: Uint128,
+        status: ResponseStatus,
+        msg: String,
+    },
     Comment {
         status: ResponseStatus,
         msg: String,
@@ -162,25 +361,90 @@ pub enum ExecuteAnswer {
         status: ResponseStatus,
         msg: String,
     },
+    ClearSpamFlags {
+        status: ResponseStatus,
+    },
+    Delist {
+        status: ResponseStatus,
+        msg: String,
+    },
     GenerateViewingKey {
         key: ViewingKey,
     },
+    SetFulfillmentKey {
+        status: ResponseStatus,
+        msg: String,
+    },
+    SubmitFulfillmentInfo {
+        status: ResponseStatus,
+        msg: String,
+    },
+    CreateSurvey {
+        survey_id: Option<u32>,
+        status: ResponseStatus,
+        msg: String,
+    },
+    AnswerSurvey {
+        status: ResponseStatus,
+        msg: String,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
-    // GetStatus returns the current status: Fundraising, Expired, or Successful
+    // Status returns the current status, computed from the current block height
     Status {},
     StatusAuth { address: Addr, key: String },
     StatusWithPermit { permit: Permit },
-    Comments { page: u32, page_size: u32 },
+    // comments after the `start_after` id, oldest first unless order is descending
+    Comments { start_after: Option<u32>, limit: Option<u32>, order: Option<SortOrder> },
+    // sponsor matches in deposit order
+    Matches {},
+    // project creator: encrypted fulfillment info submitted by backers after the `start_after` idx,
+    // oldest first unless order is descending
+    FulfillmentInfo {
+        address: Addr,
+        key: String,
+        start_after: Option<u32>,
+        limit: Option<u32>,
+        order: Option<SortOrder>,
+    },
+    FulfillmentInfoWithPermit {
+        permit: Permit,
+        start_after: Option<u32>,
+        limit: Option<u32>,
+        order: Option<SortOrder>,
+    },
+    // surveys the address can answer (all surveys for the project creator)
+    Surveys { address: Addr, key: String },
+    SurveysWithPermit { permit: Permit },
+    // project creator: aggregated counts and the individual answers after the `start_after` idx,
+    // oldest first unless order is descending
+    SurveyResults {
+        address: Addr,
+        key: String,
+        survey_id: u32,
+        start_after: Option<u32>,
+        limit: Option<u32>,
+        order: Option<SortOrder>,
+    },
+    SurveyResultsWithPermit {
+        permit: Permit,
+        survey_id: u32,
+        start_after: Option<u32>,
+        limit: Option<u32>,
+        order: Option<SortOrder>,
+    },
 }
 
 impl QueryMsg {
     pub fn get_validation_params(&self) -> (Vec<&Addr>, ViewingKey) {
         match self {
             Self::StatusAuth { address, key, .. } => (vec![address], ViewingKey(key.clone())),
+            Self::FulfillmentInfo { address, key, .. } => (vec![address], ViewingKey(key.clone())),
+            Self::Surveys { address, key, .. } => (vec![address], ViewingKey(key.clone())),
+            Self::SurveyResults { address, key, .. } => (vec![address], ViewingKey(key.clone())),
             _ => panic!("This query type does not require authentication"),
         }
     }
@@ -188,10 +452,11 @@ impl QueryMsg {
 
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
+#[allow(clippy::large_enum_variant)]
 pub enum QueryAnswer {
     Status {
         creator: Addr,
-        status: String,
+        status: ProjectStatus,
         paid_out: bool,
         goal: Uint128,
         total: Uint128,
@@ -207,10 +472,13 @@ pub enum QueryAnswer {
         minimum_pledge: Uint128,
         maximum_pledge: Uint128,
         snip24_info: Option<Snip24Info>,
+        fulfillment_public_key: Option<Binary>,
+        bond: Option<BondInfo>,
+        membership: Option<MembershipInfo>,
     },
     StatusAuth {
         creator: Addr,
-        status: String,
+        status: ProjectStatus,
         paid_out: bool,
         goal: Uint128,
         total: Uint128,
@@ -226,23 +494,85 @@ pub enum QueryAnswer {
         minimum_pledge: Uint128,
         maximum_pledge: Uint128, 
         snip24_info: Option<Snip24Info>,
+        fulfillment_public_key: Option<Binary>,
+        bond: Option<BondInfo>,
+        membership: Option<MembershipInfo>,
         pledged_message: Option<String>,
         funded_message: Option<String>,
         reward_messages: Vec<RewardMessage>,
         snip24_rewards: Option<Vec<VestingRewardStatus>>,
+        linear_vesting: Option<LinearVestingStatus>,
+        // shares of the allocation buckets the address is a recipient of
+        bucket_rewards: Option<Vec<BucketRewardStatus>>,
         contribution: Option<Uint128>,
+        // unclaimed share of a forfeited creator bond
+        bond_share: Option<Uint128>,
+        member: Option<Member>,
     },
     Comments {
-        comments: Vec<StoredComment>,
+        comments: Vec<Comment>,
+    },
+    Matches {
+        matches: Vec<SponsorMatch>,
+        // whether new matches earn snip24 rewards
+        rewarded: bool,
+    },
+    FulfillmentInfo {
+        fulfillment_info: Vec<FulfillmentInfo>,
+        count: u32,
+    },
+    Surveys {
+        surveys: Vec<Survey>,
+    },
+    SurveyResults {
+        survey: Survey,
+        // per question tally of each option, empty for non-choice questions
+        choice_counts: Vec<Vec<u32>>,
+        responses: Vec<SurveyResponse>,
     },
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct BondInfo {
+    pub amount: Uint128,
+    pub deposited: bool,
+    // distributed pro rata to backers
+    pub forfeited: bool,
+    pub returned: bool,
+    // block after which the creator can claim the bond back
+    pub release_block: u64,
+    // pledged amount that voted the creator has defaulted
+    pub default_votes: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Comment {
+    // cursor for the next page
+    pub id: u32,
+    pub comment: String,
+    pub from_creator: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct FulfillmentInfo {
+    // cursor for the next page
+    pub idx: u32,
+    // none if the backer pledged anonymously
+    pub address: Option<Addr>,
+    // zero for members who never pledged
+    pub contribution: Uint128,
+    pub info: Binary,
+    pub block: u64,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub enum PlatformQueryMsg {
     ValidatePermit {     
         permit: Permit, 
     },
+    Categories {},
+    Paused {},
 }
 
 impl Query for PlatformQueryMsg {
@@ -257,4 +587,31 @@ pub struct ValidatePermitInnerResponse {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct ValidatePermitResponse {
     pub validate_permit: ValidatePermitInnerResponse,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PausedInnerResponse {
+    pub paused: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PausedResponse {
+    pub paused: PausedInnerResponse,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PlatformCategory {
+    pub id: u16,
+    pub label: String,
+    pub active: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CategoriesInnerResponse {
+    pub categories: Vec<PlatformCategory>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CategoriesResponse {
+    pub categories: CategoriesInnerResponse,
 }
\ No newline at end of file
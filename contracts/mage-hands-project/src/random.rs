@@ -10,11 +10,10 @@ static KEY_ENTROPY_POOL: &[u8] = b"entropy_pool";
 fn get_current_entropy_pool(storage: &dyn Storage) -> [u8; 32] {
     ReadonlySingleton::new(storage, KEY_ENTROPY_POOL)
         .load()
-        .or::<[u8; 32]>(Ok([
+        .unwrap_or([
             0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
             0, 0, 0,
-        ]))
-        .unwrap()
+        ])
 }
 
 pub fn supply_more_entropy(
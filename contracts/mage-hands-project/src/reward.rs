@@ -1,9 +1,19 @@
 use serde::{Deserialize, Serialize};
 use cosmwasm_std::{Addr, Uint128, CanonicalAddr, Binary, };
+use primitive_types::U256;
 
+// contribution_weight values, only linear weighting is implemented so far
+#[allow(dead_code)]
 pub const LINEAR_WEIGHT: u8 = 1;
+#[allow(dead_code)]
 pub const SQRT_WEIGHT: u8 = 2;
+#[allow(dead_code)]
 pub const LOG_WEIGHT: u8 = 3;
+// multiplier of a pledge without a bonus
+pub const PER_MILLE: u128 = 1000;
+// limits on named allocation buckets, bounds the work done at pay out and in status queries
+pub const MAX_ALLOCATION_BUCKETS: usize = 10;
+pub const MAX_BUCKET_RECIPIENTS: usize = 20;
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug,)]
 pub struct Snip24RewardInit {
@@ -32,11 +42,70 @@ pub struct Snip24RewardInit {
     // weighting is applied after minimum and maximum is applied
     pub contribution_weight: u8,
 
+    // optional bonus for early pledges
+    pub bonus_curve: Option<RewardBonusCurve>,
+    // tokens released continuously to contributors, in addition to the schedule
+    pub contributor_linear_vesting: Option<LinearVesting>,
+
     // timeline of release of tokens to creator
     pub creator_vesting_schedule: Vec<VestingEvent>,
+    // tokens released continuously to the creator, in addition to the schedule
+    pub creator_linear_vesting: Option<LinearVesting>,
     // addresses to evenly distribute coins to
     // if none, will distribute to project creator address
     pub creator_addresses: Option<Vec<Addr>>,
+
+    // further named allocations, e.g. treasury or advisors
+    pub allocation_buckets: Option<Vec<AllocationBucket>>,
+}
+
+// Named allocation of the reward token, each vesting event is split evenly among the recipients,
+// with the remainder of the split going to the last one
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug,)]
+pub struct AllocationBucket {
+    pub name: String,
+    pub recipients: Vec<Addr>,
+    pub vesting_schedule: Vec<VestingEvent>,
+}
+
+// Allocation buckets are kept apart from the stored reward init, so settings stored before them still load
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug,)]
+pub struct StoredAllocationBucket {
+    pub name: String,
+    pub recipients: Vec<CanonicalAddr>,
+    pub vesting_schedule: Vec<VestingEvent>,
+}
+
+// Status of a bucket allocation of an address sent in status message
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug,)]
+pub struct BucketRewardStatus {
+    pub bucket: u16,
+    pub name: String,
+    pub rewards: Vec<VestingRewardStatus>,
+}
+
+// Bonus applied to the reward weight of early pledges, in per mille of the pledged amount
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug,)]
+pub struct RewardBonusCurve {
+    // applied to pledges up to `full_until`, e.g. 1500 for 1.5x
+    pub multiplier: u16,
+    // last block height with the full multiplier, it then slides linearly to 1x at the deadline
+    pub full_until: u64,
+}
+
+impl RewardBonusCurve {
+    // per mille multiplier for a pledge made at `height`
+    pub fn multiplier_at(&self, height: u64, deadline: u64) -> u128 {
+        let multiplier = self.multiplier as u128;
+        if height <= self.full_until {
+            return multiplier;
+        }
+        if height >= deadline {
+            return PER_MILLE;
+        }
+        let bonus = multiplier - PER_MILLE;
+        PER_MILLE + bonus * (deadline - height) as u128 / (deadline - self.full_until) as u128
+    }
 }
 
 // Vesting events indicate what block a share of the reward becomes valid
@@ -46,6 +115,46 @@ pub struct VestingEvent {
     pub amount: Uint128,
 }
 
+// Linear vesting releases `amount` continuously from `start` to `end`, nothing can be claimed
+// before `cliff`
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug,)]
+pub struct LinearVesting {
+    pub amount: Uint128,
+    pub start: u64,
+    pub cliff: u64,
+    pub end: u64,
+}
+
+impl LinearVesting {
+    // part of an allocation of `total` that has vested at `height`
+    pub fn vested(&self, total: u128, height: u64) -> u128 {
+        if height < self.cliff {
+            return 0;
+        }
+        if height >= self.end {
+            return total;
+        }
+        let elapsed = (height - self.start) as u128;
+        let duration = (self.end - self.start) as u128;
+        (U256::from(total) * U256::from(elapsed) / U256::from(duration)).as_u128()
+    }
+}
+
+// Linear vesting kept apart from the stored reward init, so settings stored before it still load
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct StoredLinearVestings {
+    pub contributor: Option<LinearVesting>,
+    pub creator: Option<LinearVesting>,
+}
+
+// Linear vesting allocation of an address sent in status message
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug,)]
+pub struct LinearVestingStatus {
+    pub total: Uint128,
+    pub vested: Uint128,
+    pub claimed: Uint128,
+}
+
 // Vesting reward indicate how much the reward is at each block for a given contributor
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug,)]
 pub struct VestingReward {
@@ -156,9 +265,15 @@ pub struct Snip24Info {
     // contribution weighting: one of linear, sqrt, or log
     // weighting is applied after minimum and maximum is applied
     pub contribution_weight: u8,
+    pub bonus_curve: Option<RewardBonusCurve>,
+    pub contributor_linear_vesting: Option<LinearVesting>,
 
     // timeline of release of tokens to creator
     pub creator_vesting_schedule: Vec<VestingEvent>,
+    pub creator_linear_vesting: Option<LinearVesting>,
+
+    // further named allocations and their recipients
+    pub allocation_buckets: Vec<AllocationBucket>,
 
     // address of the snip24 contract once initiated
     pub contract_address: Option<Addr>,
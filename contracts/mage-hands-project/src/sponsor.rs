@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+use cosmwasm_std::{Addr, CanonicalAddr, Uint128,};
+use primitive_types::U256;
+
+// maximum number of sponsors matching a project, bounds the work done on each pledge
+pub const MAX_MATCHES: u32 = 10;
+// ratio is the number of tokens the sponsor adds per thousand tokens from backers
+pub const MATCH_RATIO_DENOM: u128 = 1000;
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug,)]
+pub struct StoredMatch {
+    pub sponsor: CanonicalAddr,
+    pub ratio: u16,
+    // deposited by the sponsor, the most the match can add to the total
+    pub cap: u128,
+    // part of the cap currently counted toward the total
+    pub matched: u128,
+    // earns snip24 contributor rewards on the matched amount, fixed when deposited
+    pub rewarded: bool,
+    pub snip24_rewards_received: Vec<bool>,
+    pub refunded: bool,
+}
+
+impl StoredMatch {
+    // amount the match adds for the given backer total
+    pub fn matched_for(&self, backer_total: u128) -> u128 {
+        let matched = U256::from(backer_total) * U256::from(self.ratio) / U256::from(MATCH_RATIO_DENOM);
+        if matched > U256::from(self.cap) { self.cap } else { matched.as_u128() }
+    }
+}
+
+// Sponsor match sent in query responses
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug,)]
+pub struct SponsorMatch {
+    pub sponsor: Addr,
+    pub ratio: u16,
+    pub cap: Uint128,
+    pub matched: Uint128,
+    pub rewarded: bool,
+    pub refunded: bool,
+}
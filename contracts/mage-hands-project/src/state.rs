@@ -1,18 +1,27 @@
-use crate::reward::{Snip24RewardInit, StoredSnip24RewardInit, RewardMessage, StoredRewardMessage};
+use crate::reward::{
+    Snip24RewardInit, StoredSnip24RewardInit, RewardMessage, StoredRewardMessage, RewardBonusCurve,
+    StoredLinearVestings, AllocationBucket, StoredAllocationBucket,
+};
+use crate::sponsor::StoredMatch;
+use crate::membership::{StoredMembership, StoredMember};
+use crate::survey::{StoredSurvey, StoredSurveyResponse, SurveyAnswer, SurveyQuestion};
 use crate::viewing_key::ViewingKey;
-use cosmwasm_std::{CanonicalAddr, StdError, StdResult, Storage, Api, Uint128 };
+use crate::msg::{SortOrder, TextLimits};
+use crate::error::ContractError;
+use cosmwasm_std::{BlockInfo, CanonicalAddr, StdError, StdResult, Storage, Api, Uint128 };
 use cosmwasm_storage::{prefixed, prefixed_read};
 use secret_toolkit::storage::{AppendStore};
+use primitive_types::U256;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::any::type_name;
 
-pub const FUNDRAISING: u8 = 1_u8;
-pub const EXPIRED: u8 = 2_u8;
-pub const SUCCESSFUL: u8 = 3_u8;
+// storage layout version, bumped with a step in migrate.rs whenever a stored record changes
+pub const CONTRACT_VERSION: u32 = 4;
 
+pub static CONTRACT_VERSION_KEY: &[u8] = b"cver";
 pub static CONFIG_KEY: &[u8] = b"conf";
-pub static STATUS_KEY: &[u8] = b"stat";
+pub static STATUS_KEY: &[u8] = b"psts";
 
 pub static CREATOR_KEY: &[u8] = b"crea";
 pub static TITLE_KEY: &[u8] = b"titl";
@@ -25,23 +34,68 @@ pub static GOAL_KEY: &[u8] = b"goal";
 pub static DEADLINE_KEY: &[u8] = b"dead";
 pub static DEADMAN_KEY: &[u8] = b"dman";
 pub static CATEGORIES_KEY: &[u8] = b"cate";
+pub static TEXT_LIMITS_KEY: &[u8] = b"tlim";
 pub static REWARD_MESSAGES_KEY: &[u8] = b"rwms";
 pub static PLEDGE_MIN_MAX_KEY: &[u8] = b"plmm";
 pub static SNIP24_REWARD_KEY: &[u8] = b"rewa";
 pub static SNIP24_REWARD_ADDRESS_KEY: &[u8] = b"radd";
+pub static SNIP24_BONUS_CURVE_KEY: &[u8] = b"bcrv";
+pub static SNIP24_LINEAR_VESTING_KEY: &[u8] = b"lvst";
+pub static PREFIX_VESTED_CLAIMED_KEY: &[u8] = b"vclm";
+pub static SNIP24_ALLOCATION_BUCKETS_KEY: &[u8] = b"sabk";
+pub static PREFIX_BUCKET_RECEIVED_KEY: &[u8] = b"bkrc";
 pub static SNIP24_CREATOR_ALLOCATION_RECEIVED_KEY: &[u8] = b"scar";
 
 pub static TOTAL_KEY: &[u8] = b"totl";
+pub static WEIGHTED_TOTAL_KEY: &[u8] = b"wttl";
 pub static SPAM_COUNT_KEY: &[u8] = b"spac";
+pub static SPAM_EPOCH_KEY: &[u8] = b"spae";
 
-pub static FUNDER_STORE: AppendStore<CanonicalAddr> = AppendStore::new(b"fund");
-pub static COMMENT_STORE: AppendStore<StoredComment> = AppendStore::new(b"comm");
+// append stores are built where they are used: a static store caches its length for the life of
+// the process, which is a single execution on chain but goes stale across unit tests
+pub fn funder_store() -> AppendStore<'static, CanonicalAddr> {
+    AppendStore::new(b"fund")
+}
+
+pub fn comment_store() -> AppendStore<'static, StoredComment> {
+    AppendStore::new(b"comm")
+}
 
+pub static PREFIX_FUNDER_KEY: &[u8] = b"fndr";
 pub static PREFIX_VIEWING_KEY: &[u8] = b"vkey";
 pub static PREFIX_SPAM_KEY: &[u8] = b"spam";
 pub static SEED_KEY: &[u8] = b"seed";
 
-pub static PAID_OUT_KEY: &[u8] = b"pout";
+pub static BOND_KEY: &[u8] = b"bond";
+pub static BOND_RELEASE_BLOCK_KEY: &[u8] = b"brel";
+pub static DEFAULT_VOTES_KEY: &[u8] = b"dvts";
+pub static PREFIX_DEFAULT_VOTE_KEY: &[u8] = b"dvot";
+
+pub static FULFILLMENT_PUBLIC_KEY_KEY: &[u8] = b"fpub";
+pub static PREFIX_FULFILLMENT_KEY: &[u8] = b"fulf";
+pub fn fulfillment_store() -> AppendStore<'static, CanonicalAddr> {
+    AppendStore::new(b"flst")
+}
+// maximum size in bytes of an encrypted fulfillment info blob
+pub const MAX_FULFILLMENT_INFO_SIZE: usize = 2048;
+
+pub fn survey_store() -> AppendStore<'static, StoredSurvey> {
+    AppendStore::new(b"srvy")
+}
+
+pub fn survey_respondent_store() -> AppendStore<'static, CanonicalAddr> {
+    AppendStore::new(b"srsp")
+}
+pub static PREFIX_SURVEY_RESPONSE_KEY: &[u8] = b"sres";
+
+pub fn match_store() -> AppendStore<'static, StoredMatch> {
+    AppendStore::new(b"mtch")
+}
+pub static MATCH_REWARDS_KEY: &[u8] = b"mrwd";
+
+pub static MEMBERSHIP_KEY: &[u8] = b"mbsp";
+pub static PREFIX_MEMBER_KEY: &[u8] = b"mmbr";
+pub static PREFIX_REVENUE_CHANGE_KEY: &[u8] = b"rchg";
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct Config {
@@ -71,6 +125,15 @@ pub fn get_config(storage: &dyn Storage) -> StdResult<Config> {
     get_bin_data(storage, CONFIG_KEY)
 }
 
+pub fn set_contract_version(storage: &mut dyn Storage, version: u32) -> StdResult<()> {
+    set_bin_data(storage, CONTRACT_VERSION_KEY, &version)
+}
+
+// contracts instantiated before versioning was added are version 1
+pub fn get_contract_version(storage: &dyn Storage) -> u32 {
+    get_bin_data(storage, CONTRACT_VERSION_KEY).unwrap_or(1_u32)
+}
+
 pub fn set_prng_seed(storage: &mut dyn Storage, prng_seed: &Vec<u8>) -> StdResult<()> {
     set_bin_data(storage, SEED_KEY, &prng_seed)
 }
@@ -79,17 +142,120 @@ pub fn get_prng_seed(storage: &dyn Storage) -> StdResult<Vec<u8>> {
     get_bin_data(storage, SEED_KEY)
 }
 
-pub fn set_status(storage: &mut dyn Storage, new_status: u8) -> StdResult<()> {
-    if new_status < 1 || new_status > 3 {
-        return Err(StdError::generic_err("Invalid project status"));
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectStatus {
+    // accepting pledges, goal not reached yet
+    Fundraising,
+    // goal reached, still accepting pledges until the deadline
+    Succeeded,
+    // deadline passed without reaching the goal
+    Failed,
+    // cancelled by the project creator
+    Cancelled,
+    // creator has withdrawn the funds
+    PaidOut,
+    // goal reached, but the creator did not pay out before deadline + deadman
+    DeadmanExpired,
+    // backers voted that the creator defaulted after pay out
+    Defaulted,
+    // removed from the platform by a moderator before pay out, pledges are refundable
+    Delisted,
+}
+
+impl ProjectStatus {
+    pub fn can_transition_to(&self, next: ProjectStatus) -> bool {
+        matches!(
+            (self, next),
+            (ProjectStatus::Fundraising, ProjectStatus::Succeeded)
+                | (ProjectStatus::Fundraising, ProjectStatus::Failed)
+                | (ProjectStatus::Fundraising, ProjectStatus::Cancelled)
+                | (ProjectStatus::Succeeded, ProjectStatus::PaidOut)
+                | (ProjectStatus::Succeeded, ProjectStatus::Cancelled)
+                | (ProjectStatus::Succeeded, ProjectStatus::DeadmanExpired)
+                | (ProjectStatus::PaidOut, ProjectStatus::Defaulted)
+                | (ProjectStatus::Fundraising, ProjectStatus::Delisted)
+                | (ProjectStatus::Succeeded, ProjectStatus::Delisted)
+        )
+    }
+
+    // status taking into account deadlines that have passed since the stored status was written
+    pub fn effective(self, height: u64, deadline: u64, deadman: u64) -> ProjectStatus {
+        match self {
+            ProjectStatus::Fundraising if height > deadline => ProjectStatus::Failed,
+            ProjectStatus::Succeeded if height > deadline.saturating_add(deadman) => ProjectStatus::DeadmanExpired,
+            status => status,
+        }
+    }
+
+    // a funded project keeps taking pledges until its deadline, but not after it
+    pub fn accepts_pledges(&self, height: u64, deadline: u64) -> bool {
+        height <= deadline && matches!(self, ProjectStatus::Fundraising | ProjectStatus::Succeeded)
+    }
+
+    pub fn is_refundable(&self) -> bool {
+        match self {
+            ProjectStatus::Fundraising
+            | ProjectStatus::Failed
+            | ProjectStatus::Cancelled
+            | ProjectStatus::DeadmanExpired
+            | ProjectStatus::Delisted => true,
+            ProjectStatus::Succeeded | ProjectStatus::PaidOut | ProjectStatus::Defaulted => false,
+        }
+    }
+
+    // leaving a funded status other than by paying out forfeits the creator bond, a moderator
+    // delisting the project does not
+    pub fn forfeits_bond(&self, next: ProjectStatus) -> bool {
+        match self {
+            ProjectStatus::Succeeded => next != ProjectStatus::PaidOut && next != ProjectStatus::Delisted,
+            ProjectStatus::PaidOut => true,
+            _ => false,
+        }
     }
-    set_bin_data(storage, STATUS_KEY, &new_status)
 }
 
-pub fn get_status(storage: &dyn Storage) -> StdResult<u8> {
+pub fn init_status(storage: &mut dyn Storage) -> StdResult<()> {
+    set_bin_data(storage, STATUS_KEY, &ProjectStatus::Fundraising)
+}
+
+pub fn set_status(storage: &mut dyn Storage, new_status: ProjectStatus) -> Result<(), ContractError> {
+    let status = get_status(storage)?;
+    if status == new_status {
+        return Ok(());
+    }
+    if !status.can_transition_to(new_status) {
+        return Err(ContractError::InvalidTransition { from: status, to: new_status });
+    }
+    if status.forfeits_bond(new_status) {
+        forfeit_bond(storage)?;
+    }
+    Ok(set_bin_data(storage, STATUS_KEY, &new_status)?)
+}
+
+// stored status, which may be stale if a deadline passed without any execute
+pub fn get_status(storage: &dyn Storage) -> StdResult<ProjectStatus> {
     get_bin_data(storage, STATUS_KEY)
 }
 
+pub fn get_effective_status(storage: &dyn Storage, block: &BlockInfo) -> StdResult<ProjectStatus> {
+    let status = get_status(storage)?;
+    // memberships have no goal or deadline, they stay open until cancelled or delisted
+    if get_membership(storage)?.is_some() {
+        return Ok(status);
+    }
+    let deadline = get_deadline(storage)?;
+    let deadman = get_deadman(storage)?;
+    Ok(status.effective(block.height, deadline, deadman))
+}
+
+// writes any transition caused by a passed deadline and returns the current status
+pub fn sync_status(storage: &mut dyn Storage, block: &BlockInfo) -> Result<ProjectStatus, ContractError> {
+    let status = get_effective_status(storage, block)?;
+    set_status(storage, status)?;
+    Ok(status)
+}
+
 pub fn set_creator(storage: &mut dyn Storage, creator: &CanonicalAddr) -> StdResult<()> {
     set_bin_data(storage, CREATOR_KEY, &creator)
 }
@@ -111,10 +277,7 @@ pub fn set_title(storage: &mut dyn Storage, title: String) -> StdResult<()> {
 }
 
 pub fn get_title(storage: &dyn Storage) -> String {
-    let stored_title: Vec<u8> = match get_bin_data(storage, TITLE_KEY) {
-        Ok(title) => title,
-        Err(_) => vec![],
-    };
+    let stored_title: Vec<u8> = get_bin_data(storage, TITLE_KEY).unwrap_or_default();
     String::from_utf8(stored_title).ok().unwrap_or_default()
 }
 
@@ -123,10 +286,7 @@ pub fn set_subtitle(storage: &mut dyn Storage, subtitle: String) -> StdResult<()
 }
 
 pub fn get_subtitle(storage: &dyn Storage) -> String {
-    let stored_subtitle: Vec<u8> = match get_bin_data(storage, SUBTITLE_KEY) {
-        Ok(subtitle) => subtitle,
-        Err(_) => vec![],
-    };
+    let stored_subtitle: Vec<u8> = get_bin_data(storage, SUBTITLE_KEY).unwrap_or_default();
     String::from_utf8(stored_subtitle).ok().unwrap_or_default()
 }
 
@@ -135,10 +295,7 @@ pub fn set_description(storage: &mut dyn Storage, description: String) -> StdRes
 }
 
 pub fn get_description(storage: &dyn Storage) -> String {
-    let stored_description: Vec<u8> = match get_bin_data(storage, DESCRIPTION_KEY) {
-        Ok(description) => description,
-        Err(_) => vec![],
-    };
+    let stored_description: Vec<u8> = get_bin_data(storage, DESCRIPTION_KEY).unwrap_or_default();
     String::from_utf8(stored_description).ok().unwrap_or_default()
 }
 
@@ -147,10 +304,7 @@ pub fn set_cover_img(storage: &mut dyn Storage, cover_img: String) -> StdResult<
 }
 
 pub fn get_cover_img(storage: &dyn Storage) -> String {
-    let stored_cover_img: Vec<u8> = match get_bin_data(storage, COVER_IMG_KEY) {
-        Ok(cover_img) => cover_img,
-        Err(_) => vec![],
-    };
+    let stored_cover_img: Vec<u8> = get_bin_data(storage, COVER_IMG_KEY).unwrap_or_default();
     String::from_utf8(stored_cover_img).ok().unwrap_or_default()
 }
 
@@ -163,10 +317,7 @@ pub fn set_pledged_message(storage: &mut dyn Storage, pledged_message: String) -
 }
 
 pub fn get_pledged_message(storage: &dyn Storage) -> String {
-    let stored_pledged_message: Vec<u8> = match get_bin_data(storage, PLEDGED_MESSAGE_KEY) {
-        Ok(pledged_message) => pledged_message,
-        Err(_) => vec![],
-    };
+    let stored_pledged_message: Vec<u8> = get_bin_data(storage, PLEDGED_MESSAGE_KEY).unwrap_or_default();
     String::from_utf8(stored_pledged_message).ok().unwrap_or_default()
 }
 
@@ -179,10 +330,7 @@ pub fn set_funded_message(storage: &mut dyn Storage, funded_message: String) ->
 }
 
 pub fn get_funded_message(storage: &dyn Storage) -> String {
-    let stored_funded_message: Vec<u8> = match get_bin_data(storage, FUNDED_MESSAGE_KEY) {
-        Ok(funded_message) => funded_message,
-        Err(_) => vec![],
-    };
+    let stored_funded_message: Vec<u8> = get_bin_data(storage, FUNDED_MESSAGE_KEY).unwrap_or_default();
     String::from_utf8(stored_funded_message).ok().unwrap_or_default()
 }
 
@@ -218,6 +366,15 @@ pub fn get_categories(storage: &dyn Storage) -> StdResult<Vec<u16>> {
     get_bin_data(storage, CATEGORIES_KEY)
 }
 
+pub fn set_text_limits(storage: &mut dyn Storage, text_limits: &TextLimits) -> StdResult<()> {
+    set_bin_data(storage, TEXT_LIMITS_KEY, text_limits)
+}
+
+// projects created before the platform passed limits use the defaults
+pub fn get_text_limits(storage: &dyn Storage) -> TextLimits {
+    get_bin_data(storage, TEXT_LIMITS_KEY).unwrap_or_default()
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct PledgeMinMax {
     pub min: u128,
@@ -262,6 +419,28 @@ pub fn get_reward_messages(storage: &dyn Storage) -> StdResult<Vec<RewardMessage
 }
 
 pub fn set_snip24_reward(storage: &mut dyn Storage, api: &dyn Api, reward: Option<Snip24RewardInit>) -> StdResult<()> {
+    // kept apart so reward settings stored before bonus curves still load
+    let bonus_curve = reward.as_ref().and_then(|reward| reward.bonus_curve.clone());
+    set_bin_data(storage, SNIP24_BONUS_CURVE_KEY, &bonus_curve)?;
+    let linear_vestings = reward.as_ref().map_or(StoredLinearVestings::default(), |reward| StoredLinearVestings {
+        contributor: reward.contributor_linear_vesting.clone(),
+        creator: reward.creator_linear_vesting.clone(),
+    });
+    set_bin_data(storage, SNIP24_LINEAR_VESTING_KEY, &linear_vestings)?;
+    let allocation_buckets: Vec<StoredAllocationBucket> = reward.as_ref()
+        .and_then(|reward| reward.allocation_buckets.clone())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|bucket| Ok(StoredAllocationBucket {
+            name: bucket.name,
+            recipients: bucket.recipients
+                .iter()
+                .map(|a| api.addr_canonicalize(a.as_str()))
+                .collect::<StdResult<Vec<CanonicalAddr>>>()?,
+            vesting_schedule: bucket.vesting_schedule,
+        }))
+        .collect::<StdResult<Vec<StoredAllocationBucket>>>()?;
+    set_bin_data(storage, SNIP24_ALLOCATION_BUCKETS_KEY, &allocation_buckets)?;
     let stored_reward: Option<StoredSnip24RewardInit> = match reward {      
         None => None,
         Some(reward) => Some(StoredSnip24RewardInit {
@@ -280,19 +459,16 @@ pub fn set_snip24_reward(storage: &mut dyn Storage, api: &dyn Api, reward: Optio
             enable_mint: reward.enable_mint,
             enable_burn: reward.enable_burn,
             contributor_vesting_schedule: reward.contributor_vesting_schedule,
-            minimum_contribution: reward.minimum_contribution.and_then(|r| Some(r.u128())),
-            maximum_contribution: reward.maximum_contribution.and_then(|r| Some(r.u128())),
+            minimum_contribution: reward.minimum_contribution.map(|r| r.u128()),
+            maximum_contribution: reward.maximum_contribution.map(|r| r.u128()),
             contribution_weight: reward.contribution_weight,
             creator_vesting_schedule: reward.creator_vesting_schedule,
-            creator_addresses: match reward.creator_addresses {
-                None => None,
-                Some(addresses) => Some(
-                    addresses
-                        .iter()
-                        .map(|a| api.addr_canonicalize(a.as_str()).unwrap())
-                        .collect()
-                ),
-            }
+            creator_addresses: reward.creator_addresses.map(|addresses| {
+                addresses
+                    .iter()
+                    .map(|a| api.addr_canonicalize(a.as_str()).unwrap())
+                    .collect()
+            }),
         }),
     };
     set_bin_data(storage, SNIP24_REWARD_KEY, &stored_reward)
@@ -300,6 +476,18 @@ pub fn set_snip24_reward(storage: &mut dyn Storage, api: &dyn Api, reward: Optio
 
 pub fn get_snip24_reward(storage: &dyn Storage, api: &dyn Api) -> StdResult<Option<Snip24RewardInit>> {
     let stored_reward: Option<StoredSnip24RewardInit> = get_bin_data(storage, SNIP24_REWARD_KEY)?;
+    let linear_vestings: StoredLinearVestings = get_bin_data(storage, SNIP24_LINEAR_VESTING_KEY).unwrap_or_default();
+    let allocation_buckets = get_allocation_buckets(storage)?
+        .into_iter()
+        .map(|bucket| Ok(AllocationBucket {
+            name: bucket.name,
+            recipients: bucket.recipients
+                .iter()
+                .map(|a| api.addr_humanize(a))
+                .collect::<StdResult<Vec<_>>>()?,
+            vesting_schedule: bucket.vesting_schedule,
+        }))
+        .collect::<StdResult<Vec<AllocationBucket>>>()?;
     let reward: Option<Snip24RewardInit> = match stored_reward {
         None => None,
         Some(stored_reward) => Some(Snip24RewardInit {
@@ -318,19 +506,20 @@ pub fn get_snip24_reward(storage: &dyn Storage, api: &dyn Api) -> StdResult<Opti
             enable_mint: stored_reward.enable_mint,
             enable_burn: stored_reward.enable_burn,
             contributor_vesting_schedule: stored_reward.contributor_vesting_schedule,
-            minimum_contribution: stored_reward.minimum_contribution.and_then(|r| Some(Uint128::from(r))),
-            maximum_contribution: stored_reward.maximum_contribution.and_then(|r| Some(Uint128::from(r))),
+            minimum_contribution: stored_reward.minimum_contribution.map(Uint128::from),
+            maximum_contribution: stored_reward.maximum_contribution.map(Uint128::from),
             contribution_weight: stored_reward.contribution_weight,
+            bonus_curve: get_bin_data::<Option<RewardBonusCurve>>(storage, SNIP24_BONUS_CURVE_KEY).unwrap_or(None),
+            contributor_linear_vesting: linear_vestings.contributor,
             creator_vesting_schedule: stored_reward.creator_vesting_schedule,
-            creator_addresses: match stored_reward.creator_addresses {
-                None => None,
-                Some(addresses) => Some(
-                    addresses
-                        .iter()
-                        .map(|a| api.addr_humanize(a).unwrap())
-                        .collect()
-                ),
-            }
+            creator_linear_vesting: linear_vestings.creator,
+            allocation_buckets: (!allocation_buckets.is_empty()).then_some(allocation_buckets),
+            creator_addresses: stored_reward.creator_addresses.map(|addresses| {
+                addresses
+                    .iter()
+                    .map(|a| api.addr_humanize(a).unwrap())
+                    .collect()
+            }),
         }),
     };
     Ok(reward)
@@ -344,6 +533,17 @@ pub fn get_snip24_reward_address(storage: &dyn Storage) -> StdResult<Option<Cano
     get_bin_data(storage, SNIP24_REWARD_ADDRESS_KEY)
 }
 
+// linearly vested reward tokens an address has claimed
+pub fn set_vested_claimed(storage: &mut dyn Storage, addr: &CanonicalAddr, claimed: u128) -> StdResult<()> {
+    let mut claimed_store = prefixed(storage, PREFIX_VESTED_CLAIMED_KEY);
+    set_bin_data(&mut claimed_store, addr.as_slice(), &claimed)
+}
+
+pub fn get_vested_claimed(storage: &dyn Storage, addr: &CanonicalAddr) -> u128 {
+    let claimed_store = prefixed_read(storage, PREFIX_VESTED_CLAIMED_KEY);
+    get_bin_data(&claimed_store, addr.as_slice()).unwrap_or(0)
+}
+
 pub fn set_total(storage: &mut dyn Storage, total: u128) -> StdResult<()> {
     set_bin_data(storage, TOTAL_KEY, &total)
 }
@@ -352,6 +552,15 @@ pub fn get_total(storage: &dyn Storage) -> StdResult<u128> {
     get_bin_data(storage, TOTAL_KEY)
 }
 
+// sum of the reward weight of every pledge
+pub fn set_weighted_total(storage: &mut dyn Storage, total: u128) -> StdResult<()> {
+    set_bin_data(storage, WEIGHTED_TOTAL_KEY, &total)
+}
+
+pub fn get_weighted_total(storage: &dyn Storage) -> StdResult<u128> {
+    get_bin_data(storage, WEIGHTED_TOTAL_KEY)
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct StoredComment {
     pub comment: String,
@@ -363,21 +572,39 @@ pub fn push_comment(storage: &mut dyn Storage, comment: String, from_creator: bo
         comment,
         from_creator,
     };
-    COMMENT_STORE.push(storage, &stored_comment)?;
-    Ok(COMMENT_STORE.get_len(storage)? - 1)
+    comment_store().push(storage, &stored_comment)?;
+    Ok(comment_store().get_len(storage)? - 1)
+}
+
+// AppendStore positions following the `start_after` cursor in the given order
+pub fn cursor_positions(
+    len: u32,
+    start_after: Option<u32>,
+    order: SortOrder,
+) -> Box<dyn Iterator<Item = u32>> {
+    match order {
+        SortOrder::Ascending => {
+            let start = start_after.map_or(0, |idx| idx.saturating_add(1));
+            Box::new(start..len)
+        }
+        SortOrder::Descending => {
+            let end = start_after.map_or(len, |idx| idx.min(len));
+            Box::new((0..end).rev())
+        }
+    }
 }
 
 pub fn get_comments(
     storage: &dyn Storage,
-    page: u32,
-    page_size: u32,
-) -> StdResult<Vec<StoredComment>> {
-    let comments: StdResult<Vec<StoredComment>> = COMMENT_STORE
-        .iter(storage)?
-        .skip((page * page_size) as _)
-        .take(page_size as _)
-        .collect();
-    comments
+    start_after: Option<u32>,
+    limit: u32,
+    order: SortOrder,
+) -> StdResult<Vec<(u32, StoredComment)>> {
+    let len = comment_store().get_len(storage)?;
+    cursor_positions(len, start_after, order)
+        .take(limit as _)
+        .map(|idx| Ok((idx, comment_store().get_at(storage, idx)?)))
+        .collect()
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -386,26 +613,23 @@ pub struct StoredFunder {
     // stay anonymous to project creator
     pub anonymous: bool,
     pub amount: u128,
+    // amount with the early pledge bonus applied to each pledge, used for snip24 rewards
+    pub weighted: u128,
     pub snip24_rewards_received: Vec<bool>,
+    // received share of a forfeited creator bond
+    pub bond_share_claimed: bool,
 }
 
 pub fn set_funder(
     storage: &mut dyn Storage,
     funder_addr: &CanonicalAddr,
-    idx: u32,
-    anonymous: bool,
-    amount: u128,
-    snip24_rewards_received: Vec<bool>,
+    stored_funder: &StoredFunder,
 ) -> StdResult<()> {
+    let mut funder_store = prefixed(storage, PREFIX_FUNDER_KEY);
     set_bin_data(
-        storage,
+        &mut funder_store,
         funder_addr.as_slice(),
-        &StoredFunder {
-            idx,
-            anonymous,
-            amount,
-            snip24_rewards_received,
-        },
+        stored_funder,
     )
 }
 
@@ -413,12 +637,13 @@ pub fn get_funder(
     storage: &dyn Storage,
     funder_addr: &CanonicalAddr,
 ) -> StdResult<StoredFunder> {
-    get_bin_data(storage, funder_addr.as_slice())
+    let funder_store = prefixed_read(storage, PREFIX_FUNDER_KEY);
+    get_bin_data(&funder_store, funder_addr.as_slice())
 }
 
 pub fn push_funder(storage: &mut dyn Storage, funder_addr: &CanonicalAddr) -> StdResult<u32> {
-    FUNDER_STORE.push(storage, &funder_addr)?;
-    Ok(FUNDER_STORE.get_len(storage)? - 1)
+    funder_store().push(storage, funder_addr)?;
+    Ok(funder_store().get_len(storage)? - 1)
 }
 
 pub fn add_funds(
@@ -426,47 +651,101 @@ pub fn add_funds(
     funder_addr: &CanonicalAddr,
     anonymous: bool,
     amount: u128,
+    weighted: u128,
     snip24_rewards_received: Vec<bool>,
 ) -> StdResult<()> {
     // check if has previously put funds in
     let stored_funder = get_funder(storage, funder_addr);
-    let pledge_minmax = get_pledge_minmax(storage)?;
     match stored_funder {
         Ok(stored_funder) => {
-            let new_amount = stored_funder.amount + amount;
-            if new_amount < pledge_minmax.min || new_amount > pledge_minmax.max {
-                return Err(StdError::generic_err(format!("Your pledge is not within the bounds of ({},{})", pledge_minmax.min, pledge_minmax.max)));
-            }
             set_funder(
                 storage,
                 funder_addr,
-                stored_funder.idx,
-                anonymous,
-                new_amount,
-                snip24_rewards_received,
+                &StoredFunder {
+                    anonymous,
+                    amount: stored_funder.amount + amount,
+                    weighted: stored_funder.weighted + weighted,
+                    snip24_rewards_received,
+                    ..stored_funder
+                },
             )?;
         }
         Err(_) => {
-            if amount < pledge_minmax.min || amount > pledge_minmax.max {
-                return Err(StdError::generic_err(format!("Your pledge is not within the bounds of ({},{})", pledge_minmax.min, pledge_minmax.max)));
-            }
             let idx = push_funder(storage, funder_addr)?;
-            set_funder(storage, funder_addr, idx, anonymous, amount, snip24_rewards_received)?;
+            set_funder(storage, funder_addr, &StoredFunder {
+                idx,
+                anonymous,
+                amount,
+                weighted,
+                snip24_rewards_received,
+                bond_share_claimed: false,
+            })?;
         }
     };
     let prev_total = get_total(storage)?;
     set_total(storage, prev_total + amount)?;
+    let prev_weighted_total = get_weighted_total(storage)?;
+    set_weighted_total(storage, prev_weighted_total + weighted)?;
     Ok(())
 }
 
+// moves part of a pledge and its share of the reward weight to another address, the recipient
+// takes on the sender's reward status
+// moves part of a pledge with its share of the weighted amount, which is returned
+pub fn transfer_funds(
+    storage: &mut dyn Storage,
+    from_addr: &CanonicalAddr,
+    to_addr: &CanonicalAddr,
+    amount: u128,
+) -> StdResult<u128> {
+    let sender = get_funder(storage, from_addr)?;
+    let weighted = if amount == sender.amount {
+        sender.weighted
+    } else {
+        (U256::from(sender.weighted) * U256::from(amount) / U256::from(sender.amount)).as_u128()
+    };
+    let recipient = match get_funder(storage, to_addr) {
+        Ok(recipient) => StoredFunder {
+            amount: recipient.amount + amount,
+            weighted: recipient.weighted + weighted,
+            snip24_rewards_received: sender.snip24_rewards_received.clone(),
+            bond_share_claimed: sender.bond_share_claimed,
+            ..recipient
+        },
+        Err(_) => StoredFunder {
+            idx: push_funder(storage, to_addr)?,
+            anonymous: true,
+            amount,
+            weighted,
+            snip24_rewards_received: sender.snip24_rewards_received.clone(),
+            bond_share_claimed: sender.bond_share_claimed,
+        },
+    };
+    set_funder(storage, to_addr, &recipient)?;
+    set_funder(storage, from_addr, &StoredFunder {
+        amount: sender.amount - amount,
+        weighted: sender.weighted - weighted,
+        ..sender
+    })?;
+    Ok(weighted)
+}
+
 pub fn clear_funds(storage: &mut dyn Storage, funder_addr: &CanonicalAddr) -> StdResult<u128> {
     let stored_funder = get_funder(storage, funder_addr)?;
     if stored_funder.amount > 0 {
         let prev_total = get_total(storage)?;
         set_total(storage, prev_total - stored_funder.amount)?;
+        let prev_weighted_total = get_weighted_total(storage)?;
+        set_weighted_total(storage, prev_weighted_total - stored_funder.weighted)?;
     }
-    set_funder(storage, funder_addr, stored_funder.idx, true, 0_u128, stored_funder.snip24_rewards_received)?;
-    Ok(stored_funder.amount)
+    let amount = stored_funder.amount;
+    set_funder(storage, funder_addr, &StoredFunder {
+        anonymous: true,
+        amount: 0_u128,
+        weighted: 0_u128,
+        ..stored_funder
+    })?;
+    Ok(amount)
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
@@ -477,60 +756,474 @@ pub struct Funder {
 
 pub fn get_funders(
     storage: &dyn Storage,
-    page: u32,
-    page_size: u32,
-) -> StdResult<Vec<Funder>> {
-    let funder_iter = FUNDER_STORE
-        .iter(storage)?
-        .skip((page * page_size) as _)
-        .take(page_size as _);
-    let funders: StdResult<Vec<Funder>> = funder_iter
-        .map(|funder| {
-            funder.map(|funder| {
-                let stored_funder = get_funder(storage, &funder);
-                match stored_funder {
-                    Ok(stored_funder) => {
-                        if stored_funder.anonymous {
-                            Funder {
-                                address: None,
-                                amount: stored_funder.amount,
-                            }
-                        } else {
-                            Funder {
-                                address: Some(funder),
-                                amount: stored_funder.amount,
-                            }
+    start_after: Option<u32>,
+    limit: u32,
+    order: SortOrder,
+) -> StdResult<Vec<(u32, Funder)>> {
+    let len = funder_store().get_len(storage)?;
+    cursor_positions(len, start_after, order)
+        .take(limit as _)
+        .map(|idx| {
+            let funder = funder_store().get_at(storage, idx)?;
+            let stored_funder = get_funder(storage, &funder);
+            let funder = match stored_funder {
+                Ok(stored_funder) => {
+                    if stored_funder.anonymous {
+                        Funder {
+                            address: None,
+                            amount: stored_funder.amount,
+                        }
+                    } else {
+                        Funder {
+                            address: Some(funder),
+                            amount: stored_funder.amount,
                         }
                     }
-                    Err(_) => Funder {
-                        address: None,
-                        amount: 0_u128,
-                    },
                 }
-            })
+                Err(_) => Funder {
+                    address: None,
+                    amount: 0_u128,
+                },
+            };
+            Ok((idx, funder))
+        })
+        .collect()
+}
+
+//
+// Creator bond
+//
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StoredBond {
+    // bond the creator must deposit before pledges are accepted
+    pub amount: u128,
+    pub deposited: bool,
+    // total pledged by backers when the bond was forfeited, used for pro rata shares
+    pub forfeit_total: Option<u128>,
+    // bond returned to the creator
+    pub returned: bool,
+}
+
+pub fn set_bond(storage: &mut dyn Storage, bond: &StoredBond) -> StdResult<()> {
+    set_bin_data(storage, BOND_KEY, bond)
+}
+
+pub fn get_bond(storage: &dyn Storage) -> StdResult<StoredBond> {
+    get_bin_data(storage, BOND_KEY)
+}
+
+fn forfeit_bond(storage: &mut dyn Storage) -> StdResult<()> {
+    let mut bond = get_bond(storage)?;
+    if bond.deposited && bond.forfeit_total.is_none() {
+        bond.forfeit_total = Some(get_backer_total(storage)?);
+        set_bond(storage, &bond)?;
+    }
+    Ok(())
+}
+
+// backer share of a forfeited bond, or of a bond that would be forfeited by a pending transition
+pub fn calculate_bond_share(bond: &StoredBond, total: u128, amount: u128) -> u128 {
+    let forfeit_total = bond.forfeit_total.unwrap_or(total);
+    if !bond.deposited || forfeit_total == 0 {
+        return 0;
+    }
+    (U256::from(bond.amount) * U256::from(amount) / U256::from(forfeit_total)).as_u128()
+}
+
+// marks a backer's share of a forfeited bond as claimed, returns the amount owed to them
+pub fn claim_bond_share(storage: &mut dyn Storage, funder_addr: &CanonicalAddr) -> StdResult<u128> {
+    let bond = get_bond(storage)?;
+    let stored_funder = match get_funder(storage, funder_addr) {
+        Ok(stored_funder) => stored_funder,
+        Err(_) => return Ok(0),
+    };
+    if bond.forfeit_total.is_none() || stored_funder.bond_share_claimed {
+        return Ok(0);
+    }
+    let share = calculate_bond_share(&bond, 0, stored_funder.amount);
+    if share > 0 {
+        set_funder(storage, funder_addr, &StoredFunder {
+            bond_share_claimed: true,
+            ..stored_funder
+        })?;
+    }
+    Ok(share)
+}
+
+pub fn get_allocation_buckets(storage: &dyn Storage) -> StdResult<Vec<StoredAllocationBucket>> {
+    Ok(get_bin_data(storage, SNIP24_ALLOCATION_BUCKETS_KEY).unwrap_or_default())
+}
+
+// vesting events of a bucket already received by a recipient, keyed by bucket index and address
+pub fn set_bucket_received(storage: &mut dyn Storage, bucket: u16, addr: &CanonicalAddr, received: &Vec<bool>) -> StdResult<()> {
+    let mut received_store = prefixed(storage, PREFIX_BUCKET_RECEIVED_KEY);
+    let key = [&bucket.to_be_bytes(), addr.as_slice()].concat();
+    set_bin_data(&mut received_store, &key, received)
+}
+
+pub fn get_bucket_received(storage: &dyn Storage, bucket: u16, addr: &CanonicalAddr, len: usize) -> Vec<bool> {
+    let received_store = prefixed_read(storage, PREFIX_BUCKET_RECEIVED_KEY);
+    let key = [&bucket.to_be_bytes(), addr.as_slice()].concat();
+    get_bin_data(&received_store, &key).unwrap_or(vec![false; len])
+}
+
+// the bond is held until the deadman time and every vesting event have passed
+pub fn calculate_bond_release_block(deadline: u64, deadman: u64, reward_init: Option<&Snip24RewardInit>) -> u64 {
+    let last_vesting_block = reward_init
+        .and_then(|reward_init| {
+            reward_init.contributor_vesting_schedule
+                .iter()
+                .chain(reward_init.creator_vesting_schedule.iter())
+                .map(|event| event.block)
+                .chain(reward_init.contributor_linear_vesting.iter().map(|vesting| vesting.end))
+                .chain(reward_init.creator_linear_vesting.iter().map(|vesting| vesting.end))
+                .chain(reward_init.allocation_buckets.iter().flatten()
+                    .flat_map(|bucket| bucket.vesting_schedule.iter().map(|event| event.block)))
+                .max()
+        })
+        .unwrap_or(0);
+    std::cmp::max(deadline.saturating_add(deadman), last_vesting_block)
+}
+
+pub fn set_bond_release_block(storage: &mut dyn Storage, block: u64) -> StdResult<()> {
+    set_bin_data(storage, BOND_RELEASE_BLOCK_KEY, &block)
+}
+
+pub fn get_bond_release_block(storage: &dyn Storage) -> StdResult<u64> {
+    get_bin_data(storage, BOND_RELEASE_BLOCK_KEY)
+}
+
+pub fn get_default_votes(storage: &dyn Storage) -> u128 {
+    get_bin_data(storage, DEFAULT_VOTES_KEY).unwrap_or(0_u128)
+}
+
+// sets the weight of a backer's default vote, returns the new total of default votes
+pub fn set_default_vote(storage: &mut dyn Storage, funder_addr: &CanonicalAddr, weight: u128) -> StdResult<u128> {
+    let previous_weight = get_default_vote(storage, funder_addr);
+    let votes = get_default_votes(storage) - previous_weight + weight;
+    set_bin_data(storage, DEFAULT_VOTES_KEY, &votes)?;
+    let mut vote_store = prefixed(storage, PREFIX_DEFAULT_VOTE_KEY);
+    set_bin_data(&mut vote_store, funder_addr.as_slice(), &weight)?;
+    Ok(votes)
+}
+
+pub fn get_default_vote(storage: &dyn Storage, funder_addr: &CanonicalAddr) -> u128 {
+    let vote_store = prefixed_read(storage, PREFIX_DEFAULT_VOTE_KEY);
+    get_bin_data(&vote_store, funder_addr.as_slice()).unwrap_or(0_u128)
+}
+
+//
+// Fulfillment info
+//
+
+pub fn set_fulfillment_public_key(storage: &mut dyn Storage, public_key: Option<Vec<u8>>) -> StdResult<()> {
+    set_bin_data(storage, FULFILLMENT_PUBLIC_KEY_KEY, &public_key)
+}
+
+pub fn get_fulfillment_public_key(storage: &dyn Storage) -> Option<Vec<u8>> {
+    get_bin_data(storage, FULFILLMENT_PUBLIC_KEY_KEY).unwrap_or(None)
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct StoredFulfillmentInfo {
+    pub idx: u32,
+    // blob encrypted by the backer with the creator's fulfillment public key
+    pub info: Vec<u8>,
+    // block height of the latest submission
+    pub block: u64,
+}
+
+pub fn set_fulfillment_info(
+    storage: &mut dyn Storage,
+    funder_addr: &CanonicalAddr,
+    info: Vec<u8>,
+    block: u64,
+) -> StdResult<()> {
+    // resubmitting replaces the previous blob but keeps its position in the list
+    let idx = match get_fulfillment_info(storage, funder_addr) {
+        Ok(stored_info) => stored_info.idx,
+        Err(_) => {
+            fulfillment_store().push(storage, funder_addr)?;
+            fulfillment_store().get_len(storage)? - 1
+        }
+    };
+    let mut fulfillment_store = prefixed(storage, PREFIX_FULFILLMENT_KEY);
+    set_bin_data(
+        &mut fulfillment_store,
+        funder_addr.as_slice(),
+        &StoredFulfillmentInfo { idx, info, block },
+    )
+}
+
+pub fn get_fulfillment_info(
+    storage: &dyn Storage,
+    funder_addr: &CanonicalAddr,
+) -> StdResult<StoredFulfillmentInfo> {
+    let fulfillment_store = prefixed_read(storage, PREFIX_FULFILLMENT_KEY);
+    get_bin_data(&fulfillment_store, funder_addr.as_slice())
+}
+
+pub fn fulfillment_info_count(storage: &dyn Storage) -> StdResult<u32> {
+    fulfillment_store().get_len(storage)
+}
+
+pub fn get_fulfillment_infos(
+    storage: &dyn Storage,
+    start_after: Option<u32>,
+    limit: u32,
+    order: SortOrder,
+) -> StdResult<Vec<(u32, CanonicalAddr, StoredFulfillmentInfo)>> {
+    let len = fulfillment_store().get_len(storage)?;
+    cursor_positions(len, start_after, order)
+        .take(limit as _)
+        .map(|idx| {
+            let funder_addr = fulfillment_store().get_at(storage, idx)?;
+            let stored_info = get_fulfillment_info(storage, &funder_addr)?;
+            Ok((idx, funder_addr, stored_info))
+        })
+        .collect()
+}
+
+//
+// Sponsor matches
+//
+
+pub fn push_match(storage: &mut dyn Storage, sponsor_match: &StoredMatch) -> StdResult<u32> {
+    match_store().push(storage, sponsor_match)?;
+    Ok(match_store().get_len(storage)? - 1)
+}
+
+pub fn set_match(storage: &mut dyn Storage, idx: u32, sponsor_match: &StoredMatch) -> StdResult<()> {
+    match_store().set_at(storage, idx, sponsor_match)
+}
+
+pub fn get_matches(storage: &dyn Storage) -> StdResult<Vec<StoredMatch>> {
+    match_store().iter(storage)?.collect()
+}
+
+pub fn find_match(storage: &dyn Storage, sponsor: &CanonicalAddr) -> StdResult<Option<(u32, StoredMatch)>> {
+    Ok(get_matches(storage)?
+        .into_iter()
+        .enumerate()
+        .find(|(_, sponsor_match)| sponsor_match.sponsor == *sponsor)
+        .map(|(idx, sponsor_match)| (idx as u32, sponsor_match)))
+}
+
+// creator opt in for new matches to earn snip24 rewards
+pub fn set_match_rewards(storage: &mut dyn Storage, rewarded: bool) -> StdResult<()> {
+    set_bin_data(storage, MATCH_REWARDS_KEY, &rewarded)
+}
+
+pub fn get_match_rewards(storage: &dyn Storage) -> bool {
+    get_bin_data(storage, MATCH_REWARDS_KEY).unwrap_or(false)
+}
+
+// total pledged by backers, without sponsor matches
+pub fn get_backer_total(storage: &dyn Storage) -> StdResult<u128> {
+    let matched: u128 = get_matches(storage)?.iter().map(|sponsor_match| sponsor_match.matched).sum();
+    Ok(get_total(storage)? - matched)
+}
+
+// weight that snip24 contributor rewards are split over, matches count without a bonus and only
+// when rewarded
+pub fn get_rewarded_total(storage: &dyn Storage) -> StdResult<u128> {
+    let rewarded: u128 = get_matches(storage)?
+        .iter()
+        .filter(|sponsor_match| sponsor_match.rewarded)
+        .map(|sponsor_match| sponsor_match.matched)
+        .sum();
+    Ok(get_weighted_total(storage)? + rewarded)
+}
+
+// recomputes each match from the backer total and updates the project total to include them
+pub fn rebalance_matches(storage: &mut dyn Storage) -> StdResult<()> {
+    let backer_total = get_backer_total(storage)?;
+    let mut total = backer_total;
+    for (idx, mut sponsor_match) in get_matches(storage)?.into_iter().enumerate() {
+        sponsor_match.matched = sponsor_match.matched_for(backer_total);
+        total += sponsor_match.matched;
+        set_match(storage, idx as u32, &sponsor_match)?;
+    }
+    set_total(storage, total)
+}
+
+//
+// Memberships
+//
+
+pub fn set_membership(storage: &mut dyn Storage, membership: &StoredMembership) -> StdResult<()> {
+    set_bin_data(storage, MEMBERSHIP_KEY, membership)
+}
+
+// none for one-shot campaigns
+pub fn get_membership(storage: &dyn Storage) -> StdResult<Option<StoredMembership>> {
+    match storage.get(MEMBERSHIP_KEY) {
+        Some(_) => Ok(Some(get_bin_data(storage, MEMBERSHIP_KEY)?)),
+        None => Ok(None),
+    }
+}
+
+pub fn set_member(storage: &mut dyn Storage, member_addr: &CanonicalAddr, member: &StoredMember) -> StdResult<()> {
+    let mut member_store = prefixed(storage, PREFIX_MEMBER_KEY);
+    set_bin_data(&mut member_store, member_addr.as_slice(), member)
+}
+
+pub fn get_member(storage: &dyn Storage, member_addr: &CanonicalAddr) -> Option<StoredMember> {
+    let member_store = prefixed_read(storage, PREFIX_MEMBER_KEY);
+    get_bin_data(&member_store, member_addr.as_slice()).ok()
+}
+
+// revenue of a period is the running sum of the changes in it and every earlier period
+pub fn add_revenue_change(storage: &mut dyn Storage, period: u64, change: i128) -> StdResult<()> {
+    let prev = get_revenue_change(storage, period);
+    let mut change_store = prefixed(storage, PREFIX_REVENUE_CHANGE_KEY);
+    set_bin_data(&mut change_store, &period.to_be_bytes(), &(prev + change))
+}
+
+pub fn get_revenue_change(storage: &dyn Storage, period: u64) -> i128 {
+    let change_store = prefixed_read(storage, PREFIX_REVENUE_CHANGE_KEY);
+    get_bin_data(&change_store, &period.to_be_bytes()).unwrap_or(0)
+}
+
+// records that the project stopped in this period
+pub fn close_membership(storage: &mut dyn Storage, height: u64) -> StdResult<()> {
+    if let Some(mut membership) = get_membership(storage)? {
+        if membership.closed.is_none() {
+            membership.closed = Some(membership.period_at(height));
+            set_membership(storage, &membership)?;
+        }
+    }
+    Ok(())
+}
+
+//
+// Surveys
+//
+
+pub fn push_survey(
+    storage: &mut dyn Storage,
+    questions: Vec<SurveyQuestion>,
+    threshold: u128,
+    closes_at: u64,
+) -> StdResult<u32> {
+    let choice_counts = questions
+        .iter()
+        .map(|question| match question {
+            SurveyQuestion::Choice { options, .. } => vec![0_u32; options.len()],
+            _ => vec![],
         })
         .collect();
-    funders
+    survey_store().push(storage, &StoredSurvey {
+        questions,
+        threshold,
+        closes_at,
+        response_count: 0,
+        choice_counts,
+    })?;
+    Ok(survey_store().get_len(storage)? - 1)
 }
 
-pub fn paid_out(storage: &mut dyn Storage) -> StdResult<()> {
-    set_bin_data(storage, PAID_OUT_KEY, &true)
+pub fn get_survey(storage: &dyn Storage, survey_id: u32) -> StdResult<StoredSurvey> {
+    survey_store().get_at(storage, survey_id)
 }
 
-pub fn is_paid_out(storage: &dyn Storage) -> bool {
-    get_bin_data(storage, PAID_OUT_KEY).unwrap_or_else(|_| false)
+pub fn get_surveys(storage: &dyn Storage) -> StdResult<Vec<StoredSurvey>> {
+    survey_store().iter(storage)?.collect()
+}
+
+fn survey_response_prefix(survey_id: u32) -> Vec<u8> {
+    [PREFIX_SURVEY_RESPONSE_KEY, &survey_id.to_be_bytes()].concat()
+}
+
+pub fn get_survey_response(
+    storage: &dyn Storage,
+    survey_id: u32,
+    funder_addr: &CanonicalAddr,
+) -> StdResult<StoredSurveyResponse> {
+    let response_store = prefixed_read(storage, &survey_response_prefix(survey_id));
+    get_bin_data(&response_store, funder_addr.as_slice())
+}
+
+fn update_choice_counts(survey: &mut StoredSurvey, answers: &[SurveyAnswer], increment: bool) {
+    for (question_idx, answer) in answers.iter().enumerate() {
+        if let SurveyAnswer::Choice { option } = answer {
+            let count = &mut survey.choice_counts[question_idx][*option as usize];
+            if increment {
+                *count += 1;
+            } else {
+                *count = count.saturating_sub(1);
+            }
+        }
+    }
+}
+
+// answers must already be validated against the survey questions
+pub fn set_survey_response(
+    storage: &mut dyn Storage,
+    survey_id: u32,
+    funder_addr: &CanonicalAddr,
+    answers: Vec<SurveyAnswer>,
+    block: u64,
+) -> StdResult<()> {
+    let mut survey = get_survey(storage, survey_id)?;
+    let respondents = survey_respondent_store().add_suffix(&survey_id.to_be_bytes());
+    // answering again replaces the previous answers in the tally
+    let idx = match get_survey_response(storage, survey_id, funder_addr) {
+        Ok(previous) => {
+            update_choice_counts(&mut survey, &previous.answers, false);
+            previous.idx
+        }
+        Err(_) => {
+            respondents.push(storage, funder_addr)?;
+            survey.response_count += 1;
+            respondents.get_len(storage)? - 1
+        }
+    };
+    update_choice_counts(&mut survey, &answers, true);
+    survey_store().set_at(storage, survey_id, &survey)?;
+
+    let mut response_store = prefixed(storage, &survey_response_prefix(survey_id));
+    set_bin_data(
+        &mut response_store,
+        funder_addr.as_slice(),
+        &StoredSurveyResponse { idx, answers, block },
+    )
+}
+
+pub fn get_survey_responses(
+    storage: &dyn Storage,
+    survey_id: u32,
+    start_after: Option<u32>,
+    limit: u32,
+    order: SortOrder,
+) -> StdResult<Vec<(CanonicalAddr, StoredSurveyResponse)>> {
+    let respondents = survey_respondent_store().add_suffix(&survey_id.to_be_bytes());
+    let len = respondents.get_len(storage)?;
+    cursor_positions(len, start_after, order)
+        .take(limit as _)
+        .map(|idx| {
+            let funder_addr = respondents.get_at(storage, idx)?;
+            let response = get_survey_response(storage, survey_id, &funder_addr)?;
+            Ok((funder_addr, response))
+        })
+        .collect()
 }
 
 //
 // Spam flag
 //
+// a flag is stored as 1 followed by the epoch it was set in, flags from earlier epochs are cleared
 pub fn set_spam_flag(storage: &mut dyn Storage, addr: &CanonicalAddr, spam: bool) -> StdResult<()> {
     let current_spam = get_spam_flag(storage, addr)?;
+    let epoch = get_spam_epoch(storage);
 
     let mut spam_store = prefixed(storage, PREFIX_SPAM_KEY);
     if spam {
         if !current_spam {
-            spam_store.set(addr.as_slice(), &[1_u8]);
+            let mut flag = vec![1_u8];
+            flag.extend_from_slice(&epoch.to_be_bytes());
+            spam_store.set(addr.as_slice(), &flag);
             increment_spam_count(storage)?;
         }
     } else {
@@ -543,19 +1236,34 @@ pub fn set_spam_flag(storage: &mut dyn Storage, addr: &CanonicalAddr, spam: bool
 }
 
 pub fn get_spam_flag(storage: &dyn Storage, addr: &CanonicalAddr) -> StdResult<bool> {
+    let epoch = get_spam_epoch(storage);
     let spam_store = prefixed_read(storage, PREFIX_SPAM_KEY);
     let spam = spam_store.get(addr.as_slice());
     match spam.as_deref() {
         None => Ok(false),
         Some([0_u8]) => Ok(false),
+        // set before flags could be cleared
+        Some([1_u8]) => Ok(epoch == 0),
+        Some([1_u8, flag_epoch @ ..]) => Ok(flag_epoch == epoch.to_be_bytes().as_slice()),
         _ => Ok(true)
     }
 }
 
+fn get_spam_epoch(storage: &dyn Storage) -> u32 {
+    get_bin_data(storage, SPAM_EPOCH_KEY).unwrap_or(0)
+}
+
+// drops every spam flag at once by starting a new epoch
+pub fn clear_spam_flags(storage: &mut dyn Storage) -> StdResult<()> {
+    let epoch = get_spam_epoch(storage);
+    set_bin_data(storage, SPAM_EPOCH_KEY, &(epoch + 1))?;
+    set_bin_data(storage, SPAM_COUNT_KEY, &0_u32)
+}
+
 pub fn increment_spam_count(storage: &mut dyn Storage) -> StdResult<u32> {
     let spam_count = get_spam_count(storage)?;
     set_bin_data(storage, SPAM_COUNT_KEY, &(spam_count + 1))?;
-    return Ok(spam_count + 1);
+    Ok(spam_count + 1)
 }
 
 pub fn decrement_spam_count(storage: &mut dyn Storage) -> StdResult<u32> {
@@ -564,7 +1272,7 @@ pub fn decrement_spam_count(storage: &mut dyn Storage) -> StdResult<u32> {
         return Ok(spam_count);
     }
     set_bin_data(storage, SPAM_COUNT_KEY, &(spam_count - 1))?;
-    return Ok(spam_count - 1);
+    Ok(spam_count - 1)
 }
 
 pub fn get_spam_count(storage: &dyn Storage) -> StdResult<u32> {
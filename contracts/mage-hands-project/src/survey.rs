@@ -0,0 +1,135 @@
+use serde::{Deserialize, Serialize};
+use cosmwasm_std::{Addr, Uint128,};
+
+// maximum number of questions in a survey
+pub const MAX_SURVEY_QUESTIONS: usize = 20;
+// maximum number of options in a choice question
+pub const MAX_SURVEY_CHOICES: usize = 20;
+// maximum length of a free text answer
+pub const MAX_SURVEY_TEXT_LENGTH: u32 = 512;
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug,)]
+#[serde(rename_all = "snake_case")]
+pub enum SurveyQuestion {
+    // pick one of the options
+    Choice {
+        prompt: String,
+        options: Vec<String>,
+    },
+    // free text answer up to max_length characters
+    Text {
+        prompt: String,
+        max_length: u32,
+    },
+    // integer answer within optional bounds
+    Number {
+        prompt: String,
+        min: Option<i64>,
+        max: Option<i64>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug,)]
+#[serde(rename_all = "snake_case")]
+pub enum SurveyAnswer {
+    Choice {
+        option: u32,
+    },
+    Text {
+        text: String,
+    },
+    Number {
+        value: i64,
+    },
+}
+
+impl SurveyQuestion {
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            SurveyQuestion::Choice { options, .. } => {
+                if options.len() < 2 || options.len() > MAX_SURVEY_CHOICES {
+                    return Err(format!("Choice questions must have between 2 and {} options", MAX_SURVEY_CHOICES));
+                }
+            }
+            SurveyQuestion::Text { max_length, .. } => {
+                if *max_length == 0 || *max_length > MAX_SURVEY_TEXT_LENGTH {
+                    return Err(format!("Text questions must have a max length between 1 and {}", MAX_SURVEY_TEXT_LENGTH));
+                }
+            }
+            SurveyQuestion::Number { min, max, .. } => {
+                if let (Some(min), Some(max)) = (min, max) {
+                    if min > max {
+                        return Err(String::from("Number questions cannot have a minimum greater than the maximum"));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // checks that an answer has the same type as the question and is within its bounds
+    pub fn check_answer(&self, answer: &SurveyAnswer) -> Result<(), String> {
+        match (self, answer) {
+            (SurveyQuestion::Choice { options, .. }, SurveyAnswer::Choice { option }) => {
+                if (*option as usize) >= options.len() {
+                    return Err(format!("Invalid option {}", option));
+                }
+            }
+            (SurveyQuestion::Text { max_length, .. }, SurveyAnswer::Text { text }) => {
+                if text.chars().count() > *max_length as usize {
+                    return Err(format!("Text answer is longer than {} characters", max_length));
+                }
+            }
+            (SurveyQuestion::Number { min, max, .. }, SurveyAnswer::Number { value }) => {
+                if min.is_some_and(|min| *value < min) || max.is_some_and(|max| *value > max) {
+                    return Err(format!("Number answer {} is out of bounds", value));
+                }
+            }
+            _ => { return Err(String::from("Answer type does not match question type")); }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug,)]
+pub struct StoredSurvey {
+    pub questions: Vec<SurveyQuestion>,
+    // minimum contribution to be able to answer the survey
+    pub threshold: u128,
+    // last block height at which answers are accepted
+    pub closes_at: u64,
+    // number of backers who answered
+    pub response_count: u32,
+    // per question tally of each option, empty for non-choice questions
+    pub choice_counts: Vec<Vec<u32>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug,)]
+pub struct StoredSurveyResponse {
+    pub idx: u32,
+    pub answers: Vec<SurveyAnswer>,
+    pub block: u64,
+}
+
+// Survey sent in query responses
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug,)]
+pub struct Survey {
+    pub id: u32,
+    pub questions: Vec<SurveyQuestion>,
+    pub threshold: Uint128,
+    pub closes_at: u64,
+    pub response_count: u32,
+    // answers of the querying backer, if any
+    pub answers: Option<Vec<SurveyAnswer>>,
+}
+
+// Individual survey response sent to the project creator
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug,)]
+pub struct SurveyResponse {
+    // cursor for the next page
+    pub idx: u32,
+    // none if the backer pledged anonymously
+    pub address: Option<Addr>,
+    pub answers: Vec<SurveyAnswer>,
+    pub block: u64,
+}
@@ -24,6 +24,6 @@ pub fn space_pad(block_size: usize, message: &mut Vec<u8>) -> &mut Vec<u8> {
 
     let missing = block_size - surplus;
     message.reserve(missing);
-    message.extend(std::iter::repeat(b' ').take(missing));
+    message.extend(std::iter::repeat_n(b' ', missing));
     message
 }
@@ -4,7 +4,7 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use cosmwasm_std::{Env, MessageInfo,};
-use secret_toolkit::crypto::{sha_256, Prng};
+use secret_toolkit::crypto::{sha_256, ContractPrng};
 
 use crate::utils::{create_hashed_password, ct_slice_compare};
 
@@ -27,10 +27,10 @@ impl ViewingKey {
         let mut rng_entropy = Vec::with_capacity(entropy_len);
         rng_entropy.extend_from_slice(&env.block.height.to_be_bytes());
         //rng_entropy.extend_from_slice(&env.block.time.to_be_bytes());
-        rng_entropy.extend_from_slice(&info.sender.as_bytes());
+        rng_entropy.extend_from_slice(info.sender.as_bytes());
         rng_entropy.extend_from_slice(entropy);
 
-        let mut rng = Prng::new(seed, &rng_entropy);
+        let mut rng = ContractPrng::new(seed, &rng_entropy);
 
         let rand_slice = rng.rand_bytes();
 
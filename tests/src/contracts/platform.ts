@@ -9,12 +9,26 @@ export type PlatformInitMsg = {
     project_contract_code_hash: string;
     token_min_max_pledges: PledgeMinMax[];
     deadman?: number;
+    max_duration?: number;
+    text_limits?: TextLimits;
+    categories?: string[];
 }
 
 export type PledgeMinMax = {
     token_addr: string;
     min: string;
     max: string;
+    min_goal?: string;
+    max_goal?: string;
+}
+
+export type TextLimits = {
+    max_title_len: number;
+    max_subtitle_len: number;
+    max_description_len: number;
+    max_message_len: number;
+    max_cover_img_len: number;
+    max_categories: number;
 }
 
 export type PlatformCreateMsg = {
@@ -36,16 +50,25 @@ export type PlatformCreateMsg = {
 }
 
 export type PlatformConfigMsg = {
-    owner?: string;
     project_contract_code_id?: number;
     project_contract_code_hash?: string;
     deadman?: number;
+    token_min_max_pledges?: PledgeMinMax[];
+    max_duration?: number;
+    text_limits?: TextLimits;
     padding?: string;
 }
 
-export type PlatformRegisterMsg = {
-    contract_addr: string;
-    contract_code_hash: string;
+export type PlatformCategory = {
+    id: number;
+    label: string;
+    active: boolean;
+}
+
+export type PlatformCategoriesResult = {
+    categories: {
+        categories: PlatformCategory[];
+    };
 }
 
 export type PlatformContractInfo = {
@@ -53,9 +76,43 @@ export type PlatformContractInfo = {
     address: string;
 }
 
+export type PlatformProjectSummary = {
+    title: string;
+    creator: string;
+    categories: number[];
+    goal: string;
+    deadline: number;
+    token: string;
+    status: string;
+    hidden: boolean;
+    membership: boolean;
+}
+
+export type PlatformProjectInfo = PlatformContractInfo & {
+    project_id: number;
+    summary?: PlatformProjectSummary;
+    delisting?: {
+        reason: string;
+        text: string;
+        height: number;
+        time: number;
+    };
+}
+
+export type SortOrder = "ascending" | "descending";
+
+export type PlatformProjectsFilter = {
+    category?: number;
+    status?: string;
+    creator?: string;
+    token?: string;
+    hidden?: boolean;
+    delisted?: boolean;
+}
+
 export type PlatformProjectsResult = {
     projects: {
-        projects: PlatformContractInfo[];
+        projects: PlatformProjectInfo[];
         count: number;
     };
 }
@@ -77,15 +134,21 @@ export class PlatformContractInstance extends ContractInstance {
         return tx;
     }
 
-    async register(secretjs: SecretNetworkClient, registerMsg: PlatformRegisterMsg, gasLimit: number = 150_000): Promise<Tx> {
-        const msg = { register: registerMsg };
+    async queryProjects(secretjs: SecretNetworkClient, start_after?: number, limit: number = 10, order?: SortOrder, filter?: PlatformProjectsFilter): Promise<PlatformProjectsResult> {
+        const query = { projects: { start_after, limit, order, filter } };
+        const result = (await this.query(secretjs, query)) as PlatformProjectsResult;
+        return result;
+    }
+
+    async setCategory(secretjs: SecretNetworkClient, label: string, active: boolean, id?: number, gasLimit: number = 150_000): Promise<Tx> {
+        const msg = { set_category: { id, label, active } };
         const tx = await this.exec(secretjs, msg, gasLimit);
         return tx;
     }
 
-    async queryProjects(secretjs: SecretNetworkClient, page: number = 0, page_size: number = 10): Promise<PlatformProjectsResult> {
-        const query = { projects: { page, page_size } };
-        const result = (await this.query(secretjs, query)) as PlatformProjectsResult;
+    async queryCategories(secretjs: SecretNetworkClient): Promise<PlatformCategoriesResult> {
+        const query = { categories: {} };
+        const result = (await this.query(secretjs, query)) as PlatformCategoriesResult;
         return result;
     }
 
@@ -90,8 +90,14 @@ export type RewardMessage = {
     message: string;
 }
 
+export type ProjectComment = {
+    id: number;
+    comment: string;
+    from_creator: boolean;
+}
+
 export type ProjectCommentsResult = {
-    comments: string[];
+    comments: ProjectComment[];
 }
 
 export class ProjectContractInstance extends ContractInstance {
@@ -141,8 +147,8 @@ export class ProjectContractInstance extends ContractInstance {
         return tx;
     }
 
-    async queryComments(secretjs: SecretNetworkClient, page: number = 0, page_size: number = 10): Promise<ProjectCommentsResult> {
-        const query = { comments: { page, page_size } };
+    async queryComments(secretjs: SecretNetworkClient, start_after?: number, limit: number = 10, order?: "ascending" | "descending"): Promise<ProjectCommentsResult> {
+        const query = { comments: { start_after, limit, order } };
         const result = (await this.query(secretjs, query)) as ProjectCommentsResult;
         return result;
     }
@@ -160,6 +160,7 @@ const setupPlatformContract = async (
                 max: "10000000000", // 10000 sscrt
             },
         ],
+        categories: ["Secret Network", "Crypto", "NFTs", "Art"],
     };
     await platform.instantiate(secretjs, platformInitMsg, `platform-${platformCode.codeId}`);
     console.log(platform.address);
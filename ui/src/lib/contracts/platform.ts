@@ -1,6 +1,6 @@
-import { SecretNetworkClient, Tx} from "secretjs";
+import { SecretNetworkClient, Permit, Tx} from "secretjs";
 import { ContractInstance } from "./contracts";
-import { ProjectRewardMessage, Snip24RewardInit } from "./project";
+import { ProjectMembershipInit, ProjectRewardMessage, ProjectStatus, Snip24RewardInit, SortOrder } from "./project";
 
 export type PlatformInitMsg = {
     owner?: string;
@@ -8,12 +8,26 @@ export type PlatformInitMsg = {
     project_contract_code_hash: string;
     token_min_max_pledges: PledgeMinMax[];
     deadman?: number;
+    max_duration?: number;
+    text_limits?: TextLimits;
+    categories?: string[];
 }
 
 export type PledgeMinMax = {
     token_addr: string;
     min: string;
     max: string;
+    min_goal?: string;
+    max_goal?: string;
+}
+
+export type TextLimits = {
+    max_title_len: number;
+    max_subtitle_len: number;
+    max_description_len: number;
+    max_message_len: number;
+    max_cover_img_len: number;
+    max_categories: number;
 }
 
 export type PlatformCreateMsg = {
@@ -30,21 +44,33 @@ export type PlatformCreateMsg = {
     snip20_contract: string;
     snip20_hash: string;
     snip24_reward_init?: Snip24RewardInit;
+    // goal and deadline are not used for memberships
+    membership?: ProjectMembershipInit;
     entropy: string; // used to set up prng in project contract
     padding?: string;
 }
 
 export type PlatformConfigMsg = {
-    owner?: string;
     project_contract_code_id?: number;
     project_contract_code_hash?: string;
     deadman?: number;
+    token_min_max_pledges?: PledgeMinMax[];
+    max_duration?: number;
+    clear_max_duration?: boolean;
+    text_limits?: TextLimits;
     padding?: string;
 }
 
-export type PlatformRegisterMsg = {
-    contract_addr: string;
-    contract_code_hash: string;
+export type PlatformCategory = {
+    id: number;
+    label: string;
+    active: boolean;
+}
+
+export type PlatformCategoriesResult = {
+    categories: {
+        categories: PlatformCategory[];
+    };
 }
 
 export type PlatformContractInfo = {
@@ -52,10 +78,184 @@ export type PlatformContractInfo = {
     address: string;
 }
 
+// v1 projects indexed by the migration are unindexed until they report a status
+export type PlatformProjectStatus = ProjectStatus | "unindexed";
+
+export type PlatformProjectSummary = {
+    title: string;
+    creator: string;
+    categories: number[];
+    goal: string;
+    deadline: number;
+    token: string;
+    status: PlatformProjectStatus;
+    hidden: boolean;
+    membership: boolean;
+}
+
+export type PlatformDelistReason = "scam" | "illegal" | "spam" | "terms_violation" | "other";
+
+export type PlatformDelisting = {
+    reason: PlatformDelistReason;
+    text: string;
+    height: number;
+    time: number;
+}
+
+export type PlatformProjectInfo = PlatformContractInfo & {
+    project_id: number;
+    summary?: PlatformProjectSummary;
+    delisting?: PlatformDelisting;
+}
+
+export type PlatformProjectsFilter = {
+    category?: number;
+    status?: PlatformProjectStatus;
+    creator?: string;
+    token?: string;
+    hidden?: boolean;
+    delisted?: boolean;
+}
+
 export type PlatformProjectsResult = {
     projects: {
-        projects: PlatformContractInfo[];
+        projects: PlatformProjectInfo[];
         count: number;
+        next_start_after?: number;
+    };
+}
+
+export type PlatformProjectStatsResult = {
+    project_stats: {
+        active: number;
+        successful: number;
+        fundraising: number;
+        succeeded: number;
+        failed: number;
+        cancelled: number;
+        paid_out: number;
+        deadman_expired: number;
+        defaulted: number;
+        delisted: number;
+        hidden: number;
+    };
+}
+
+export type PlatformCreatorStatsResult = {
+    creator_stats: {
+        created: number;
+        active: number;
+        successful: number;
+        unsuccessful: number;
+        success_rate?: number; // per mille
+    };
+}
+
+export type PlatformProfileMsg = {
+    display_name: string;
+    bio?: string;
+    avatar_url?: string;
+    links?: string[];
+    contact_public_key?: string; // base64
+}
+
+export type PlatformProfile = {
+    display_name: string;
+    bio: string;
+    avatar_url?: string;
+    links: string[];
+    contact_public_key?: string; // base64
+}
+
+export type PlatformProfileResult = {
+    profile: {
+        profile?: PlatformProfile;
+    };
+}
+
+export type PlatformCreatorProjectsResult = {
+    creator_projects: {
+        projects: PlatformProjectInfo[];
+        created: number;
+        active: number;
+        successful: number;
+        unsuccessful: number;
+    };
+}
+
+export type PlatformBacking = {
+    project_id: number;
+    address: string;
+    amount: string;
+}
+
+export type PlatformMyProjectsResult = {
+    my_projects: {
+        projects: PlatformBacking[];
+    };
+}
+
+export type PlatformPausedResult = {
+    paused: {
+        paused: boolean;
+    };
+}
+
+export type PlatformRole = "moderator" | "fee_manager" | "round_manager";
+
+export type PlatformRolesResult = {
+    roles: {
+        owner: string;
+        pending_owner?: string;
+        moderators: string[];
+        fee_managers: string[];
+        round_managers: string[];
+    };
+}
+
+export type PlatformAdminLogEntry = {
+    id: number;
+    actor: string;
+    action: string | object; // e.g. "config" or { set_hidden: { project_id, hidden } }
+    height: number;
+    time: number;
+}
+
+export type PlatformAdminLogResult = {
+    admin_log: {
+        entries: PlatformAdminLogEntry[];
+        count: number;
+    };
+}
+
+export type PlatformRound = {
+    id: number;
+    sponsor: string;
+    token: string;
+    pool: string;
+    start: number;
+    end: number;
+    finalized: boolean;
+    project_count: number;
+}
+
+export type PlatformRoundsResult = {
+    rounds: {
+        rounds: PlatformRound[];
+        count: number;
+    };
+}
+
+export type PlatformRoundProject = {
+    idx: number;
+    project_id: number;
+    matched: string;
+    settled: boolean;
+}
+
+export type PlatformRoundProjectsResult = {
+    round_projects: {
+        projects: PlatformRoundProject[];
     };
 }
 
@@ -73,15 +273,165 @@ export class PlatformContractInstance extends ContractInstance {
         return tx;
     }
 
-    async register(secretjs: SecretNetworkClient, registerMsg: PlatformRegisterMsg, gasLimit: number = 250_000): Promise<Tx> {
-        const msg = { register: registerMsg };
+    async queryProjects(secretjs: SecretNetworkClient, start_after?: number, limit: number = 10, order?: SortOrder, filter?: PlatformProjectsFilter): Promise<PlatformProjectsResult> {
+        const query = { projects: { start_after, limit, order, filter } };
+        const result = (await this.query(secretjs, query)) as PlatformProjectsResult;
+        return result;
+    }
+
+    async setCategory(secretjs: SecretNetworkClient, label: string, active: boolean, id?: number, gasLimit: number = 150_000): Promise<Tx> {
+        const msg = { set_category: { id, label, active } };
         const tx = await this.exec(secretjs, msg, gasLimit);
         return tx;
     }
 
-    async queryProjects(secretjs: SecretNetworkClient, page: number = 0, page_size: number = 10): Promise<PlatformProjectsResult> {
-        const query = { projects: { page, page_size } };
-        const result = (await this.query(secretjs, query)) as PlatformProjectsResult;
+    async queryCategories(secretjs: SecretNetworkClient): Promise<PlatformCategoriesResult> {
+        const query = { categories: {} };
+        const result = (await this.query(secretjs, query)) as PlatformCategoriesResult;
+        return result;
+    }
+
+    async setPledgeTokens(secretjs: SecretNetworkClient, token_min_max_pledges: PledgeMinMax[], gasLimit: number = 150_000): Promise<Tx> {
+        const msg = { set_pledge_tokens: { token_min_max_pledges } };
+        const tx = await this.exec(secretjs, msg, gasLimit);
+        return tx;
+    }
+
+    async setRole(secretjs: SecretNetworkClient, address: string, role: PlatformRole, granted: boolean, gasLimit: number = 150_000): Promise<Tx> {
+        const msg = { set_role: { address, role, granted } };
+        const tx = await this.exec(secretjs, msg, gasLimit);
+        return tx;
+    }
+
+    async proposeOwner(secretjs: SecretNetworkClient, owner?: string, gasLimit: number = 150_000): Promise<Tx> {
+        const msg = { propose_owner: { owner } };
+        const tx = await this.exec(secretjs, msg, gasLimit);
+        return tx;
+    }
+
+    async acceptOwner(secretjs: SecretNetworkClient, gasLimit: number = 150_000): Promise<Tx> {
+        const msg = { accept_owner: {} };
+        const tx = await this.exec(secretjs, msg, gasLimit);
+        return tx;
+    }
+
+    async setHidden(secretjs: SecretNetworkClient, project_id: number, hidden: boolean, gasLimit: number = 150_000): Promise<Tx> {
+        const msg = { set_hidden: { project_id, hidden } };
+        const tx = await this.exec(secretjs, msg, gasLimit);
+        return tx;
+    }
+
+    async clearSpamFlags(secretjs: SecretNetworkClient, project_id: number, gasLimit: number = 200_000): Promise<Tx> {
+        const msg = { clear_spam_flags: { project_id } };
+        const tx = await this.exec(secretjs, msg, gasLimit);
+        return tx;
+    }
+
+    async delist(secretjs: SecretNetworkClient, project_id: number, reason: PlatformDelistReason, text: string, gasLimit: number = 250_000): Promise<Tx> {
+        const msg = { delist: { project_id, reason, text } };
+        const tx = await this.exec(secretjs, msg, gasLimit);
+        return tx;
+    }
+
+    async syncStatus(secretjs: SecretNetworkClient, project_ids: number[], gasLimit: number = 400_000): Promise<Tx> {
+        const msg = { sync_status: { project_ids } };
+        const tx = await this.exec(secretjs, msg, gasLimit);
+        return tx;
+    }
+
+    async queryRoles(secretjs: SecretNetworkClient): Promise<PlatformRolesResult> {
+        const query = { roles: {} };
+        const result = (await this.query(secretjs, query)) as PlatformRolesResult;
+        return result;
+    }
+
+    async queryAdminLog(secretjs: SecretNetworkClient, start_after?: number, limit: number = 10, order?: SortOrder): Promise<PlatformAdminLogResult> {
+        const query = { admin_log: { start_after, limit, order } };
+        const result = (await this.query(secretjs, query)) as PlatformAdminLogResult;
+        return result;
+    }
+
+    async setPaused(secretjs: SecretNetworkClient, paused: boolean, gasLimit: number = 100_000): Promise<Tx> {
+        const msg = { set_paused: { paused } };
+        const tx = await this.exec(secretjs, msg, gasLimit);
+        return tx;
+    }
+
+    async queryPaused(secretjs: SecretNetworkClient): Promise<PlatformPausedResult> {
+        const query = { paused: {} };
+        const result = (await this.query(secretjs, query)) as PlatformPausedResult;
+        return result;
+    }
+
+    async queryProjectStats(secretjs: SecretNetworkClient): Promise<PlatformProjectStatsResult> {
+        const query = { project_stats: {} };
+        const result = (await this.query(secretjs, query)) as PlatformProjectStatsResult;
+        return result;
+    }
+
+    async queryCreatorStats(secretjs: SecretNetworkClient, creator: string): Promise<PlatformCreatorStatsResult> {
+        const query = { creator_stats: { creator } };
+        const result = (await this.query(secretjs, query)) as PlatformCreatorStatsResult;
+        return result;
+    }
+
+    async setProfile(secretjs: SecretNetworkClient, profileMsg: PlatformProfileMsg, gasLimit: number = 150_000): Promise<Tx> {
+        const msg = { set_profile: profileMsg };
+        const tx = await this.exec(secretjs, msg, gasLimit);
+        return tx;
+    }
+
+    async queryProfile(secretjs: SecretNetworkClient, address: string): Promise<PlatformProfileResult> {
+        const query = { profile: { address } };
+        const result = (await this.query(secretjs, query)) as PlatformProfileResult;
+        return result;
+    }
+
+    async queryCreatorProjects(secretjs: SecretNetworkClient, creator: string, start_after?: number, limit: number = 10, order?: SortOrder): Promise<PlatformCreatorProjectsResult> {
+        const query = { creator_projects: { creator, start_after, limit, order } };
+        const result = (await this.query(secretjs, query)) as PlatformCreatorProjectsResult;
+        return result;
+    }
+
+    async createRound(secretjs: SecretNetworkClient, token: string, token_hash: string, start: number, end: number, gasLimit: number = 200_000): Promise<Tx> {
+        const msg = { create_round: { token, token_hash, start, end } };
+        const tx = await this.exec(secretjs, msg, gasLimit);
+        return tx;
+    }
+
+    async joinRound(secretjs: SecretNetworkClient, round_id: number, project_id: number, gasLimit: number = 150_000): Promise<Tx> {
+        const msg = { join_round: { round_id, project_id } };
+        const tx = await this.exec(secretjs, msg, gasLimit);
+        return tx;
+    }
+
+    async finalizeRound(secretjs: SecretNetworkClient, round_id: number, gasLimit: number = 1_000_000): Promise<Tx> {
+        const msg = { finalize_round: { round_id } };
+        const tx = await this.exec(secretjs, msg, gasLimit);
+        return tx;
+    }
+
+    async settleMatch(secretjs: SecretNetworkClient, round_id: number, project_id: number, gasLimit: number = 200_000): Promise<Tx> {
+        const msg = { settle_match: { round_id, project_id } };
+        const tx = await this.exec(secretjs, msg, gasLimit);
+        return tx;
+    }
+
+    async queryRounds(secretjs: SecretNetworkClient, start_after?: number, limit: number = 10, order?: SortOrder): Promise<PlatformRoundsResult> {
+        const query = { rounds: { start_after, limit, order } };
+        const result = (await this.query(secretjs, query)) as PlatformRoundsResult;
+        return result;
+    }
+
+    async queryRoundProjects(secretjs: SecretNetworkClient, round_id: number, start_after?: number, limit: number = 10): Promise<PlatformRoundProjectsResult> {
+        const query = { round_projects: { round_id, start_after, limit } };
+        const result = (await this.query(secretjs, query)) as PlatformRoundProjectsResult;
+        return result;
+    }
+
+    async queryMyProjects(secretjs: SecretNetworkClient, permit: Permit): Promise<PlatformMyProjectsResult> {
+        const query = { my_projects: { permit } };
+        const result = (await this.query(secretjs, query)) as PlatformMyProjectsResult;
         return result;
     }
 
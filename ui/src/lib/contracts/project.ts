@@ -41,8 +41,35 @@ export type Snip24RewardInit = {
     minimum_contribution?: string;
     maximum_contribution?: string;
     contribution_weight: number;
+    bonus_curve?: RewardBonusCurve;
+    contributor_linear_vesting?: LinearVesting;
     creator_vesting_schedule: VestingEvent[];
+    creator_linear_vesting?: LinearVesting;
     creator_addresses?: string[];
+    allocation_buckets?: AllocationBucket[];
+}
+
+export type AllocationBucket = {
+    // e.g. treasury, advisors or community
+    name: string;
+    // each vesting event is split evenly among the recipients
+    recipients: string[];
+    vesting_schedule: VestingEvent[];
+}
+
+export type RewardBonusCurve = {
+    // per mille, e.g. 1500 for 1.5x
+    multiplier: number;
+    // last block with the full multiplier, it slides to 1x at the deadline
+    full_until: number;
+}
+
+export type LinearVesting = {
+    amount: string;
+    start: number;
+    // nothing can be claimed before the cliff
+    cliff: number;
+    end: number;
 }
 
 export type VestingEvent = {
@@ -61,9 +88,11 @@ export type ProjectChangeTextMsg = {
     padding?: string;
 }
 
+export type ProjectStatus = "fundraising" | "succeeded" | "failed" | "cancelled" | "paid_out" | "deadman_expired" | "defaulted" | "delisted";
+
 export type ProjectStatusResult = {
     creator: string;
-    status: string;
+    status: ProjectStatus;
     paid_out: boolean;
     goal: string;
     total: string;
@@ -83,7 +112,38 @@ export type ProjectStatusResult = {
     funded_message?: string;
     reward_messages?: ProjectRewardMessage[];
     snip24_rewards?: ProjectVestingRewardStatus[];
+    linear_vesting?: ProjectLinearVestingStatus;
+    bucket_rewards?: ProjectBucketRewardStatus[];
     contribution?: string;
+    membership?: ProjectMembershipInfo;
+    member?: ProjectMember;
+}
+
+export type ProjectMembershipTier = {
+    name: string;
+    // price per period
+    price: string;
+}
+
+export type ProjectMembershipInit = {
+    // period length in blocks
+    period: number;
+    tiers: ProjectMembershipTier[];
+}
+
+export type ProjectMembershipInfo = {
+    period: number;
+    start: number;
+    tiers: ProjectMembershipTier[];
+    current_period: number;
+    closed?: number;
+}
+
+export type ProjectMember = {
+    tier: number;
+    start_period: number;
+    end_period: number;
+    active: boolean;
 }
 
 export type ProjectSnip24Info = {
@@ -99,11 +159,27 @@ export type ProjectSnip24Info = {
     minimum_contribution?: string;
     maximum_contribution?: string;
     contribution_weight: number;
+    bonus_curve?: RewardBonusCurve;
+    contributor_linear_vesting?: LinearVesting;
     creator_vesting_schedule: VestingEvent[];
+    creator_linear_vesting?: LinearVesting;
+    allocation_buckets: AllocationBucket[];
     contract_address?: string;
     contract_hash?: string;
 }
 
+export type ProjectLinearVestingStatus = {
+    total: string;
+    vested: string;
+    claimed: string;
+}
+
+export type ProjectBucketRewardStatus = {
+    bucket: number;
+    name: string;
+    rewards: ProjectVestingRewardStatus[];
+}
+
 export type ProjectVestingRewardStatus = {
     amount: string;
     block: number;
@@ -123,7 +199,10 @@ export type ProjectRewardMessage = {
     message: string;
 }
 
+export type SortOrder = "ascending" | "descending";
+
 export type ProjectComment = {
+    id: number;
     comment: string;
     from_creator: boolean;
 }
@@ -136,6 +215,25 @@ export type ProjectOuterCommentsResult = {
     comments: ProjectCommentsResult;
 }
 
+export type ProjectSponsorMatch = {
+    sponsor: string;
+    // tokens added per thousand pledged by backers
+    ratio: number;
+    cap: string;
+    matched: string;
+    rewarded: boolean;
+    refunded: boolean;
+}
+
+export type ProjectMatchesResult = {
+    matches: ProjectSponsorMatch[];
+    rewarded: boolean;
+}
+
+export type ProjectOuterMatchesResult = {
+    matches: ProjectMatchesResult;
+}
+
 export class ProjectContractInstance extends ContractInstance {
 
     async changeText(secretjs: SecretNetworkClient, changeTextMsg: ProjectChangeTextMsg, gasLimit: number = 150_000): Promise<Tx> {
@@ -174,18 +272,66 @@ export class ProjectContractInstance extends ContractInstance {
         return tx;
     }
 
+    async setMatchRewards(secretjs: SecretNetworkClient, rewarded: boolean, gasLimit: number = 150_000): Promise<Tx> {
+        const msg = { set_match_rewards: { rewarded } };
+        const tx = await this.exec(secretjs, msg, gasLimit);
+        return tx;
+    }
+
+    async refundMatch(secretjs: SecretNetworkClient, gasLimit: number = 300_000): Promise<Tx> {
+        const msg = { refund_match: { } };
+        const tx = await this.exec(secretjs, msg, gasLimit);
+        return tx;
+    }
+
+    async claimMatchReward(secretjs: SecretNetworkClient, idx: number, gasLimit: number = 300_000): Promise<Tx> {
+        const msg = { claim_match_reward: { idx } };
+        const tx = await this.exec(secretjs, msg, gasLimit);
+        return tx;
+    }
+
+    async claimBucketReward(secretjs: SecretNetworkClient, bucket: number, idx: number, gasLimit: number = 300_000): Promise<Tx> {
+        const msg = { claim_bucket_reward: { bucket, idx } };
+        const tx = await this.exec(secretjs, msg, gasLimit);
+        return tx;
+    }
+
+    async claimVested(secretjs: SecretNetworkClient, gasLimit: number = 300_000): Promise<Tx> {
+        const msg = { claim_vested: { } };
+        const tx = await this.exec(secretjs, msg, gasLimit);
+        return tx;
+    }
+
+    async cancelMembership(secretjs: SecretNetworkClient, gasLimit: number = 200_000): Promise<Tx> {
+        const msg = { cancel_membership: { } };
+        const tx = await this.exec(secretjs, msg, gasLimit);
+        return tx;
+    }
+
+    async withdrawMembership(secretjs: SecretNetworkClient, gasLimit: number = 400_000): Promise<Tx> {
+        const msg = { withdraw_membership: { } };
+        const tx = await this.exec(secretjs, msg, gasLimit);
+        return tx;
+    }
+
     async generateViewingKey(secretjs: SecretNetworkClient, gasLimit: number = 150_000): Promise<Tx> {
         const msg = { generate_viewing_key: { entropy: entropy() } };
         const tx = await this.exec(secretjs, msg, gasLimit);
         return tx;
     }
 
-    async queryComments(secretjs: SecretNetworkClient, page: number = 0, page_size: number = 10): Promise<ProjectCommentsResult> {
-        const query = { comments: { page, page_size } };
+    async queryComments(secretjs: SecretNetworkClient, start_after?: number, limit: number = 10, order?: SortOrder): Promise<ProjectCommentsResult> {
+        const query = { comments: { start_after, limit, order } };
         const result = (await this.query(secretjs, query)) as ProjectOuterCommentsResult;
         return result.comments;
     }
 
+    async queryMatches(secretjs: SecretNetworkClient): Promise<ProjectMatchesResult> {
+        const query = { matches: { } };
+        const result = (await this.query(secretjs, query)) as ProjectOuterMatchesResult;
+        return result.matches;
+    }
+
     async queryStatus(secretjs: SecretNetworkClient): Promise<ProjectStatusResult> {
         const query = { status: { } };
         const result = (await this.query(secretjs, query)) as ProjectStatusNoAuthResult;
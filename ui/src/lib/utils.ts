@@ -58,4 +58,9 @@ export const timeUntilDeadline = (currentBlock: number, deadline: number): strin
             return `less than one minute left`;
         }
     }
-}
\ No newline at end of file
+}
+// contract errors start with a stable code in brackets, e.g. "[own_project] Cannot fund your own project"
+export const contractErrorCode = (err: any): string | undefined => {
+    const match = /\[([a-z0-9_]+)\]/.exec(err?.message ?? String(err));
+    return match ? match[1] : undefined;
+}